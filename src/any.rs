@@ -0,0 +1,623 @@
+//! Driver for a device model and mode only known at runtime
+
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+#[cfg(feature = "fixed")]
+use crate::FixedMeasurement;
+use crate::{
+    ic, mode, AutoSleepDataRate, CalibrationOrientation, DataStatus, DebounceCounterMode,
+    DetectedDevice, DetectedIc, DetectionError, DeviceConfig, DriverMode, EnabledInterrupts, Error,
+    Events, EventsError, FfMtSource, FifoMode, FifoStatus, FifoTriggerSources,
+    FreefallMotionConfig, GScale, HighPassConfig, InterruptPinConfiguration, InterruptPinPolarity,
+    InterruptPinRoutes, InterruptStatus, Measurement, MilliGMeasurement, Mma8x5x, ModeChangeError,
+    OffsetCalibration, OutputDataRate, PortraitLandscapeAngles, PortraitLandscapeStatus, PowerMode,
+    PulseConfig, PulseSource, ReadMode, RegisterDump, SelfTestReport, SystemMode, SystemStatus,
+    TransientConfig, TransientSource, UnscaledMeasurement, WakeInterrupts,
+};
+
+/// Driver for any device model in any mode
+///
+/// The device model and mode are stored at runtime instead of in the type.
+/// This is useful when talking to several different devices.
+///
+/// Functions not supported by the device return `Error::Unsupported` and
+/// functions not available in the current mode return `Error::WrongMode`.
+///
+/// It can be created from a [`DetectedDevice`] or from a typed driver
+/// and converted back into a typed driver with `try_into()`.
+#[derive(Debug)]
+pub struct AnyMma8x5x<I2C> {
+    device: Device<I2C>,
+}
+
+#[derive(Debug)]
+enum Device<I2C> {
+    Mma8451(Typed<I2C, ic::Mma8451>),
+    Mma8452(Typed<I2C, ic::Mma8452>),
+    Mma8453(Typed<I2C, ic::Mma8453>),
+    Mma8652(Typed<I2C, ic::Mma8652>),
+    Mma8653(Typed<I2C, ic::Mma8653>),
+}
+
+#[derive(Debug)]
+enum Typed<I2C, IC> {
+    Standby(Mma8x5x<I2C, IC, mode::Standby>),
+    Active(Mma8x5x<I2C, IC, mode::Active>),
+}
+
+/// Call a function on the typed driver
+///
+/// Without a device list, the function is called for all devices.
+/// Otherwise, other devices return `Error::Unsupported`.
+/// With a mode, the function is only called in that mode and
+/// `Error::WrongMode` is returned in the other one.
+macro_rules! dispatch {
+    ($self:ident, $dev:ident => $e:expr) => {
+        dispatch!(@any $self, [Mma8451, Mma8452, Mma8453, Mma8652, Mma8653], $dev => $e, {})
+    };
+    ($self:ident, [$($ic:ident),*], $dev:ident => $e:expr) => {
+        dispatch!(@any $self, [$($ic),*], $dev => $e, { _ => Err(Error::Unsupported) })
+    };
+    ($self:ident, $mode:ident, $dev:ident => $e:expr) => {
+        dispatch!(@$mode $self, [Mma8451, Mma8452, Mma8453, Mma8652, Mma8653], $dev => $e, {})
+    };
+    ($self:ident, $mode:ident [$($ic:ident),*], $dev:ident => $e:expr) => {
+        dispatch!(@$mode $self, [$($ic),*], $dev => $e, { _ => Err(Error::Unsupported) })
+    };
+    (@any $self:ident, [$($ic:ident),*], $dev:ident => $e:expr, { $($rest:tt)* }) => {
+        match &mut $self.device {
+            $(
+                Device::$ic(Typed::Standby($dev)) => $e,
+                Device::$ic(Typed::Active($dev)) => $e,
+            )*
+            $($rest)*
+        }
+    };
+    (@standby $self:ident, [$($ic:ident),*], $dev:ident => $e:expr, { $($rest:tt)* }) => {
+        match &mut $self.device {
+            $(
+                Device::$ic(Typed::Standby($dev)) => $e,
+                Device::$ic(Typed::Active(_)) => Err(Error::WrongMode),
+            )*
+            $($rest)*
+        }
+    };
+    (@active $self:ident, [$($ic:ident),*], $dev:ident => $e:expr, { $($rest:tt)* }) => {
+        match &mut $self.device {
+            $(
+                Device::$ic(Typed::Active($dev)) => $e,
+                Device::$ic(Typed::Standby(_)) => Err(Error::WrongMode),
+            )*
+            $($rest)*
+        }
+    };
+}
+
+/// Change the mode of the typed driver, keeping the driver on errors
+macro_rules! change_mode {
+    ($self:ident, $from:ident, $dev:ident => $e:expr) => {
+        match $self.device {
+            Device::Mma8451(Typed::$from($dev)) => into_any($e),
+            Device::Mma8452(Typed::$from($dev)) => into_any($e),
+            Device::Mma8453(Typed::$from($dev)) => into_any($e),
+            Device::Mma8652(Typed::$from($dev)) => into_any($e),
+            Device::Mma8653(Typed::$from($dev)) => into_any($e),
+            device => Err(ModeChangeError {
+                error: Error::WrongMode,
+                dev: AnyMma8x5x { device },
+            }),
+        }
+    };
+}
+
+fn into_any<E, I2C, NEW, OLD>(
+    result: Result<NEW, ModeChangeError<E, OLD>>,
+) -> Result<AnyMma8x5x<I2C>, ModeChangeError<E, AnyMma8x5x<I2C>>>
+where
+    NEW: Into<AnyMma8x5x<I2C>>,
+    OLD: Into<AnyMma8x5x<I2C>>,
+{
+    result.map(Into::into).map_err(|e| ModeChangeError {
+        error: e.error,
+        dev: e.dev.into(),
+    })
+}
+
+impl<E, I2C> AnyMma8x5x<I2C>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Detect the device connected to the bus.
+    ///
+    /// See [`DetectedDevice::detect()`]. The driver is in standby mode.
+    pub fn detect(i2c: I2C) -> Result<Self, DetectionError<I2C>> {
+        DetectedDevice::detect(i2c).map(Into::into)
+    }
+
+    /// Change mode to active
+    pub fn into_active(self) -> Result<Self, ModeChangeError<E, Self>> {
+        change_mode!(self, Standby, dev => dev.into_active())
+    }
+
+    /// Change mode to standby
+    pub fn into_standby(self) -> Result<Self, ModeChangeError<E, Self>> {
+        change_mode!(self, Active, dev => dev.into_standby())
+    }
+
+    /// Reset (changes mode to standby)
+    pub fn reset(mut self) -> Result<Self, ModeChangeError<E, Self>> {
+        if self.mode() == DriverMode::Active {
+            return change_mode!(self, Active, dev => dev.reset());
+        }
+        match dispatch!(self, standby, dev => dev.reset()) {
+            Ok(()) => Ok(self),
+            Err(error) => Err(ModeChangeError { error, dev: self }),
+        }
+    }
+
+    /// Verify the device and reset it. See [`Mma8x5x::init()`].
+    pub fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.init(delay))
+    }
+
+    /// Read the device ID
+    pub fn device_id(&mut self) -> Result<u8, Error<E>> {
+        dispatch!(self, dev => dev.device_id())
+    }
+
+    /// Read the configuration back from the device into the driver.
+    ///
+    /// See [`Mma8x5x::sync_from_device()`].
+    pub fn sync_from_device(&mut self) -> Result<(), Error<E>> {
+        dispatch!(self, dev => dev.sync_from_device())
+    }
+
+    /// Read all the registers of the device.
+    ///
+    /// See [`Mma8x5x::dump_registers()`].
+    pub fn dump_registers(&mut self) -> Result<RegisterDump, Error<E>> {
+        dispatch!(self, dev => dev.dump_registers())
+    }
+
+    /// Read the current device configuration
+    pub fn read_config(&mut self) -> Result<DeviceConfig, Error<E>> {
+        dispatch!(self, standby, dev => dev.read_config())
+    }
+
+    /// Apply a device configuration. See [`Mma8x5x::apply_config()`].
+    pub fn apply_config(&mut self, config: &DeviceConfig) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.apply_config(config))
+    }
+
+    /// Read unscaled acceleration sensor data
+    pub fn read_unscaled(&mut self) -> Result<UnscaledMeasurement, Error<E>> {
+        dispatch!(self, active, dev => dev.read_unscaled())
+    }
+
+    /// Read the data status and the unscaled acceleration sensor data in a single transaction
+    pub fn read_with_status(&mut self) -> Result<(DataStatus, UnscaledMeasurement), Error<E>> {
+        dispatch!(self, active, dev => dev.read_with_status())
+    }
+
+    /// Read acceleration sensor data scaled to G
+    pub fn read(&mut self) -> Result<Measurement, Error<E>> {
+        dispatch!(self, active, dev => dev.read())
+    }
+
+    /// Read acceleration sensor data in m/s²
+    pub fn read_ms2(&mut self) -> Result<Measurement, Error<E>> {
+        dispatch!(self, active, dev => dev.read_ms2())
+    }
+
+    /// Read acceleration sensor data in milli-g
+    pub fn read_mg(&mut self) -> Result<MilliGMeasurement, Error<E>> {
+        dispatch!(self, active, dev => dev.read_mg())
+    }
+
+    /// Read acceleration sensor data scaled to G in fixed-point
+    #[cfg(feature = "fixed")]
+    pub fn read_fixed(&mut self) -> Result<FixedMeasurement, Error<E>> {
+        dispatch!(self, active, dev => dev.read_fixed())
+    }
+
+    /// Read current system operating mode
+    pub fn system_mode(&mut self) -> Result<SystemMode, Error<E>> {
+        dispatch!(self, dev => dev.system_mode())
+    }
+
+    /// Read current data status
+    pub fn data_status(&mut self) -> Result<DataStatus, Error<E>> {
+        dispatch!(self, dev => dev.data_status())
+    }
+
+    /// Read current interrupt status
+    pub fn interrupt_status(&mut self) -> Result<InterruptStatus, Error<E>> {
+        dispatch!(self, dev => dev.interrupt_status())
+    }
+
+    /// Read the pending interrupt sources and return the decoded events.
+    /// See [`Mma8x5x::next_events()`].
+    pub fn next_events(&mut self) -> Result<Events, EventsError<E>> {
+        dispatch!(self, active, dev => Ok(dev.next_events()))?
+    }
+
+    /// Read current portrait/landscape status
+    pub fn portrait_landscape_status(&mut self) -> Result<PortraitLandscapeStatus, Error<E>> {
+        dispatch!(self, dev => dev.portrait_landscape_status())
+    }
+
+    /// Read current offset correction
+    pub fn offset_correction(&mut self) -> Result<(i8, i8, i8), Error<E>> {
+        dispatch!(self, dev => dev.offset_correction())
+    }
+
+    /// Set G scale
+    pub fn set_scale(&mut self, scale: GScale) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_scale(scale))
+    }
+
+    /// Set read mode
+    pub fn set_read_mode(&mut self, mode: ReadMode) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_read_mode(mode))
+    }
+
+    /// Set offset correction
+    pub fn set_offset_correction(&mut self, x: i8, y: i8, z: i8) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_offset_correction(x, y, z))
+    }
+
+    /// Calibrate the offset correction. See [`Mma8x5x::calibrate_offsets()`].
+    pub fn calibrate_offsets<D: DelayNs>(
+        &mut self,
+        orientation: CalibrationOrientation,
+        delay: &mut D,
+    ) -> Result<OffsetCalibration, Error<E>> {
+        dispatch!(self, standby, dev => dev.calibrate_offsets(orientation, delay))
+    }
+
+    /// Set data rate
+    pub fn set_data_rate(&mut self, rate: OutputDataRate) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_data_rate(rate))
+    }
+
+    /// Set wake power mode
+    pub fn set_wake_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_wake_power_mode(power_mode))
+    }
+
+    /// Enable self-test
+    pub fn enable_self_test(&mut self) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.enable_self_test())
+    }
+
+    /// Disable self-test
+    pub fn disable_self_test(&mut self) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.disable_self_test())
+    }
+
+    /// Run the self-test. See [`Mma8x5x::run_self_test()`].
+    pub fn run_self_test<D: DelayNs>(&mut self, delay: &mut D) -> Result<SelfTestReport, Error<E>> {
+        dispatch!(self, standby, dev => dev.run_self_test(delay))
+    }
+
+    /// Set sleep power mode
+    pub fn set_sleep_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_sleep_power_mode(power_mode))
+    }
+
+    /// Enable auto-sleep
+    pub fn enable_auto_sleep(&mut self) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.enable_auto_sleep())
+    }
+
+    /// Disable auto-sleep
+    pub fn disable_auto_sleep(&mut self) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.disable_auto_sleep())
+    }
+
+    /// Set auto-sleep data rate
+    pub fn set_auto_sleep_data_rate(&mut self, rate: AutoSleepDataRate) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_auto_sleep_data_rate(rate))
+    }
+
+    /// Set auto-sleep count
+    pub fn set_auto_sleep_count(&mut self, count: u8) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_auto_sleep_count(count))
+    }
+
+    /// Enable portrait/landscape detection
+    pub fn enable_portrait_landscape_detection(&mut self) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.enable_portrait_landscape_detection())
+    }
+
+    /// Disable portrait/landscape detection
+    pub fn disable_portrait_landscape_detection(&mut self) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.disable_portrait_landscape_detection())
+    }
+
+    /// Set portrait/landscape debounce counter mode
+    pub fn set_debounce_counter_mode(&mut self, mode: DebounceCounterMode) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_debounce_counter_mode(mode))
+    }
+
+    /// Set portrait/landscape debounce counter
+    ///
+    /// This is only possible in active mode on the MMA8451 and MMA8652.
+    pub fn set_debounce_counter(&mut self, counter: u8) -> Result<(), Error<E>> {
+        match &mut self.device {
+            Device::Mma8451(Typed::Active(dev)) => dev.set_debounce_counter(counter),
+            Device::Mma8652(Typed::Active(dev)) => dev.set_debounce_counter(counter),
+            _ => dispatch!(self, standby, dev => dev.set_debounce_counter(counter)),
+        }
+    }
+
+    /// Set portrait/landscape back/front trip, Z-lockout, threshold and hysteresis angles
+    pub fn set_portrait_landscape_angles(
+        &mut self,
+        angles: PortraitLandscapeAngles,
+    ) -> Result<(), Error<E>> {
+        dispatch!(self, standby [Mma8451, Mma8652], dev => dev.set_portrait_landscape_angles(angles))
+    }
+
+    /// Set interrupt pin polarity
+    pub fn set_interrupt_pin_polarity(
+        &mut self,
+        polarity: InterruptPinPolarity,
+    ) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_interrupt_pin_polarity(polarity))
+    }
+
+    /// Set interrupt pin configuration
+    pub fn set_interrupt_pin_configuration(
+        &mut self,
+        configuration: InterruptPinConfiguration,
+    ) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_interrupt_pin_configuration(configuration))
+    }
+
+    /// Set enabled interrupts
+    pub fn set_enabled_interrupts(&mut self, enabled: EnabledInterrupts) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_enabled_interrupts(enabled))
+    }
+
+    /// Set interrupt source pin routes
+    pub fn set_interrupt_pin_routes(&mut self, routes: InterruptPinRoutes) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_interrupt_pin_routes(routes))
+    }
+
+    /// Set interrupts that wake the device
+    pub fn set_wake_interrupts(&mut self, interrupts: WakeInterrupts) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_wake_interrupts(interrupts))
+    }
+
+    /// Set FIFO mode
+    pub fn set_fifo_mode(&mut self, mode: FifoMode) -> Result<(), Error<E>> {
+        dispatch!(self, standby [Mma8451, Mma8652], dev => dev.set_fifo_mode(mode))
+    }
+
+    /// Set FIFO sample count watermark [0-32]
+    pub fn set_fifo_watermark(&mut self, watermark: u8) -> Result<(), Error<E>> {
+        dispatch!(self, standby [Mma8451, Mma8652], dev => dev.set_fifo_watermark(watermark))
+    }
+
+    /// Set interrupt sources that trigger the FIFO in trigger mode
+    pub fn set_fifo_trigger_sources(
+        &mut self,
+        sources: FifoTriggerSources,
+    ) -> Result<(), Error<E>> {
+        dispatch!(self, standby [Mma8451, Mma8652], dev => dev.set_fifo_trigger_sources(sources))
+    }
+
+    /// Enable the FIFO gate. See [`Mma8x5x::enable_fifo_gate()`].
+    pub fn enable_fifo_gate(&mut self) -> Result<(), Error<E>> {
+        dispatch!(self, standby [Mma8451, Mma8652], dev => dev.enable_fifo_gate())
+    }
+
+    /// Disable the FIFO gate (default)
+    pub fn disable_fifo_gate(&mut self) -> Result<(), Error<E>> {
+        dispatch!(self, standby [Mma8451, Mma8652], dev => dev.disable_fifo_gate())
+    }
+
+    /// Read current FIFO status
+    pub fn fifo_status(&mut self) -> Result<FifoStatus, Error<E>> {
+        dispatch!(self, [Mma8451, Mma8652], dev => dev.fifo_status())
+    }
+
+    /// Read current system status including the FIFO gate error
+    pub fn system_status(&mut self) -> Result<SystemStatus, Error<E>> {
+        dispatch!(self, [Mma8451, Mma8652], dev => dev.system_status())
+    }
+
+    /// Read the unscaled samples stored in the FIFO. See [`Mma8x5x::read_fifo()`].
+    pub fn read_fifo(&mut self, buffer: &mut [UnscaledMeasurement]) -> Result<usize, Error<E>> {
+        dispatch!(self, active [Mma8451, Mma8652], dev => dev.read_fifo(buffer))
+    }
+
+    /// Set transient detection configuration
+    pub fn set_transient_config(&mut self, config: TransientConfig) -> Result<(), Error<E>> {
+        dispatch!(
+            self,
+            standby [Mma8451, Mma8452, Mma8453, Mma8652],
+            dev => dev.set_transient_config(config)
+        )
+    }
+
+    /// Read transient detection source
+    pub fn transient_source(&mut self) -> Result<TransientSource, Error<E>> {
+        dispatch!(self, [Mma8451, Mma8452, Mma8453, Mma8652], dev => dev.transient_source())
+    }
+
+    /// Set pulse (tap) detection configuration
+    pub fn set_pulse_config(&mut self, config: PulseConfig) -> Result<(), Error<E>> {
+        dispatch!(
+            self,
+            standby [Mma8451, Mma8452, Mma8453, Mma8652],
+            dev => dev.set_pulse_config(config)
+        )
+    }
+
+    /// Read pulse (tap) detection source
+    pub fn pulse_source(&mut self) -> Result<PulseSource, Error<E>> {
+        dispatch!(self, [Mma8451, Mma8452, Mma8453, Mma8652], dev => dev.pulse_source())
+    }
+
+    /// Set freefall/motion detection configuration
+    pub fn set_freefall_motion_config(
+        &mut self,
+        config: FreefallMotionConfig,
+    ) -> Result<(), Error<E>> {
+        dispatch!(self, standby, dev => dev.set_freefall_motion_config(config))
+    }
+
+    /// Read freefall/motion detection source
+    pub fn freefall_motion_source(&mut self) -> Result<FfMtSource, Error<E>> {
+        dispatch!(self, dev => dev.freefall_motion_source())
+    }
+
+    /// Set high-pass filter configuration
+    pub fn set_high_pass_filter(&mut self, config: HighPassConfig) -> Result<(), Error<E>> {
+        dispatch!(
+            self,
+            standby [Mma8451, Mma8452, Mma8453, Mma8652],
+            dev => dev.set_high_pass_filter(config)
+        )
+    }
+}
+
+impl<I2C> AnyMma8x5x<I2C> {
+    /// Device model
+    pub fn ic(&self) -> DetectedIc {
+        match self.device {
+            Device::Mma8451(_) => DetectedIc::Mma8451,
+            Device::Mma8452(_) => DetectedIc::Mma8452,
+            Device::Mma8453(_) => DetectedIc::Mma8453,
+            Device::Mma8652(_) => DetectedIc::Mma8652,
+            Device::Mma8653(_) => DetectedIc::Mma8653,
+        }
+    }
+
+    /// I²C address of the device
+    pub fn address(&self) -> u8 {
+        match &self.device {
+            Device::Mma8451(typed) => typed.address(),
+            Device::Mma8452(typed) => typed.address(),
+            Device::Mma8453(typed) => typed.address(),
+            Device::Mma8652(typed) => typed.address(),
+            Device::Mma8653(typed) => typed.address(),
+        }
+    }
+
+    /// Output data rate configured through the driver
+    /// See [`Mma8x5x::data_rate()`].
+    pub fn data_rate(&self) -> OutputDataRate {
+        match &self.device {
+            Device::Mma8451(typed) => typed.data_rate(),
+            Device::Mma8452(typed) => typed.data_rate(),
+            Device::Mma8453(typed) => typed.data_rate(),
+            Device::Mma8652(typed) => typed.data_rate(),
+            Device::Mma8653(typed) => typed.data_rate(),
+        }
+    }
+
+    /// Current driver mode
+    pub fn mode(&self) -> DriverMode {
+        match &self.device {
+            Device::Mma8451(typed) => typed.mode(),
+            Device::Mma8452(typed) => typed.mode(),
+            Device::Mma8453(typed) => typed.mode(),
+            Device::Mma8652(typed) => typed.mode(),
+            Device::Mma8653(typed) => typed.mode(),
+        }
+    }
+
+    /// Destroy driver instance, return I²C bus instance.
+    pub fn destroy(self) -> I2C {
+        match self.device {
+            Device::Mma8451(typed) => typed.destroy(),
+            Device::Mma8452(typed) => typed.destroy(),
+            Device::Mma8453(typed) => typed.destroy(),
+            Device::Mma8652(typed) => typed.destroy(),
+            Device::Mma8653(typed) => typed.destroy(),
+        }
+    }
+}
+
+impl<I2C, IC> Typed<I2C, IC> {
+    fn mode(&self) -> DriverMode {
+        match self {
+            Typed::Standby(_) => DriverMode::Standby,
+            Typed::Active(_) => DriverMode::Active,
+        }
+    }
+
+    fn address(&self) -> u8 {
+        match self {
+            Typed::Standby(dev) => dev.address,
+            Typed::Active(dev) => dev.address,
+        }
+    }
+
+    fn data_rate(&self) -> OutputDataRate {
+        match self {
+            Typed::Standby(dev) => dev.data_rate(),
+            Typed::Active(dev) => dev.data_rate(),
+        }
+    }
+
+    fn destroy(self) -> I2C {
+        match self {
+            Typed::Standby(dev) => dev.destroy(),
+            Typed::Active(dev) => dev.destroy(),
+        }
+    }
+}
+
+macro_rules! conversion_impl {
+    ($ic:ident, $mode:ident) => {
+        impl<I2C> From<Mma8x5x<I2C, ic::$ic, mode::$mode>> for AnyMma8x5x<I2C> {
+            fn from(dev: Mma8x5x<I2C, ic::$ic, mode::$mode>) -> Self {
+                AnyMma8x5x {
+                    device: Device::$ic(Typed::$mode(dev)),
+                }
+            }
+        }
+
+        impl<I2C> TryFrom<AnyMma8x5x<I2C>> for Mma8x5x<I2C, ic::$ic, mode::$mode> {
+            /// The driver is returned if the device model or mode do not match.
+            type Error = AnyMma8x5x<I2C>;
+
+            fn try_from(any: AnyMma8x5x<I2C>) -> Result<Self, Self::Error> {
+                match any.device {
+                    Device::$ic(Typed::$mode(dev)) => Ok(dev),
+                    device => Err(AnyMma8x5x { device }),
+                }
+            }
+        }
+    };
+}
+
+conversion_impl!(Mma8451, Standby);
+conversion_impl!(Mma8451, Active);
+conversion_impl!(Mma8452, Standby);
+conversion_impl!(Mma8452, Active);
+conversion_impl!(Mma8453, Standby);
+conversion_impl!(Mma8453, Active);
+conversion_impl!(Mma8652, Standby);
+conversion_impl!(Mma8652, Active);
+conversion_impl!(Mma8653, Standby);
+conversion_impl!(Mma8653, Active);
+
+impl<I2C> From<DetectedDevice<I2C>> for AnyMma8x5x<I2C> {
+    fn from(device: DetectedDevice<I2C>) -> Self {
+        match device {
+            DetectedDevice::Mma8451(dev) => dev.into(),
+            DetectedDevice::Mma8452(dev) => dev.into(),
+            DetectedDevice::Mma8453(dev) => dev.into(),
+            DetectedDevice::Mma8652(dev) => dev.into(),
+            DetectedDevice::Mma8653(dev) => dev.into(),
+        }
+    }
+}
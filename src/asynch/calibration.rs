@@ -0,0 +1,61 @@
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use super::Mma8x5xAsync;
+use crate::{
+    common::calibration::{get_offset_calibration, CALIBRATION_SAMPLES},
+    ic::private::Sealed,
+    mode,
+    register_access::Register,
+    CalibrationOrientation, Error, OffsetCalibration,
+};
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Calibrate the offset correction.
+    ///
+    /// See [`Mma8x5x::calibrate_offsets()`](crate::Mma8x5x::calibrate_offsets).
+    pub async fn calibrate_offsets<D: DelayNs>(
+        &mut self,
+        orientation: CalibrationOrientation,
+        delay: &mut D,
+    ) -> Result<OffsetCalibration, Error<E>> {
+        let mut previous = [0; 3];
+        self.dev
+            .i2c
+            .write_read(self.dev.address, &[Register::OFF_X], &mut previous)
+            .await
+            .map_err(Error::I2C)?;
+        self.set_offset_correction(0, 0, 0).await?;
+        let result = self.calibration_sequence(delay).await;
+        let restored = self.restore_configuration().await;
+        let sum = match result.and_then(|sum| restored.map(|_| sum)) {
+            Ok(sum) => sum,
+            Err(e) => {
+                // Report the original error also if this fails.
+                let [x, y, z] = previous;
+                let _ = self.set_offset_correction(x as i8, y as i8, z as i8).await;
+                return Err(e);
+            }
+        };
+        let counts_per_g = f32::from(1_u16 << IC::SENSITIVITY_SHIFT);
+        let calibration =
+            get_offset_calibration(sum, CALIBRATION_SAMPLES, counts_per_g, orientation);
+        self.set_offset_correction(calibration.x, calibration.y, calibration.z)
+            .await?;
+        Ok(calibration)
+    }
+
+    async fn calibration_sequence<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<[i32; 3], Error<E>> {
+        self.prepare_sampling().await?;
+        self.sample_output(CALIBRATION_SAMPLES, delay).await
+    }
+}
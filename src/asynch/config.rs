@@ -0,0 +1,259 @@
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+use super::Mma8x5xAsync;
+use crate::{
+    common::{
+        config::{
+            get_data_rate_config, get_read_mode_config, get_scale_config,
+            get_wake_power_mode_config,
+        },
+        config_auto_sleep::{get_auto_sleep_data_rate_config, get_sleep_power_mode_config},
+        config_int::{
+            get_enabled_int_reg, get_int_routes_reg, get_pin_configuration_config,
+            get_pin_polarity_config, get_wake_ints_config,
+        },
+        config_pl::get_debounce_counter_mode_config,
+        freefall_motion::{get_ff_mt_regs, get_ff_mt_source},
+    },
+    conversion::counter_time_step_ms,
+    mode,
+    register_access::{BitFlags, Register},
+    AutoSleepDataRate, Config, DebounceCounterMode, EnabledInterrupts, Error, FfMtSource,
+    FreefallMotionConfig, GScale, InterruptPinConfiguration, InterruptPinPolarity,
+    InterruptPinRoutes, OutputDataRate, PowerMode, ReadMode, WakeInterrupts,
+};
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Set G scale: +/-2g, +/-4g, +/-8g
+    pub async fn set_scale(&mut self, scale: GScale) -> Result<(), Error<E>> {
+        let config = get_scale_config(self.dev.xyz_data_cfg, scale);
+        self.write_reg(Register::XYZ_DATA_CFG, config.bits).await?;
+        self.dev.xyz_data_cfg = config;
+        Ok(())
+    }
+
+    /// Set read mode (Normal/Fast)
+    pub async fn set_read_mode(&mut self, mode: ReadMode) -> Result<(), Error<E>> {
+        let config = get_read_mode_config(self.dev.ctrl_reg1, mode);
+        self.write_reg(Register::CTRL_REG1, config.bits).await?;
+        self.dev.ctrl_reg1 = config;
+        Ok(())
+    }
+
+    /// Set offset correction.
+    ///
+    /// The resolution is 1.96/LSB. The offset compensation range is +/-250mg.
+    pub async fn set_offset_correction(&mut self, x: i8, y: i8, z: i8) -> Result<(), Error<E>> {
+        self.dev
+            .i2c
+            .write(
+                self.dev.address,
+                &[Register::OFF_X, x as u8, y as u8, z as u8],
+            )
+            .await
+            .map_err(Error::I2C)
+    }
+
+    /// Set output data rate in WAKE mode
+    pub async fn set_data_rate(&mut self, rate: OutputDataRate) -> Result<(), Error<E>> {
+        let config = get_data_rate_config(self.dev.ctrl_reg1, rate);
+        self.write_reg(Register::CTRL_REG1, config.bits).await?;
+        self.dev.ctrl_reg1 = config;
+        Ok(())
+    }
+
+    /// Set power mode in WAKE mode
+    pub async fn set_wake_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Error<E>> {
+        let config = get_wake_power_mode_config(self.dev.ctrl_reg2, power_mode);
+        self.write_reg(Register::CTRL_REG2, config.bits).await?;
+        self.dev.ctrl_reg2 = config;
+        Ok(())
+    }
+
+    /// Reset device
+    pub async fn reset(&mut self) -> Result<(), Error<E>> {
+        self.reset_internal().await
+    }
+
+    /// Enable self-test mode
+    pub async fn enable_self_test(&mut self) -> Result<(), Error<E>> {
+        let config = self.dev.ctrl_reg2.with_high(BitFlags::ST);
+        self.write_reg(Register::CTRL_REG2, config.bits).await?;
+        self.dev.ctrl_reg2 = config;
+        Ok(())
+    }
+
+    /// Disable self-test mode
+    pub async fn disable_self_test(&mut self) -> Result<(), Error<E>> {
+        let config = self.dev.ctrl_reg2.with_low(BitFlags::ST);
+        self.write_reg(Register::CTRL_REG2, config.bits).await?;
+        self.dev.ctrl_reg2 = config;
+        Ok(())
+    }
+
+    /// Set power mode in sleep mode
+    pub async fn set_sleep_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Error<E>> {
+        let config = get_sleep_power_mode_config(self.dev.ctrl_reg2, power_mode);
+        self.write_reg(Register::CTRL_REG2, config.bits).await?;
+        self.dev.ctrl_reg2 = config;
+        Ok(())
+    }
+
+    /// Enable auto-sleep mode
+    pub async fn enable_auto_sleep(&mut self) -> Result<(), Error<E>> {
+        let config = self.dev.ctrl_reg2.with_high(BitFlags::SLPE);
+        self.write_reg(Register::CTRL_REG2, config.bits).await?;
+        self.dev.ctrl_reg2 = config;
+        Ok(())
+    }
+
+    /// Disable auto-sleep mode
+    pub async fn disable_auto_sleep(&mut self) -> Result<(), Error<E>> {
+        let config = self.dev.ctrl_reg2.with_low(BitFlags::SLPE);
+        self.write_reg(Register::CTRL_REG2, config.bits).await?;
+        self.dev.ctrl_reg2 = config;
+        Ok(())
+    }
+
+    /// Set sampling rate used in auto-sleep mode
+    pub async fn set_auto_sleep_data_rate(
+        &mut self,
+        rate: AutoSleepDataRate,
+    ) -> Result<(), Error<E>> {
+        let config = get_auto_sleep_data_rate_config(self.dev.ctrl_reg1, rate);
+        self.write_reg(Register::CTRL_REG1, config.bits).await?;
+        self.dev.ctrl_reg1 = config;
+        Ok(())
+    }
+
+    /// Set auto-sleep/wake count
+    pub async fn set_auto_sleep_count(&mut self, count: u8) -> Result<(), Error<E>> {
+        self.write_reg(Register::ASLP_COUNT, count).await
+    }
+
+    /// Set interrupt pin polarity
+    pub async fn set_interrupt_pin_polarity(
+        &mut self,
+        polarity: InterruptPinPolarity,
+    ) -> Result<(), Error<E>> {
+        let config = get_pin_polarity_config(self.dev.ctrl_reg3, polarity);
+        self.write_reg(Register::CTRL_REG3, config.bits).await?;
+        self.dev.ctrl_reg3 = config;
+        Ok(())
+    }
+
+    /// Set interrupt pin configuration
+    pub async fn set_interrupt_pin_configuration(
+        &mut self,
+        configuration: InterruptPinConfiguration,
+    ) -> Result<(), Error<E>> {
+        let config = get_pin_configuration_config(self.dev.ctrl_reg3, configuration);
+        self.write_reg(Register::CTRL_REG3, config.bits).await?;
+        self.dev.ctrl_reg3 = config;
+        Ok(())
+    }
+
+    /// Set enabled interrupts
+    pub async fn set_enabled_interrupts(
+        &mut self,
+        enabled: EnabledInterrupts,
+    ) -> Result<(), Error<E>> {
+        let config = Config {
+            bits: get_enabled_int_reg(enabled),
+        };
+        self.write_reg(Register::CTRL_REG4, config.bits).await?;
+        self.dev.ctrl_reg4 = config;
+        Ok(())
+    }
+
+    /// Set interrupt source pin routes
+    pub async fn set_interrupt_pin_routes(
+        &mut self,
+        routes: InterruptPinRoutes,
+    ) -> Result<(), Error<E>> {
+        let config = Config {
+            bits: get_int_routes_reg(routes),
+        };
+        self.write_reg(Register::CTRL_REG5, config.bits).await?;
+        self.dev.ctrl_reg5 = config;
+        Ok(())
+    }
+
+    /// Set interrupts that wake the device
+    pub async fn set_wake_interrupts(
+        &mut self,
+        interrupts: WakeInterrupts,
+    ) -> Result<(), Error<E>> {
+        let config = get_wake_ints_config(self.dev.ctrl_reg3, interrupts);
+        self.write_reg(Register::CTRL_REG3, config.bits).await?;
+        self.dev.ctrl_reg3 = config;
+        Ok(())
+    }
+
+    /// Enable portrait/landscape detection
+    pub async fn enable_portrait_landscape_detection(&mut self) -> Result<(), Error<E>> {
+        let config = self.dev.pl_cfg.with_high(BitFlags::PL_EN);
+        self.write_reg(Register::PL_CFG, config.bits).await?;
+        self.dev.pl_cfg = config;
+        Ok(())
+    }
+
+    /// Disable portrait/landscape detection
+    pub async fn disable_portrait_landscape_detection(&mut self) -> Result<(), Error<E>> {
+        let config = self.dev.pl_cfg.with_low(BitFlags::PL_EN);
+        self.write_reg(Register::PL_CFG, config.bits).await?;
+        self.dev.pl_cfg = config;
+        Ok(())
+    }
+
+    /// Set portrait/landscape debounce counter mode
+    pub async fn set_debounce_counter_mode(
+        &mut self,
+        mode: DebounceCounterMode,
+    ) -> Result<(), Error<E>> {
+        let config = get_debounce_counter_mode_config(self.dev.pl_cfg, mode);
+        self.write_reg(Register::PL_CFG, config.bits).await?;
+        self.dev.pl_cfg = config;
+        Ok(())
+    }
+
+    /// Set portrait/landscape debounce counter
+    pub async fn set_debounce_counter(&mut self, counter: u8) -> Result<(), Error<E>> {
+        self.write_reg(Register::PL_COUNT, counter).await
+    }
+
+    /// Set freefall/motion detection configuration
+    ///
+    /// The debounce time is converted into counts based on the currently
+    /// configured output data rate and wake power mode so these should
+    /// be set beforehand.
+    pub async fn set_freefall_motion_config(
+        &mut self,
+        config: FreefallMotionConfig,
+    ) -> Result<(), Error<E>> {
+        let step = counter_time_step_ms(self.dev.data_rate(), self.dev.wake_power_mode());
+        let (cfg, payload) = get_ff_mt_regs(&config, step).ok_or(Error::InvalidInputData)?;
+        self.write_reg(Register::FF_MT_CFG, cfg).await?;
+        self.dev
+            .i2c
+            .write(self.dev.address, &payload)
+            .await
+            .map_err(Error::I2C)
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5xAsync<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Read freefall/motion detection source
+    ///
+    /// Reading this clears the freefall/motion interrupt and the latched events.
+    pub async fn freefall_motion_source(&mut self) -> Result<FfMtSource, Error<E>> {
+        let src = self.read_reg(Register::FF_MT_SRC).await?;
+        Ok(get_ff_mt_source(src))
+    }
+}
@@ -0,0 +1,50 @@
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+use super::Mma8x5xAsync;
+use crate::{
+    common::device_config::get_config_blocks, ic::private::Sealed, mode, DeviceConfig, Error,
+};
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the current device configuration.
+    pub async fn read_config(&mut self) -> Result<DeviceConfig, Error<E>> {
+        let mut registers = DeviceConfig::default().to_registers();
+        for (register, len) in get_config_blocks(IC::MODEL) {
+            let start = usize::from(register);
+            self.dev
+                .i2c
+                .write_read(
+                    self.dev.address,
+                    &[register],
+                    &mut registers[start..start + len],
+                )
+                .await
+                .map_err(Error::I2C)?;
+        }
+        Ok(DeviceConfig::from_registers(&registers))
+    }
+
+    /// Apply a device configuration.
+    ///
+    /// See [`Mma8x5x::apply_config()`](crate::Mma8x5x::apply_config).
+    pub async fn apply_config(&mut self, config: &DeviceConfig) -> Result<(), Error<E>> {
+        let registers = config.to_registers();
+        for (register, len) in get_config_blocks(IC::MODEL) {
+            let start = usize::from(register);
+            let mut payload = [0; 8];
+            payload[0] = register;
+            payload[1..=len].copy_from_slice(&registers[start..start + len]);
+            self.dev
+                .i2c
+                .write(self.dev.address, &payload[..=len])
+                .await
+                .map_err(Error::I2C)?;
+        }
+        self.dev.set_config_state(config);
+        Ok(())
+    }
+}
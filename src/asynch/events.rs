@@ -0,0 +1,47 @@
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+use super::Mma8x5xAsync;
+use crate::{
+    ic::private::Sealed,
+    interrupt_handler::SOURCES,
+    mode,
+    register_access::{BitFlags, Register},
+    Error, Event, Events, EventsError,
+};
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the pending interrupt sources and return the decoded events.
+    ///
+    /// Reads the interrupt source and then the source register of each
+    /// pending interrupt exactly once, which clears it. The sources are read
+    /// in the order: auto-sleep, FIFO, transient, portrait/landscape, pulse,
+    /// freefall/motion and data ready.
+    ///
+    /// To tell freefall and motion events apart, the freefall/motion
+    /// configuration is read as well if such an event is pending.
+    ///
+    /// If a read fails, no further sources are read and the error contains
+    /// the events decoded until then.
+    pub async fn next_events(&mut self) -> Result<Events, EventsError<E>> {
+        let int_src = self.read_reg(Register::INT_SOURCE).await?;
+        let mut events = Events::default();
+        for source in SOURCES.into_iter().filter(|source| (int_src & source) != 0) {
+            match self.read_source_event(source).await {
+                Ok((event, motion)) => events.decode(event, motion),
+                Err(error) => return Err(EventsError { error, events }),
+            }
+        }
+        Ok(events)
+    }
+
+    /// Read the event of a source and whether a freefall/motion event is a motion event.
+    async fn read_source_event(&mut self, source: u8) -> Result<(Event, bool), Error<E>> {
+        let motion = source == BitFlags::SRC_FF_MT
+            && (self.read_reg(Register::FF_MT_CFG).await? & BitFlags::OAE) != 0;
+        Ok((self.read_event(source).await?, motion))
+    }
+}
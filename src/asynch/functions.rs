@@ -0,0 +1,264 @@
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+use super::Mma8x5xAsync;
+use crate::{
+    common::{
+        config_pl::{get_bf_zcomp_reg, get_pl_ths_reg},
+        fifo::{
+            get_fifo_mode_config, get_fifo_status, get_fifo_watermark_config, get_system_status,
+            get_trigger_sources_reg, FIFO_SIZE,
+        },
+        high_pass_filter::get_high_pass_filter_configs,
+        pulse::{get_pulse_regs, get_pulse_source},
+        transient::{get_transient_regs, get_transient_source},
+    },
+    conversion::{counter_time_step_ms, pulse_time_step_ms, raw_from_bytes},
+    ic, mode,
+    register_access::{BitFlags, Register},
+    Error, FifoMode, FifoStatus, FifoTriggerSources, HighPassConfig, PortraitLandscapeAngles,
+    PulseConfig, PulseSource, SystemStatus, TransientConfig, TransientSource, UnscaledMeasurement,
+};
+
+/// FIFO configuration
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasFifo,
+{
+    /// Set FIFO mode
+    pub async fn set_fifo_mode(&mut self, mode: FifoMode) -> Result<(), Error<E>> {
+        let config = get_fifo_mode_config(self.dev.f_setup, mode);
+        self.write_reg(Register::F_SETUP, config.bits).await?;
+        self.dev.f_setup = config;
+        Ok(())
+    }
+
+    /// Set FIFO sample count watermark [0-32]
+    ///
+    /// A value of 0 disables the watermark.
+    pub async fn set_fifo_watermark(&mut self, watermark: u8) -> Result<(), Error<E>> {
+        let config = get_fifo_watermark_config(self.dev.f_setup, watermark)
+            .ok_or(Error::InvalidInputData)?;
+        self.write_reg(Register::F_SETUP, config.bits).await?;
+        self.dev.f_setup = config;
+        Ok(())
+    }
+
+    /// Set interrupt sources that trigger the FIFO in trigger mode
+    pub async fn set_fifo_trigger_sources(
+        &mut self,
+        sources: FifoTriggerSources,
+    ) -> Result<(), Error<E>> {
+        self.write_reg(Register::TRIG_CFG, get_trigger_sources_reg(sources))
+            .await
+    }
+
+    /// Enable the FIFO gate
+    ///
+    /// The FIFO input is blocked on wake/sleep transitions until the FIFO
+    /// has been read, instead of flushing it.
+    pub async fn enable_fifo_gate(&mut self) -> Result<(), Error<E>> {
+        let config = self.dev.ctrl_reg3.with_high(BitFlags::FGATE);
+        self.write_reg(Register::CTRL_REG3, config.bits).await?;
+        self.dev.ctrl_reg3 = config;
+        Ok(())
+    }
+
+    /// Disable the FIFO gate (default)
+    ///
+    /// The FIFO is flushed on wake/sleep transitions.
+    pub async fn disable_fifo_gate(&mut self) -> Result<(), Error<E>> {
+        let config = self.dev.ctrl_reg3.with_low(BitFlags::FGATE);
+        self.write_reg(Register::CTRL_REG3, config.bits).await?;
+        self.dev.ctrl_reg3 = config;
+        Ok(())
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5xAsync<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasFifo,
+{
+    /// Read current FIFO status
+    ///
+    /// Note: This is only valid while the FIFO is enabled.
+    pub async fn fifo_status(&mut self) -> Result<FifoStatus, Error<E>> {
+        let st = self.read_reg(Register::STATUS).await?;
+        Ok(get_fifo_status(st))
+    }
+
+    /// Read current system status including the FIFO gate error
+    pub async fn system_status(&mut self) -> Result<SystemStatus, Error<E>> {
+        let sysmod = self.read_reg(Register::SYSMOD).await?;
+        Ok(get_system_status(sysmod))
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasFifo,
+{
+    /// Read the unscaled samples stored in the FIFO into the provided buffer.
+    ///
+    /// Reads as many samples as are available and fit in the buffer
+    /// in a single burst and returns the number of samples read.
+    pub async fn read_fifo(
+        &mut self,
+        buffer: &mut [UnscaledMeasurement],
+    ) -> Result<usize, Error<E>> {
+        let status = self.fifo_status().await?;
+        let count = usize::from(status.count).min(FIFO_SIZE).min(buffer.len());
+        if count == 0 {
+            return Ok(0);
+        }
+        let fast_read = self.dev.ctrl_reg1.is_high(BitFlags::F_READ);
+        let sample_len = if fast_read { 3 } else { 6 };
+        let mut data = [0; FIFO_SIZE * 6];
+        let data = &mut data[..count * sample_len];
+        self.dev
+            .i2c
+            .write_read(self.dev.address, &[Register::OUT_X_H], data)
+            .await
+            .map_err(Error::I2C)?;
+        for (m, sample) in buffer.iter_mut().zip(data.chunks(sample_len)) {
+            let (x, y, z) = raw_from_bytes(sample, fast_read);
+            *m = IC::convert(x, y, z);
+        }
+        Ok(count)
+    }
+}
+
+/// Transient detection configuration
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasTransient,
+{
+    /// Set transient detection configuration
+    ///
+    /// The debounce time is converted into counts based on the currently
+    /// configured output data rate and wake power mode so these should
+    /// be set beforehand.
+    pub async fn set_transient_config(&mut self, config: TransientConfig) -> Result<(), Error<E>> {
+        let step = counter_time_step_ms(self.dev.data_rate(), self.dev.wake_power_mode());
+        let (cfg, ths, count) = get_transient_regs(&config, step).ok_or(Error::InvalidInputData)?;
+        self.write_reg(Register::TRANSIENT_CFG, cfg).await?;
+        self.write_reg(Register::TRANSIENT_THS, ths).await?;
+        self.write_reg(Register::TRANSIENT_COUNT, count).await
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5xAsync<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasTransient,
+{
+    /// Read transient detection source
+    ///
+    /// Reading this clears the transient interrupt and the latched events.
+    pub async fn transient_source(&mut self) -> Result<TransientSource, Error<E>> {
+        let src = self.read_reg(Register::TRANSIENT_SRC).await?;
+        Ok(get_transient_source(src))
+    }
+}
+
+/// Pulse (tap) detection configuration
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasPulse,
+{
+    /// Set pulse (tap) detection configuration
+    ///
+    /// The times are converted into counts based on the currently
+    /// configured output data rate, wake power mode and pulse low-pass
+    /// filter setting so these should be set beforehand.
+    pub async fn set_pulse_config(&mut self, config: PulseConfig) -> Result<(), Error<E>> {
+        let step = pulse_time_step_ms(
+            self.dev.data_rate(),
+            self.dev.wake_power_mode(),
+            self.dev.hp_filter_cutoff.is_high(BitFlags::PULSE_LPF_EN),
+        );
+        let (cfg, payload) = get_pulse_regs(&config, step).ok_or(Error::InvalidInputData)?;
+        self.write_reg(Register::PULSE_CFG, cfg).await?;
+        self.dev
+            .i2c
+            .write(self.dev.address, &payload)
+            .await
+            .map_err(Error::I2C)
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5xAsync<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasPulse,
+{
+    /// Read pulse (tap) detection source
+    ///
+    /// Reading this clears the pulse interrupt and the latched events.
+    pub async fn pulse_source(&mut self) -> Result<PulseSource, Error<E>> {
+        let src = self.read_reg(Register::PULSE_SRC).await?;
+        Ok(get_pulse_source(src))
+    }
+}
+
+/// High-pass filter configuration
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasHighPassFilter,
+{
+    /// Set high-pass filter configuration
+    ///
+    /// When the output is enabled, the acceleration data returned by
+    /// [`read()`](#method.read) and [`read_unscaled()`](#method.read_unscaled)
+    /// is high-pass filtered.
+    pub async fn set_high_pass_filter(&mut self, config: HighPassConfig) -> Result<(), Error<E>> {
+        let (xyz_data_cfg, hp_filter_cutoff) =
+            get_high_pass_filter_configs(self.dev.xyz_data_cfg, &config);
+        self.write_reg(Register::XYZ_DATA_CFG, xyz_data_cfg.bits)
+            .await?;
+        self.dev.xyz_data_cfg = xyz_data_cfg;
+        self.write_reg(Register::HP_FILTER_CUTOFF, hp_filter_cutoff.bits)
+            .await?;
+        self.dev.hp_filter_cutoff = hp_filter_cutoff;
+        Ok(())
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasProgrammableOrientation,
+{
+    /// Set portrait/landscape back/front trip, Z-lockout, threshold and hysteresis angles
+    pub async fn set_portrait_landscape_angles(
+        &mut self,
+        angles: PortraitLandscapeAngles,
+    ) -> Result<(), Error<E>> {
+        let payload = [
+            Register::PL_BF_ZCOMP,
+            get_bf_zcomp_reg(angles.back_front_trip, angles.z_lockout),
+            get_pl_ths_reg(angles.threshold, angles.hysteresis),
+        ];
+        self.dev
+            .i2c
+            .write(self.dev.address, &payload)
+            .await
+            .map_err(Error::I2C)
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasProgrammableOrientation,
+{
+    /// Set portrait/landscape debounce counter
+    pub async fn set_debounce_counter(&mut self, counter: u8) -> Result<(), Error<E>> {
+        self.write_reg(Register::PL_COUNT, counter).await
+    }
+}
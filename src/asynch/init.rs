@@ -0,0 +1,65 @@
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use super::Mma8x5xAsync;
+use crate::{
+    common::init::RESET_POLL_ATTEMPTS,
+    ic, mode,
+    register_access::{BitFlags, Register},
+    DetectedIc, Error,
+};
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    async fn init_internal<D: DelayNs>(
+        &mut self,
+        expected_id: u8,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        let id = self.device_id().await?;
+        if id != expected_id {
+            return Err(Error::InvalidDevice(id));
+        }
+        self.reset_internal().await?;
+        // The device may not acknowledge while it reboots.
+        let mut result = Err(Error::ResetTimeout);
+        for _ in 0..RESET_POLL_ATTEMPTS {
+            delay.delay_ms(1).await;
+            result = match self.read_reg(Register::CTRL_REG2).await {
+                Ok(ctrl_reg2) if (ctrl_reg2 & BitFlags::RST) == 0 => return Ok(()),
+                Ok(_) => Err(Error::ResetTimeout),
+                Err(e) => Err(e),
+            };
+        }
+        result
+    }
+}
+
+macro_rules! init_impl {
+    ($ic:ident) => {
+        impl<E, I2C> Mma8x5xAsync<I2C, ic::$ic, mode::Standby>
+        where
+            I2C: I2c<SevenBitAddress, Error = E>,
+        {
+            /// Verify the device and reset it.
+            ///
+            /// Reads the device ID (Who am I) and returns `Error::InvalidDevice`
+            /// if it does not correspond to this device model.
+            /// Then resets the device and waits until the reset has finished,
+            /// which leaves the device and the driver in a known state.
+            pub async fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), Error<E>> {
+                self.init_internal(DetectedIc::$ic.device_id(), delay).await
+            }
+        }
+    };
+}
+
+init_impl!(Mma8451);
+init_impl!(Mma8452);
+init_impl!(Mma8453);
+init_impl!(Mma8652);
+init_impl!(Mma8653);
@@ -0,0 +1,77 @@
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+use super::Mma8x5xAsync;
+use crate::{
+    common::{fifo::get_fifo_status, pulse::get_pulse_source, transient::get_transient_source},
+    ic::private::Sealed,
+    interrupt_handler::get_next_source,
+    mode,
+    register_access::{BitFlags, Register},
+    Error, Event, InputPins, InterruptHandler,
+};
+
+impl<PINS: InputPins> InterruptHandler<PINS> {
+    /// Service a pending interrupt with the asynchronous driver
+    ///
+    /// See [`service()`](#method.service).
+    pub async fn service_async<E, I2C, IC>(
+        &mut self,
+        sensor: &mut Mma8x5xAsync<I2C, IC, mode::Active>,
+    ) -> Result<Option<Event>, Error<E>>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        IC: Sealed,
+    {
+        let pending = self.pending_sources()?;
+        if pending == 0 {
+            return Ok(None);
+        }
+        let int_src = sensor.read_reg(Register::INT_SOURCE).await?;
+        match get_next_source(int_src & pending) {
+            Some(source) => sensor.read_event(source).await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<I2C, IC, MODE> Mma8x5xAsync<I2C, IC, MODE> {
+    /// Create an interrupt handler owning the given interrupt pins.
+    ///
+    /// The interrupt pin polarity, enabled interrupts and pin routes are
+    /// taken from the current configuration, so the interrupts should be
+    /// configured beforehand.
+    pub fn interrupt_handler<PINS: InputPins>(&self, pins: PINS) -> InterruptHandler<PINS> {
+        self.dev.interrupt_handler(pins)
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the source register of the given interrupt source, which clears it.
+    pub(crate) async fn read_event(&mut self, source: u8) -> Result<Event, Error<E>> {
+        let event = match source {
+            BitFlags::SRC_ASLP => Event::AutoSleep(self.system_mode().await?),
+            BitFlags::SRC_FIFO => {
+                Event::Fifo(get_fifo_status(self.read_reg(Register::STATUS).await?))
+            }
+            BitFlags::SRC_TRANS => Event::Transient(get_transient_source(
+                self.read_reg(Register::TRANSIENT_SRC).await?,
+            )),
+            BitFlags::SRC_LNDPRT => {
+                Event::PortraitLandscape(self.portrait_landscape_status().await?)
+            }
+            BitFlags::SRC_PULSE => {
+                Event::Pulse(get_pulse_source(self.read_reg(Register::PULSE_SRC).await?))
+            }
+            BitFlags::SRC_FF_MT => Event::FreefallMotion(self.freefall_motion_source().await?),
+            _ => {
+                let (x, y, z) = self.read_raw().await?;
+                Event::DataReady(IC::convert(x, y, z))
+            }
+        };
+        Ok(event)
+    }
+}
@@ -0,0 +1,322 @@
+//! Asynchronous driver based on the `embedded-hal-async` traits
+
+use embedded_hal_async::{
+    digital::Wait,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use crate::{
+    common::read_status::{get_data_status, get_interrupt_status, get_pl_status, get_system_mode},
+    ic, mode,
+    register_access::{BitFlags, Register},
+    DataStatus, Error, InterruptStatus, Mma8x5x, ModeChangeError, PortraitLandscapeStatus,
+    SlaveAddr, SystemMode,
+};
+
+mod calibration;
+mod config;
+mod device_config;
+mod events;
+mod functions;
+mod init;
+mod interrupt_handler;
+mod read;
+mod register_dump;
+mod sampling;
+mod self_test;
+mod sync;
+
+/// Asynchronous MMA8x5x device driver
+///
+/// Offers the same functionality as [`Mma8x5x`] using the `embedded-hal-async`
+/// I²C traits. Available with the `async` feature.
+#[derive(Debug)]
+pub struct Mma8x5xAsync<I2C, IC, MODE> {
+    dev: Mma8x5x<I2C, IC, MODE>,
+}
+
+impl<I2C> Mma8x5xAsync<I2C, ic::Mma8451, mode::Standby> {
+    /// Create new instance of the MMA8451 device.
+    pub fn new_mma8451(i2c: I2C, address: SlaveAddr) -> Self {
+        Mma8x5xAsync {
+            dev: Mma8x5x::new_mma8451(i2c, address),
+        }
+    }
+}
+
+impl<I2C> Mma8x5xAsync<I2C, ic::Mma8452, mode::Standby> {
+    /// Create new instance of the MMA8452 device.
+    pub fn new_mma8452(i2c: I2C, address: SlaveAddr) -> Self {
+        Mma8x5xAsync {
+            dev: Mma8x5x::new_mma8452(i2c, address),
+        }
+    }
+}
+
+impl<I2C> Mma8x5xAsync<I2C, ic::Mma8453, mode::Standby> {
+    /// Create new instance of the MMA8453 device.
+    pub fn new_mma8453(i2c: I2C, address: SlaveAddr) -> Self {
+        Mma8x5xAsync {
+            dev: Mma8x5x::new_mma8453(i2c, address),
+        }
+    }
+}
+
+impl<I2C> Mma8x5xAsync<I2C, ic::Mma8652, mode::Standby> {
+    /// Create new instance of the MMA8652 device.
+    pub fn new_mma8652(i2c: I2C) -> Self {
+        Mma8x5xAsync {
+            dev: Mma8x5x::new_mma8652(i2c),
+        }
+    }
+}
+
+impl<I2C> Mma8x5xAsync<I2C, ic::Mma8653, mode::Standby> {
+    /// Create new instance of the MMA8653 device.
+    pub fn new_mma8653(i2c: I2C) -> Self {
+        Mma8x5xAsync {
+            dev: Mma8x5x::new_mma8653(i2c),
+        }
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasSelectableAddress,
+{
+    async fn attach_with_address(
+        i2c: I2C,
+        address: SlaveAddr,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5xAsync<I2C, IC, mode::Standby>>> {
+        Mma8x5xAsync {
+            dev: Mma8x5x::new_with_address(i2c, address),
+        }
+        .attach()
+        .await
+    }
+}
+
+impl<E, I2C> Mma8x5xAsync<I2C, ic::Mma8451, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8451 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub async fn attach_mma8451(
+        i2c: I2C,
+        address: SlaveAddr,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5xAsync<I2C, ic::Mma8451, mode::Standby>>> {
+        Mma8x5xAsync::attach_with_address(i2c, address).await
+    }
+}
+
+impl<E, I2C> Mma8x5xAsync<I2C, ic::Mma8452, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8452 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub async fn attach_mma8452(
+        i2c: I2C,
+        address: SlaveAddr,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5xAsync<I2C, ic::Mma8452, mode::Standby>>> {
+        Mma8x5xAsync::attach_with_address(i2c, address).await
+    }
+}
+
+impl<E, I2C> Mma8x5xAsync<I2C, ic::Mma8453, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8453 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub async fn attach_mma8453(
+        i2c: I2C,
+        address: SlaveAddr,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5xAsync<I2C, ic::Mma8453, mode::Standby>>> {
+        Mma8x5xAsync::attach_with_address(i2c, address).await
+    }
+}
+
+impl<E, I2C> Mma8x5xAsync<I2C, ic::Mma8652, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8652 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub async fn attach_mma8652(
+        i2c: I2C,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5xAsync<I2C, ic::Mma8652, mode::Standby>>> {
+        Mma8x5xAsync::new_mma8652(i2c).attach().await
+    }
+}
+
+impl<E, I2C> Mma8x5xAsync<I2C, ic::Mma8653, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8653 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub async fn attach_mma8653(
+        i2c: I2C,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5xAsync<I2C, ic::Mma8653, mode::Standby>>> {
+        Mma8x5xAsync::new_mma8653(i2c).attach().await
+    }
+}
+
+impl<I2C, IC, MODE> Mma8x5xAsync<I2C, IC, MODE> {
+    /// Destroy driver instance, return I²C bus instance.
+    pub fn destroy(self) -> I2C {
+        self.dev.destroy()
+    }
+
+    /// Wait until the given interrupt pin is at its active level.
+    ///
+    /// The active level is taken from the configured interrupt pin polarity.
+    /// See [`set_interrupt_pin_polarity()`](#method.set_interrupt_pin_polarity).
+    /// Returns immediately if an interrupt is already pending.
+    /// Afterwards the interrupt source can be read with
+    /// [`interrupt_status()`](#method.interrupt_status).
+    pub async fn wait_for_interrupt<PIN: Wait>(&self, pin: &mut PIN) -> Result<(), PIN::Error> {
+        if self.dev.ctrl_reg3.is_high(BitFlags::IPOL) {
+            pin.wait_for_high().await
+        } else {
+            pin.wait_for_low().await
+        }
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5xAsync<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    pub(crate) async fn write_reg(&mut self, register: u8, value: u8) -> Result<(), Error<E>> {
+        self.dev
+            .i2c
+            .write(self.dev.address, &[register, value])
+            .await
+            .map_err(Error::I2C)
+    }
+
+    pub(crate) async fn read_reg(&mut self, register: u8) -> Result<u8, Error<E>> {
+        let mut data = [0];
+        self.dev
+            .i2c
+            .write_read(self.dev.address, &[register], &mut data)
+            .await
+            .map_err(Error::I2C)
+            .and(Ok(data[0]))
+    }
+
+    pub(crate) async fn reset_internal(&mut self) -> Result<(), Error<E>> {
+        let config = self.dev.ctrl_reg2.with_high(BitFlags::RST);
+        self.write_reg(Register::CTRL_REG2, config.bits).await?;
+        self.dev.reset_shadow_registers();
+        Ok(())
+    }
+
+    /// Return device ID (Who am I)
+    pub async fn device_id(&mut self) -> Result<u8, Error<E>> {
+        self.read_reg(Register::WHO_AM_I).await
+    }
+
+    /// Get offset correction for axes X, Y and Z.
+    pub async fn offset_correction(&mut self) -> Result<(i8, i8, i8), Error<E>> {
+        let mut data = [0; 3];
+        self.dev
+            .i2c
+            .write_read(self.dev.address, &[Register::OFF_X], &mut data)
+            .await
+            .map_err(Error::I2C)?;
+        Ok((data[0] as i8, data[1] as i8, data[2] as i8))
+    }
+
+    /// Read current system mode
+    pub async fn system_mode(&mut self) -> Result<SystemMode, Error<E>> {
+        let sysmod = self.read_reg(Register::SYSMOD).await?;
+        Ok(get_system_mode(sysmod))
+    }
+
+    /// Read current data status
+    pub async fn data_status(&mut self) -> Result<DataStatus, Error<E>> {
+        let st = self.read_reg(Register::STATUS).await?;
+        Ok(get_data_status(st))
+    }
+
+    /// Read current portrait/landscape status
+    pub async fn portrait_landscape_status(&mut self) -> Result<PortraitLandscapeStatus, Error<E>> {
+        let st = self.read_reg(Register::PL_STATUS).await?;
+        Ok(get_pl_status(st))
+    }
+
+    /// Read current interrupt status
+    pub async fn interrupt_status(&mut self) -> Result<InterruptStatus, Error<E>> {
+        let int_src = self.read_reg(Register::INT_SOURCE).await?;
+        Ok(get_interrupt_status(int_src))
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Change mode to active
+    pub async fn into_active(
+        mut self,
+    ) -> Result<Mma8x5xAsync<I2C, IC, mode::Active>, ModeChangeError<E, Self>> {
+        let config = self.dev.ctrl_reg1.with_high(BitFlags::ACTIVE);
+        match self.write_reg(Register::CTRL_REG1, config.bits).await {
+            Err(error) => Err(ModeChangeError { error, dev: self }),
+            Ok(_) => {
+                self.dev.ctrl_reg1 = config;
+                Ok(Mma8x5xAsync {
+                    dev: self.dev.into_mode(),
+                })
+            }
+        }
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Change mode to standby
+    pub async fn into_standby(
+        mut self,
+    ) -> Result<Mma8x5xAsync<I2C, IC, mode::Standby>, ModeChangeError<E, Self>> {
+        let config = self.dev.ctrl_reg1.with_low(BitFlags::ACTIVE);
+        match self.write_reg(Register::CTRL_REG1, config.bits).await {
+            Err(error) => Err(ModeChangeError { error, dev: self }),
+            Ok(_) => {
+                self.dev.ctrl_reg1 = config;
+                Ok(Mma8x5xAsync {
+                    dev: self.dev.into_mode(),
+                })
+            }
+        }
+    }
+
+    /// Reset (changes mode to standby)
+    pub async fn reset(
+        mut self,
+    ) -> Result<Mma8x5xAsync<I2C, IC, mode::Standby>, ModeChangeError<E, Self>> {
+        match self.reset_internal().await {
+            Err(error) => Err(ModeChangeError { error, dev: self }),
+            Ok(_) => Ok(Mma8x5xAsync {
+                dev: self.dev.into_mode(),
+            }),
+        }
+    }
+}
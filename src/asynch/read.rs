@@ -0,0 +1,107 @@
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+use super::Mma8x5xAsync;
+#[cfg(feature = "fixed")]
+use crate::{common::read_measurement::scale_to_fixed, FixedMeasurement};
+use crate::{
+    common::{
+        read_measurement::{scale, scale_to_mg, scale_to_ms2},
+        read_status::get_data_status,
+    },
+    conversion::raw_from_bytes,
+    ic::private::Sealed,
+    mode,
+    register_access::{BitFlags, Register},
+    DataStatus, Error, Measurement, MilliGMeasurement, UnscaledMeasurement,
+};
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    pub(crate) async fn read_raw(&mut self) -> Result<(u16, u16, u16), Error<E>> {
+        let fast_read = self.dev.ctrl_reg1.is_high(BitFlags::F_READ);
+        let mut data = [0; 6];
+        let data = if fast_read {
+            &mut data[..3]
+        } else {
+            &mut data[..]
+        };
+        self.dev
+            .i2c
+            .write_read(self.dev.address, &[Register::OUT_X_H], data)
+            .await
+            .map_err(Error::I2C)?;
+        Ok(raw_from_bytes(data, fast_read))
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read unscaled acceleration sensor data.
+    ///
+    /// See [`Mma8x5x::read_unscaled()`](crate::Mma8x5x::read_unscaled).
+    pub async fn read_unscaled(&mut self) -> Result<UnscaledMeasurement, Error<E>> {
+        let (x, y, z) = self.read_raw().await?;
+        Ok(IC::convert(x, y, z))
+    }
+
+    /// Read the data status and the unscaled acceleration sensor data
+    /// in a single transaction.
+    ///
+    /// See [`Mma8x5x::read_with_status()`](crate::Mma8x5x::read_with_status).
+    pub async fn read_with_status(
+        &mut self,
+    ) -> Result<(DataStatus, UnscaledMeasurement), Error<E>> {
+        let fast_read = self.dev.ctrl_reg1.is_high(BitFlags::F_READ);
+        let mut data = [0; 7];
+        let data = if fast_read {
+            &mut data[..4]
+        } else {
+            &mut data[..]
+        };
+        self.dev
+            .i2c
+            .write_read(self.dev.address, &[Register::STATUS], data)
+            .await
+            .map_err(Error::I2C)?;
+        let (x, y, z) = raw_from_bytes(&data[1..], fast_read);
+        Ok((get_data_status(data[0]), IC::convert(x, y, z)))
+    }
+
+    /// Read acceleration sensor data scaled to G.
+    ///
+    /// See [`Mma8x5x::read()`](crate::Mma8x5x::read).
+    pub async fn read(&mut self) -> Result<Measurement, Error<E>> {
+        let unscaled = self.read_unscaled().await?;
+        Ok(scale(unscaled, self.dev.sensitivity_shift()))
+    }
+
+    /// Read acceleration sensor data in m/s².
+    ///
+    /// See [`Mma8x5x::read_ms2()`](crate::Mma8x5x::read_ms2).
+    pub async fn read_ms2(&mut self) -> Result<Measurement, Error<E>> {
+        let unscaled = self.read_unscaled().await?;
+        Ok(scale_to_ms2(unscaled, self.dev.sensitivity_shift()))
+    }
+
+    /// Read acceleration sensor data in milli-g.
+    ///
+    /// See [`Mma8x5x::read_mg()`](crate::Mma8x5x::read_mg).
+    pub async fn read_mg(&mut self) -> Result<MilliGMeasurement, Error<E>> {
+        let unscaled = self.read_unscaled().await?;
+        Ok(scale_to_mg(unscaled, self.dev.sensitivity_shift()))
+    }
+
+    /// Read acceleration sensor data scaled to G in fixed-point.
+    ///
+    /// See [`Mma8x5x::read_fixed()`](crate::Mma8x5x::read_fixed).
+    #[cfg(feature = "fixed")]
+    pub async fn read_fixed(&mut self) -> Result<FixedMeasurement, Error<E>> {
+        let unscaled = self.read_unscaled().await?;
+        Ok(scale_to_fixed(unscaled, self.dev.sensitivity_shift()))
+    }
+}
@@ -0,0 +1,39 @@
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+use super::Mma8x5xAsync;
+use crate::{ic::private::Sealed, register_access::Register, Error, RegisterDump};
+
+impl<E, I2C, IC, MODE> Mma8x5xAsync<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read all the registers of the device.
+    ///
+    /// See [`Mma8x5x::dump_registers()`](crate::Mma8x5x::dump_registers).
+    pub async fn dump_registers(&mut self) -> Result<RegisterDump, Error<E>> {
+        let mut dump = RegisterDump::new(IC::MODEL);
+        self.dev
+            .i2c
+            .write_read(
+                self.dev.address,
+                &[Register::F_SETUP],
+                dump.get_config_buffer(),
+            )
+            .await
+            .map_err(Error::I2C)?;
+        self.dev
+            .i2c
+            .write_read(
+                self.dev.address,
+                &[Register::STATUS],
+                dump.get_data_buffer(),
+            )
+            .await
+            .map_err(Error::I2C)?;
+        if dump.is_fast_read() {
+            dump.expand_fast_read_data();
+        }
+        Ok(dump)
+    }
+}
@@ -0,0 +1,81 @@
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use super::Mma8x5xAsync;
+use crate::{
+    common::sampling::{
+        add_to_sum, get_sampling_ctrl_reg1, SAMPLING_PERIOD_MS, SAMPLING_SETTLE_MS,
+    },
+    conversion::raw_from_bytes,
+    ic::private::Sealed,
+    mode,
+    register_access::{BitFlags, Register},
+    Error,
+};
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Select +/-2g with the high-pass filter output and the FIFO disabled
+    /// and normal power mode.
+    ///
+    /// The shadow registers are not modified.
+    pub(crate) async fn prepare_sampling(&mut self) -> Result<(), Error<E>> {
+        self.write_reg(Register::XYZ_DATA_CFG, 0).await?;
+        self.write_reg(Register::CTRL_REG2, 0).await?;
+        if IC::MODEL.has_fifo() {
+            self.write_reg(Register::F_SETUP, 0).await?;
+        }
+        Ok(())
+    }
+
+    /// Go active, add up the samples and return to standby.
+    pub(crate) async fn sample_output<D: DelayNs>(
+        &mut self,
+        samples: u8,
+        delay: &mut D,
+    ) -> Result<[i32; 3], Error<E>> {
+        let ctrl_reg1 = get_sampling_ctrl_reg1();
+        self.write_reg(
+            Register::CTRL_REG1,
+            ctrl_reg1.with_high(BitFlags::ACTIVE).bits,
+        )
+        .await?;
+        delay.delay_ms(SAMPLING_SETTLE_MS).await;
+        let mut sum = [0; 3];
+        for _ in 0..samples {
+            delay.delay_ms(SAMPLING_PERIOD_MS).await;
+            let mut data = [0; 6];
+            self.dev
+                .i2c
+                .write_read(self.dev.address, &[Register::OUT_X_H], &mut data)
+                .await
+                .map_err(Error::I2C)?;
+            let (x, y, z) = raw_from_bytes(&data, false);
+            add_to_sum(&mut sum, IC::convert(x, y, z));
+        }
+        self.write_reg(Register::CTRL_REG1, ctrl_reg1.bits).await?;
+        Ok(sum)
+    }
+
+    /// Write the configuration stored in the shadow registers back.
+    ///
+    /// CTRL_REG1 goes first so that the device is in standby mode.
+    pub(crate) async fn restore_configuration(&mut self) -> Result<(), Error<E>> {
+        self.write_reg(Register::CTRL_REG1, self.dev.ctrl_reg1.bits)
+            .await?;
+        self.write_reg(Register::CTRL_REG2, self.dev.ctrl_reg2.bits)
+            .await?;
+        self.write_reg(Register::XYZ_DATA_CFG, self.dev.xyz_data_cfg.bits)
+            .await?;
+        if IC::MODEL.has_fifo() {
+            self.write_reg(Register::F_SETUP, self.dev.f_setup.bits)
+                .await?;
+        }
+        Ok(())
+    }
+}
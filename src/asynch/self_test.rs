@@ -0,0 +1,49 @@
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use super::Mma8x5xAsync;
+use crate::{
+    common::self_test::{get_self_test_report, SELF_TEST_SAMPLES},
+    ic::private::Sealed,
+    mode,
+    register_access::{BitFlags, Register},
+    Error, SelfTestReport,
+};
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Run the self-test and evaluate the result.
+    ///
+    /// See [`Mma8x5x::run_self_test()`](crate::Mma8x5x::run_self_test).
+    pub async fn run_self_test<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<SelfTestReport, Error<E>> {
+        let result = self.self_test_sequence(delay).await;
+        let restored = self.restore_configuration().await;
+        let (off, on) = result?;
+        restored?;
+        Ok(get_self_test_report(
+            off,
+            on,
+            SELF_TEST_SAMPLES,
+            IC::SELF_TEST_LIMITS,
+        ))
+    }
+
+    async fn self_test_sequence<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<([i32; 3], [i32; 3]), Error<E>> {
+        self.prepare_sampling().await?;
+        let off = self.sample_output(SELF_TEST_SAMPLES, delay).await?;
+        self.write_reg(Register::CTRL_REG2, BitFlags::ST).await?;
+        let on = self.sample_output(SELF_TEST_SAMPLES, delay).await?;
+        Ok((off, on))
+    }
+}
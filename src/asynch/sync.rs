@@ -0,0 +1,106 @@
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+use super::Mma8x5xAsync;
+use crate::{
+    ic::private::Sealed,
+    mode,
+    register_access::{BitFlags, Register},
+    Config, Error, ModeChangeError,
+};
+
+impl<E, I2C, IC, MODE> Mma8x5xAsync<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the configuration from the device and put it in active or standby mode.
+    async fn sync_internal(&mut self, active: bool) -> Result<(), Error<E>> {
+        let mut ctrl = [0; 5];
+        self.dev
+            .i2c
+            .write_read(self.dev.address, &[Register::CTRL_REG1], &mut ctrl)
+            .await
+            .map_err(Error::I2C)?;
+        // HP_FILTER_CUTOFF follows XYZ_DATA_CFG
+        let mut data_cfg = [0; 2];
+        let len = if IC::MODEL.has_high_pass_filter() {
+            2
+        } else {
+            1
+        };
+        self.dev
+            .i2c
+            .write_read(
+                self.dev.address,
+                &[Register::XYZ_DATA_CFG],
+                &mut data_cfg[..len],
+            )
+            .await
+            .map_err(Error::I2C)?;
+        let pl_cfg = self.read_reg(Register::PL_CFG).await?;
+        let f_setup = if IC::MODEL.has_fifo() {
+            self.read_reg(Register::F_SETUP).await?
+        } else {
+            0
+        };
+        self.dev.ctrl_reg1 = Config { bits: ctrl[0] };
+        self.dev.ctrl_reg2 = Config {
+            bits: ctrl[1] & !BitFlags::RST,
+        };
+        self.dev.ctrl_reg3 = Config { bits: ctrl[2] };
+        self.dev.ctrl_reg4 = Config { bits: ctrl[3] };
+        self.dev.ctrl_reg5 = Config { bits: ctrl[4] };
+        self.dev.xyz_data_cfg = Config { bits: data_cfg[0] };
+        self.dev.hp_filter_cutoff = Config { bits: data_cfg[1] };
+        self.dev.pl_cfg = Config { bits: pl_cfg };
+        self.dev.f_setup = Config { bits: f_setup };
+        if self.dev.ctrl_reg1.is_high(BitFlags::ACTIVE) != active {
+            let config = if active {
+                self.dev.ctrl_reg1.with_high(BitFlags::ACTIVE)
+            } else {
+                self.dev.ctrl_reg1.with_low(BitFlags::ACTIVE)
+            };
+            self.write_reg(Register::CTRL_REG1, config.bits).await?;
+            self.dev.ctrl_reg1 = config;
+        }
+        Ok(())
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the configuration back from the device into the driver.
+    ///
+    /// See [`Mma8x5x::sync_from_device()`](crate::Mma8x5x::sync_from_device).
+    pub async fn sync_from_device(&mut self) -> Result<(), Error<E>> {
+        self.sync_internal(false).await
+    }
+
+    /// Read the configuration from the device and make sure it is active.
+    pub(crate) async fn attach(
+        mut self,
+    ) -> Result<Mma8x5xAsync<I2C, IC, mode::Active>, ModeChangeError<E, Self>> {
+        if let Err(error) = self.sync_internal(true).await {
+            return Err(ModeChangeError { error, dev: self });
+        }
+        Ok(Mma8x5xAsync {
+            dev: self.dev.into_mode(),
+        })
+    }
+}
+
+impl<E, I2C, IC> Mma8x5xAsync<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the configuration back from the device into the driver.
+    ///
+    /// If the device is in standby mode, it is put into active mode.
+    pub async fn sync_from_device(&mut self) -> Result<(), Error<E>> {
+        self.sync_internal(true).await
+    }
+}
@@ -0,0 +1,590 @@
+//! Command-line tool to configure and stream the sensor on Linux
+//!
+//! Run `mma8x5x-cli help` for the usage.
+
+use std::{
+    env,
+    fmt::{self, Write as _},
+    process, thread,
+    time::{Duration, Instant},
+};
+
+use linux_embedded_hal::{Delay, I2cdev};
+use mma8x5x::{
+    AnyMma8x5x, CalibrationOrientation, DriverMode, GScale, OutputDataRate, PowerMode, SystemMode,
+};
+
+const USAGE: &str = "\
+Usage: mma8x5x-cli [--bus <path>] <command> [options]
+
+Options:
+    --bus <path>                 I2C bus device [default: /dev/i2c-1]
+
+Commands:
+    probe                        Detect the device and print its model and address
+    dump                         Read all registers and decode their fields
+    set [options]                Change the configuration
+        --scale <2|4|8>              G scale
+        --odr <hz>                   Output data rate: 800, 400, 200, 100, 50, 12.5, 6.25 or 1.56
+        --power-mode <mode>          Wake power mode: normal, low-noise, high-resolution or low-power
+        --offsets <x,y,z>            Offset correction in 1.96mg steps
+        --mode <active|standby>      Mode to leave the device in [default: unchanged]
+    self-test                    Run the self-test
+    calibrate [options]          Calibrate the offset correction with the device at rest
+        --orientation <axis>         Axis pointing up: x-up, x-down, y-up, y-down, z-up or z-down
+                                     [default: z-up]
+    stream [options]             Print samples until interrupted
+        --rate <hz>                  Sample rate [default: 10]
+        --format <csv|json>          Output format: CSV or JSON lines [default: csv]
+        --unit <g|mg|ms2|raw>        Unit [default: g]
+        --count <n>                  Stop after n samples and restore the data rate and mode
+    help                         Print this help
+
+Reading the status and source registers clears them.
+When a stream without --count is interrupted, the device stays active at the streaming
+data rate.";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Probe,
+    Dump,
+    Set(Settings),
+    SelfTest,
+    Calibrate(CalibrationOrientation),
+    Stream(StreamOptions),
+    Help,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Settings {
+    scale: Option<GScale>,
+    data_rate: Option<OutputDataRate>,
+    power_mode: Option<PowerMode>,
+    offsets: Option<(i8, i8, i8)>,
+    mode: Option<DriverMode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    G,
+    MilliG,
+    Ms2,
+    Raw,
+}
+
+#[derive(Debug, PartialEq)]
+struct StreamOptions {
+    rate: f32,
+    format: Format,
+    unit: Unit,
+    count: Option<u64>,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions {
+            rate: 10.0,
+            format: Format::Csv,
+            unit: Unit::G,
+            count: None,
+        }
+    }
+}
+
+type Sensor = AnyMma8x5x<I2cdev>;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (bus, command) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(e) = run(&bus, command) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(bus: &str, command: Command) -> Result<(), String> {
+    if command == Command::Help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    let i2c = I2cdev::new(bus).map_err(|e| format!("cannot open {}: {}", bus, e))?;
+    let sensor = AnyMma8x5x::detect(i2c).map_err(|e| match e.device_id {
+        Some(id) => format!("unsupported device ID 0x{:02X}", id),
+        None => String::from("no device found"),
+    })?;
+    match command {
+        Command::Probe => probe(sensor),
+        Command::Dump => dump(sensor),
+        Command::Set(settings) => set(sensor, &settings),
+        Command::SelfTest => self_test(sensor),
+        Command::Calibrate(orientation) => calibrate(sensor, orientation),
+        Command::Stream(options) => stream(sensor, &options),
+        Command::Help => Ok(()),
+    }
+}
+
+fn error<E: fmt::Debug>(e: E) -> String {
+    format!("{:?}", e)
+}
+
+fn probe(mut sensor: Sensor) -> Result<(), String> {
+    let id = sensor.device_id().map_err(error)?;
+    let mode = sensor.system_mode().map_err(error)?;
+    println!(
+        "{:?} at address 0x{:02X} (device ID 0x{:02X}), {:?} mode",
+        sensor.ic(),
+        sensor.address(),
+        id,
+        mode
+    );
+    Ok(())
+}
+
+fn dump(mut sensor: Sensor) -> Result<(), String> {
+    println!("{}", sensor.dump_registers().map_err(error)?);
+    Ok(())
+}
+
+fn set(sensor: Sensor, settings: &Settings) -> Result<(), String> {
+    let (mut sensor, was_active) = attach_standby(sensor)?;
+    if let Some(scale) = settings.scale {
+        sensor.set_scale(scale).map_err(error)?;
+    }
+    if let Some(rate) = settings.data_rate {
+        sensor.set_data_rate(rate).map_err(error)?;
+    }
+    if let Some(power_mode) = settings.power_mode {
+        sensor.set_wake_power_mode(power_mode).map_err(error)?;
+    }
+    if let Some((x, y, z)) = settings.offsets {
+        sensor.set_offset_correction(x, y, z).map_err(error)?;
+    }
+    let active = settings
+        .mode
+        .map_or(was_active, |mode| mode == DriverMode::Active);
+    restore_mode(sensor, active)
+}
+
+fn self_test(sensor: Sensor) -> Result<(), String> {
+    let (mut sensor, was_active) = attach_standby(sensor)?;
+    let report = sensor.run_self_test(&mut Delay).map_err(error)?;
+    for (axis, result) in [("X", report.x), ("Y", report.y), ("Z", report.z)] {
+        println!(
+            "{}: delta {} (accepted {} to {})",
+            axis, result.delta, result.min, result.max
+        );
+    }
+    println!("{}", if report.passed { "PASSED" } else { "FAILED" });
+    restore_mode(sensor, was_active)?;
+    if !report.passed {
+        return Err(String::from("self-test failed"));
+    }
+    Ok(())
+}
+
+fn calibrate(sensor: Sensor, orientation: CalibrationOrientation) -> Result<(), String> {
+    let (mut sensor, was_active) = attach_standby(sensor)?;
+    let calibration = sensor
+        .calibrate_offsets(orientation, &mut Delay)
+        .map_err(error)?;
+    println!(
+        "Offsets written: {}, {}, {}",
+        calibration.x, calibration.y, calibration.z
+    );
+    let residual = calibration.residual;
+    println!(
+        "Residual error: {:.4}g, {:.4}g, {:.4}g",
+        residual.x, residual.y, residual.z
+    );
+    restore_mode(sensor, was_active)
+}
+
+/// Stream samples and restore the data rate and mode afterwards.
+///
+/// Without a sample count, the streaming only ends when the process is
+/// interrupted and the device keeps the streaming data rate in active mode.
+fn stream(sensor: Sensor, options: &StreamOptions) -> Result<(), String> {
+    let (mut sensor, was_active) = attach_standby(sensor)?;
+    let data_rate = sensor.data_rate();
+    sensor
+        .set_data_rate(get_data_rate(options.rate))
+        .map_err(error)?;
+    let mut sensor = sensor.into_active().map_err(|e| error(e.error))?;
+    let result = stream_samples(&mut sensor, options);
+    let mut sensor = sensor.into_standby().map_err(|e| error(e.error))?;
+    sensor.set_data_rate(data_rate).map_err(error)?;
+    restore_mode(sensor, was_active)?;
+    result
+}
+
+fn stream_samples(sensor: &mut Sensor, options: &StreamOptions) -> Result<(), String> {
+    if options.format == Format::Csv {
+        println!("time,x,y,z");
+    }
+    let period = Duration::from_secs_f32(1.0 / options.rate);
+    let start = Instant::now();
+    let mut deadline = start;
+    let mut count = 0;
+    while options.count != Some(count) {
+        let time = deadline.duration_since(start).as_secs_f32();
+        let (x, y, z) = read_sample(sensor, options.unit)?;
+        println!("{}", format_sample(options.format, time, &x, &y, &z));
+        count += 1;
+        deadline += period;
+        if let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            thread::sleep(remaining);
+        }
+    }
+    Ok(())
+}
+
+fn read_sample(sensor: &mut Sensor, unit: Unit) -> Result<(String, String, String), String> {
+    Ok(match unit {
+        Unit::G => {
+            let m = sensor.read().map_err(error)?;
+            (
+                format!("{:.4}", m.x),
+                format!("{:.4}", m.y),
+                format!("{:.4}", m.z),
+            )
+        }
+        Unit::Ms2 => {
+            let m = sensor.read_ms2().map_err(error)?;
+            (
+                format!("{:.3}", m.x),
+                format!("{:.3}", m.y),
+                format!("{:.3}", m.z),
+            )
+        }
+        Unit::MilliG => {
+            let m = sensor.read_mg().map_err(error)?;
+            (m.x.to_string(), m.y.to_string(), m.z.to_string())
+        }
+        Unit::Raw => {
+            let m = sensor.read_unscaled().map_err(error)?;
+            (m.x.to_string(), m.y.to_string(), m.z.to_string())
+        }
+    })
+}
+
+fn format_sample(format: Format, time: f32, x: &str, y: &str, z: &str) -> String {
+    let mut line = String::new();
+    // Writing to a String cannot fail.
+    let _ = match format {
+        Format::Csv => write!(line, "{:.4},{},{},{}", time, x, y, z),
+        Format::Json => write!(
+            line,
+            "{{\"time\":{:.4},\"x\":{},\"y\":{},\"z\":{}}}",
+            time, x, y, z
+        ),
+    };
+    line
+}
+
+/// Read the configuration from the device and put it in standby mode.
+///
+/// Returns whether the device was active.
+fn attach_standby(mut sensor: Sensor) -> Result<(Sensor, bool), String> {
+    let active = sensor.system_mode().map_err(error)? != SystemMode::Standby;
+    sensor.sync_from_device().map_err(error)?;
+    Ok((sensor, active))
+}
+
+fn restore_mode(sensor: Sensor, active: bool) -> Result<(), String> {
+    if active {
+        sensor.into_active().map_err(|e| error(e.error))?;
+    }
+    Ok(())
+}
+
+/// Return the lowest output data rate at or above a sample rate.
+fn get_data_rate(rate: f32) -> OutputDataRate {
+    [
+        (1.56, OutputDataRate::Hz1_56),
+        (6.25, OutputDataRate::Hz6_25),
+        (12.5, OutputDataRate::Hz12_5),
+        (50.0, OutputDataRate::Hz50),
+        (100.0, OutputDataRate::Hz100),
+        (200.0, OutputDataRate::Hz200),
+        (400.0, OutputDataRate::Hz400),
+    ]
+    .into_iter()
+    .find(|&(hz, _)| rate <= hz)
+    .map_or(OutputDataRate::Hz800, |(_, odr)| odr)
+}
+
+fn parse_args(args: &[String]) -> Result<(String, Command), String> {
+    let mut bus = String::from("/dev/i2c-1");
+    let mut args = args.iter().map(String::as_str);
+    let mut command = None;
+    let mut options = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--bus" if command.is_none() => {
+                bus = args.next().ok_or("missing value for --bus")?.to_string();
+            }
+            "-h" | "--help" if command.is_none() => command = Some("help"),
+            _ if command.is_none() => command = Some(arg),
+            _ => options.push(arg),
+        }
+    }
+    let command = match command.ok_or("missing command")? {
+        "probe" => no_options(&options, Command::Probe)?,
+        "dump" => no_options(&options, Command::Dump)?,
+        "self-test" => no_options(&options, Command::SelfTest)?,
+        "help" => Command::Help,
+        "set" => Command::Set(parse_settings(&options)?),
+        "calibrate" => Command::Calibrate(parse_calibrate(&options)?),
+        "stream" => Command::Stream(parse_stream(&options)?),
+        other => return Err(format!("unknown command '{}'", other)),
+    };
+    Ok((bus, command))
+}
+
+fn no_options(options: &[&str], command: Command) -> Result<Command, String> {
+    match options.first() {
+        Some(option) => Err(format!("unexpected argument '{}'", option)),
+        None => Ok(command),
+    }
+}
+
+/// Split the options into pairs of option name and value.
+fn option_pairs<'a>(options: &[&'a str]) -> Result<Vec<(&'a str, &'a str)>, String> {
+    options
+        .chunks(2)
+        .map(|pair| match pair {
+            [name, value] if name.starts_with("--") => Ok((*name, *value)),
+            [name] if name.starts_with("--") => Err(format!("missing value for {}", name)),
+            _ => Err(format!("unexpected argument '{}'", pair[0])),
+        })
+        .collect()
+}
+
+fn invalid(name: &str, value: &str) -> String {
+    format!("invalid value '{}' for {}", value, name)
+}
+
+fn parse_settings(options: &[&str]) -> Result<Settings, String> {
+    let mut settings = Settings::default();
+    for (name, value) in option_pairs(options)? {
+        match name {
+            "--scale" => settings.scale = Some(parse_scale(value).ok_or(invalid(name, value))?),
+            "--odr" => settings.data_rate = Some(parse_odr(value).ok_or(invalid(name, value))?),
+            "--power-mode" => {
+                settings.power_mode = Some(parse_power_mode(value).ok_or(invalid(name, value))?)
+            }
+            "--offsets" => {
+                settings.offsets = Some(parse_offsets(value).ok_or(invalid(name, value))?)
+            }
+            "--mode" => {
+                settings.mode = Some(match value {
+                    "active" => DriverMode::Active,
+                    "standby" => DriverMode::Standby,
+                    _ => return Err(invalid(name, value)),
+                })
+            }
+            _ => return Err(format!("unknown option '{}'", name)),
+        }
+    }
+    Ok(settings)
+}
+
+fn parse_calibrate(options: &[&str]) -> Result<CalibrationOrientation, String> {
+    let mut orientation = CalibrationOrientation::ZUp;
+    for (name, value) in option_pairs(options)? {
+        match name {
+            "--orientation" => {
+                orientation = parse_orientation(value).ok_or(invalid(name, value))?
+            }
+            _ => return Err(format!("unknown option '{}'", name)),
+        }
+    }
+    Ok(orientation)
+}
+
+fn parse_stream(options: &[&str]) -> Result<StreamOptions, String> {
+    let mut stream = StreamOptions::default();
+    for (name, value) in option_pairs(options)? {
+        match name {
+            "--rate" => {
+                stream.rate = value
+                    .parse()
+                    .ok()
+                    .filter(|rate: &f32| *rate > 0.0 && *rate <= 800.0)
+                    .ok_or(invalid(name, value))?
+            }
+            "--format" => {
+                stream.format = match value {
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    _ => return Err(invalid(name, value)),
+                }
+            }
+            "--unit" => {
+                stream.unit = match value {
+                    "g" => Unit::G,
+                    "mg" => Unit::MilliG,
+                    "ms2" => Unit::Ms2,
+                    "raw" => Unit::Raw,
+                    _ => return Err(invalid(name, value)),
+                }
+            }
+            "--count" => stream.count = Some(value.parse().map_err(|_| invalid(name, value))?),
+            _ => return Err(format!("unknown option '{}'", name)),
+        }
+    }
+    Ok(stream)
+}
+
+fn parse_scale(value: &str) -> Option<GScale> {
+    match value.trim_end_matches('g') {
+        "2" => Some(GScale::G2),
+        "4" => Some(GScale::G4),
+        "8" => Some(GScale::G8),
+        _ => None,
+    }
+}
+
+fn parse_odr(value: &str) -> Option<OutputDataRate> {
+    match value.trim_end_matches("Hz") {
+        "800" => Some(OutputDataRate::Hz800),
+        "400" => Some(OutputDataRate::Hz400),
+        "200" => Some(OutputDataRate::Hz200),
+        "100" => Some(OutputDataRate::Hz100),
+        "50" => Some(OutputDataRate::Hz50),
+        "12.5" => Some(OutputDataRate::Hz12_5),
+        "6.25" => Some(OutputDataRate::Hz6_25),
+        "1.56" => Some(OutputDataRate::Hz1_56),
+        _ => None,
+    }
+}
+
+fn parse_power_mode(value: &str) -> Option<PowerMode> {
+    match value {
+        "normal" => Some(PowerMode::Normal),
+        "low-noise" => Some(PowerMode::LowNoiseLowPower),
+        "high-resolution" => Some(PowerMode::HighResolution),
+        "low-power" => Some(PowerMode::LowPower),
+        _ => None,
+    }
+}
+
+fn parse_offsets(value: &str) -> Option<(i8, i8, i8)> {
+    let mut values = value.split(',').map(|v| v.trim().parse::<i8>());
+    match (values.next(), values.next(), values.next(), values.next()) {
+        (Some(Ok(x)), Some(Ok(y)), Some(Ok(z)), None) => Some((x, y, z)),
+        _ => None,
+    }
+}
+
+fn parse_orientation(value: &str) -> Option<CalibrationOrientation> {
+    match value {
+        "x-up" => Some(CalibrationOrientation::XUp),
+        "x-down" => Some(CalibrationOrientation::XDown),
+        "y-up" => Some(CalibrationOrientation::YUp),
+        "y-down" => Some(CalibrationOrientation::YDown),
+        "z-up" => Some(CalibrationOrientation::ZUp),
+        "z-down" => Some(CalibrationOrientation::ZDown),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<(String, Command), String> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn can_parse_bus_and_command() {
+        let (bus, command) = parse("--bus /dev/i2c-0 probe").unwrap();
+        assert_eq!("/dev/i2c-0", bus);
+        assert_eq!(Command::Probe, command);
+        let (bus, command) = parse("dump").unwrap();
+        assert_eq!("/dev/i2c-1", bus);
+        assert_eq!(Command::Dump, command);
+    }
+
+    #[test]
+    fn can_parse_settings() {
+        let (_, command) =
+            parse("set --scale 4 --odr 12.5 --power-mode low-power --offsets 1,-2,3 --mode active")
+                .unwrap();
+        let expected = Settings {
+            scale: Some(GScale::G4),
+            data_rate: Some(OutputDataRate::Hz12_5),
+            power_mode: Some(PowerMode::LowPower),
+            offsets: Some((1, -2, 3)),
+            mode: Some(DriverMode::Active),
+        };
+        assert_eq!(Command::Set(expected), command);
+    }
+
+    #[test]
+    fn can_parse_stream_options() {
+        let (_, command) = parse("stream --rate 50 --format json --unit mg --count 3").unwrap();
+        let expected = StreamOptions {
+            rate: 50.0,
+            format: Format::Json,
+            unit: Unit::MilliG,
+            count: Some(3),
+        };
+        assert_eq!(Command::Stream(expected), command);
+        let (_, command) = parse("stream").unwrap();
+        assert_eq!(Command::Stream(StreamOptions::default()), command);
+    }
+
+    #[test]
+    fn can_parse_calibration_orientation() {
+        let (_, command) = parse("calibrate --orientation y-down").unwrap();
+        assert_eq!(Command::Calibrate(CalibrationOrientation::YDown), command);
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(parse("").is_err());
+        assert!(parse("reset").is_err());
+        assert!(parse("probe --scale 2").is_err());
+        assert!(parse("set --scale 3").is_err());
+        assert!(parse("set --scale").is_err());
+        assert!(parse("set --offsets 1,2").is_err());
+        assert!(parse("set 2").is_err());
+        assert!(parse("stream --rate 0").is_err());
+        assert!(parse("stream --format xml").is_err());
+    }
+
+    #[test]
+    fn selects_data_rate() {
+        assert_eq!(OutputDataRate::Hz1_56, get_data_rate(1.0));
+        assert_eq!(OutputDataRate::Hz50, get_data_rate(50.0));
+        assert_eq!(OutputDataRate::Hz100, get_data_rate(60.0));
+        assert_eq!(OutputDataRate::Hz800, get_data_rate(500.0));
+    }
+
+    #[test]
+    fn formats_samples() {
+        assert_eq!(
+            "0.1000,1,-2,3",
+            format_sample(Format::Csv, 0.1, "1", "-2", "3")
+        );
+        assert_eq!(
+            "{\"time\":0.1000,\"x\":1,\"y\":-2,\"z\":3}",
+            format_sample(Format::Json, 0.1, "1", "-2", "3")
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use crate::{
+    ic::private::Sealed, mode, register_access::Register, CalibrationOrientation, Error,
+    Measurement, Mma8x5x, OffsetCalibration,
+};
+
+/// Number of samples averaged for the offset calibration
+pub(crate) const CALIBRATION_SAMPLES: u8 = 32;
+/// Offset correction resolution in G/LSB
+pub(crate) const OFFSET_RESOLUTION: f32 = 0.00196;
+
+fn get_expected_acceleration(orientation: CalibrationOrientation) -> [f32; 3] {
+    match orientation {
+        CalibrationOrientation::XUp => [1.0, 0.0, 0.0],
+        CalibrationOrientation::XDown => [-1.0, 0.0, 0.0],
+        CalibrationOrientation::YUp => [0.0, 1.0, 0.0],
+        CalibrationOrientation::YDown => [0.0, -1.0, 0.0],
+        CalibrationOrientation::ZUp => [0.0, 0.0, 1.0],
+        CalibrationOrientation::ZDown => [0.0, 0.0, -1.0],
+    }
+}
+
+/// Round to the closest offset correction value, saturating at the range limits
+fn round_offset(counts: f32) -> i8 {
+    if counts < 0.0 {
+        (counts - 0.5) as i8
+    } else {
+        (counts + 0.5) as i8
+    }
+}
+
+fn get_axis_offset(mean: f32, expected: f32) -> (i8, f32) {
+    let error = mean - expected;
+    let offset = round_offset(-error / OFFSET_RESOLUTION);
+    (offset, error + f32::from(offset) * OFFSET_RESOLUTION)
+}
+
+/// Compute the offset correction from the sum of the samples taken at +/-2g
+pub(crate) fn get_offset_calibration(
+    sum: [i32; 3],
+    samples: u8,
+    counts_per_g: f32,
+    orientation: CalibrationOrientation,
+) -> OffsetCalibration {
+    let expected = get_expected_acceleration(orientation);
+    let mean = |i: usize| sum[i] as f32 / f32::from(samples) / counts_per_g;
+    let (x, residual_x) = get_axis_offset(mean(0), expected[0]);
+    let (y, residual_y) = get_axis_offset(mean(1), expected[1]);
+    let (z, residual_z) = get_axis_offset(mean(2), expected[2]);
+    OffsetCalibration {
+        x,
+        y,
+        z,
+        residual: Measurement {
+            x: residual_x,
+            y: residual_y,
+            z: residual_z,
+        },
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Calibrate the offset correction.
+    ///
+    /// The device must be at rest in the given orientation.
+    /// Clears the current offset correction, averages samples at
+    /// +/-2g and 100Hz and writes the offset correction that brings
+    /// the axis pointing up to +1g and the other axes to 0g.
+    /// The values are rounded to the 1.96mg/LSB resolution and
+    /// limited to the +/-250mg compensation range.
+    ///
+    /// This takes about 350ms. The previous configuration is restored
+    /// afterwards. If an error occurs, the previous offset correction is
+    /// restored as well. Returns the values written and the remaining error.
+    pub fn calibrate_offsets<D: DelayNs>(
+        &mut self,
+        orientation: CalibrationOrientation,
+        delay: &mut D,
+    ) -> Result<OffsetCalibration, Error<E>> {
+        let mut previous = [0; 3];
+        self.i2c
+            .write_read(self.address, &[Register::OFF_X], &mut previous)
+            .map_err(Error::I2C)?;
+        self.set_offset_correction(0, 0, 0)?;
+        let result = self.calibration_sequence(delay);
+        let restored = self.restore_configuration();
+        let sum = match result.and_then(|sum| restored.map(|_| sum)) {
+            Ok(sum) => sum,
+            Err(e) => {
+                // Report the original error also if this fails.
+                let [x, y, z] = previous;
+                let _ = self.set_offset_correction(x as i8, y as i8, z as i8);
+                return Err(e);
+            }
+        };
+        let counts_per_g = f32::from(1_u16 << IC::SENSITIVITY_SHIFT);
+        let calibration =
+            get_offset_calibration(sum, CALIBRATION_SAMPLES, counts_per_g, orientation);
+        self.set_offset_correction(calibration.x, calibration.y, calibration.z)?;
+        Ok(calibration)
+    }
+
+    fn calibration_sequence<D: DelayNs>(&mut self, delay: &mut D) -> Result<[i32; 3], Error<E>> {
+        self.prepare_sampling()?;
+        self.sample_output(CALIBRATION_SAMPLES, delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(expected: f32, actual: f32) {
+        assert!((expected - actual).abs() < 0.0001, "{expected} != {actual}");
+    }
+
+    #[test]
+    fn can_round_offset() {
+        assert_eq!(0, round_offset(0.4));
+        assert_eq!(1, round_offset(0.5));
+        assert_eq!(-1, round_offset(-0.5));
+        assert_eq!(-2, round_offset(-1.6));
+        assert_eq!(127, round_offset(300.0));
+        assert_eq!(-128, round_offset(-300.0));
+    }
+
+    #[test]
+    fn can_compute_offset_calibration() {
+        // Averages of 41, -20 and 1024+82 counts at 1024 counts/g
+        let cal = get_offset_calibration(
+            [41 * 4, -20 * 4, (1024 + 82) * 4],
+            4,
+            1024.0,
+            CalibrationOrientation::ZUp,
+        );
+        assert_eq!((-20, 10, -41), (cal.x, cal.y, cal.z));
+        assert_near(41.0 / 1024.0 - 20.0 * 0.00196, cal.residual.x);
+        assert_near(-20.0 / 1024.0 + 10.0 * 0.00196, cal.residual.y);
+        assert_near(82.0 / 1024.0 - 41.0 * 0.00196, cal.residual.z);
+    }
+
+    #[test]
+    fn can_compute_offset_calibration_in_other_orientations() {
+        let cal = get_offset_calibration([-256, 0, 0], 1, 256.0, CalibrationOrientation::XDown);
+        assert_eq!((0, 0, 0), (cal.x, cal.y, cal.z));
+        let cal = get_offset_calibration([0, 250, 0], 1, 256.0, CalibrationOrientation::YUp);
+        assert_eq!((0, 12, 0), (cal.x, cal.y, cal.z));
+    }
+
+    #[test]
+    fn limits_offset_to_compensation_range() {
+        let cal = get_offset_calibration([0, 0, 0], 1, 1024.0, CalibrationOrientation::ZUp);
+        assert_eq!(127, cal.z);
+        assert_near(-1.0 + 127.0 * 0.00196, cal.residual.z);
+    }
+}
@@ -1,11 +1,86 @@
-use crate::{register_access::Register, Error, Mma8x5x};
+use crate::{
+    common::config::{get_data_rate, get_power_mode},
+    mode,
+    register_access::{BitFlags, Register},
+    Config, Error, Mma8x5x, OutputDataRate, PowerMode,
+};
+use core::marker::PhantomData;
 use embedded_hal::i2c::{I2c, SevenBitAddress};
 
+impl<I2C, IC> Mma8x5x<I2C, IC, mode::Standby> {
+    /// Create a driver with the shadow registers at the device reset values.
+    pub(crate) fn create(i2c: I2C, address: u8) -> Self {
+        Mma8x5x {
+            i2c,
+            address,
+            xyz_data_cfg: Config::default(),
+            f_setup: Config::default(),
+            hp_filter_cutoff: Config::default(),
+            ctrl_reg1: Config::default(),
+            ctrl_reg2: Config::default(),
+            ctrl_reg3: Config::default(),
+            ctrl_reg4: Config::default(),
+            ctrl_reg5: Config::default(),
+            pl_cfg: Config {
+                bits: BitFlags::DBCNTM,
+            },
+            _ic: PhantomData,
+            _mode: PhantomData,
+        }
+    }
+}
+
 impl<I2C, IC, MODE> Mma8x5x<I2C, IC, MODE> {
     /// Destroy driver instance, return I²C bus and delay instance.
     pub fn destroy(self) -> I2C {
         self.i2c
     }
+
+    /// Change the mode marker keeping the bus, address and shadow registers.
+    pub(crate) fn into_mode<MODE2>(self) -> Mma8x5x<I2C, IC, MODE2> {
+        Mma8x5x {
+            i2c: self.i2c,
+            address: self.address,
+            ctrl_reg1: self.ctrl_reg1,
+            ctrl_reg2: self.ctrl_reg2,
+            ctrl_reg3: self.ctrl_reg3,
+            ctrl_reg4: self.ctrl_reg4,
+            ctrl_reg5: self.ctrl_reg5,
+            pl_cfg: self.pl_cfg,
+            xyz_data_cfg: self.xyz_data_cfg,
+            f_setup: self.f_setup,
+            hp_filter_cutoff: self.hp_filter_cutoff,
+            _ic: PhantomData,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Set the shadow registers to the device reset values.
+    pub(crate) fn reset_shadow_registers(&mut self) {
+        self.ctrl_reg1 = Config::default();
+        self.ctrl_reg2 = Config::default();
+        self.ctrl_reg3 = Config::default();
+        self.ctrl_reg4 = Config::default();
+        self.ctrl_reg5 = Config::default();
+        self.pl_cfg = Config {
+            bits: BitFlags::DBCNTM,
+        };
+        self.xyz_data_cfg = Config::default();
+        self.f_setup = Config::default();
+        self.hp_filter_cutoff = Config::default();
+    }
+
+    /// Output data rate configured through the driver
+    ///
+    /// After attaching to a running device, call
+    /// [`sync_from_device()`](#method.sync_from_device) first.
+    pub fn data_rate(&self) -> OutputDataRate {
+        get_data_rate(self.ctrl_reg1.bits)
+    }
+
+    pub(crate) fn wake_power_mode(&self) -> PowerMode {
+        get_power_mode(self.ctrl_reg2.bits)
+    }
 }
 
 impl<E, I2C, IC, MODE> Mma8x5x<I2C, IC, MODE>
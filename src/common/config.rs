@@ -12,20 +12,7 @@ where
 {
     /// Set G scale: +/-2g, +/-4g, +/-8g
     pub fn set_scale(&mut self, scale: GScale) -> Result<(), Error<E>> {
-        let config = match scale {
-            GScale::G2 => self
-                .xyz_data_cfg
-                .with_low(BitFlags::FS1)
-                .with_low(BitFlags::FS0),
-            GScale::G4 => self
-                .xyz_data_cfg
-                .with_low(BitFlags::FS1)
-                .with_high(BitFlags::FS0),
-            GScale::G8 => self
-                .xyz_data_cfg
-                .with_high(BitFlags::FS1)
-                .with_low(BitFlags::FS0),
-        };
+        let config = get_scale_config(self.xyz_data_cfg, scale);
         self.write_reg(Register::XYZ_DATA_CFG, config.bits)?;
         self.xyz_data_cfg = config;
         Ok(())
@@ -33,10 +20,7 @@ where
 
     /// Set read mode (Normal/Fast)
     pub fn set_read_mode(&mut self, mode: ReadMode) -> Result<(), Error<E>> {
-        let config = match mode {
-            ReadMode::Normal => self.ctrl_reg1.with_low(BitFlags::F_READ),
-            ReadMode::Fast => self.ctrl_reg1.with_high(BitFlags::F_READ),
-        };
+        let config = get_read_mode_config(self.ctrl_reg1, mode);
         self.write_reg(Register::CTRL_REG1, config.bits)?;
         self.ctrl_reg1 = config;
         Ok(())
@@ -53,35 +37,17 @@ where
 
     /// Set output data rate in WAKE mode
     pub fn set_data_rate(&mut self, rate: OutputDataRate) -> Result<(), Error<E>> {
-        let bits = self.ctrl_reg1.bits & !(BitFlags::ODR0 | BitFlags::ODR1 | BitFlags::ODR2);
-        let mask = match rate {
-            OutputDataRate::Hz800 => 0,
-            OutputDataRate::Hz400 => BitFlags::ODR0,
-            OutputDataRate::Hz200 => BitFlags::ODR1,
-            OutputDataRate::Hz100 => BitFlags::ODR1 | BitFlags::ODR0,
-            OutputDataRate::Hz50 => BitFlags::ODR2,
-            OutputDataRate::Hz12_5 => BitFlags::ODR2 | BitFlags::ODR0,
-            OutputDataRate::Hz6_25 => BitFlags::ODR2 | BitFlags::ODR1,
-            OutputDataRate::Hz1_56 => BitFlags::ODR2 | BitFlags::ODR1 | BitFlags::ODR0,
-        };
-        let bits = bits | mask;
-        self.write_reg(Register::CTRL_REG1, bits)?;
-        self.ctrl_reg1 = Config { bits };
+        let config = get_data_rate_config(self.ctrl_reg1, rate);
+        self.write_reg(Register::CTRL_REG1, config.bits)?;
+        self.ctrl_reg1 = config;
         Ok(())
     }
 
     /// Set power mode in WAKE mode
     pub fn set_wake_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Error<E>> {
-        let bits = self.ctrl_reg2.bits & !(BitFlags::MODS0 | BitFlags::MODS1);
-        let mask = match power_mode {
-            PowerMode::Normal => 0,
-            PowerMode::LowNoiseLowPower => BitFlags::MODS0,
-            PowerMode::HighResolution => BitFlags::MODS1,
-            PowerMode::LowPower => BitFlags::MODS1 | BitFlags::MODS0,
-        };
-        let bits = bits | mask;
-        self.write_reg(Register::CTRL_REG2, bits)?;
-        self.ctrl_reg2 = Config { bits };
+        let config = get_wake_power_mode_config(self.ctrl_reg2, power_mode);
+        self.write_reg(Register::CTRL_REG2, config.bits)?;
+        self.ctrl_reg2 = config;
         Ok(())
     }
 
@@ -114,13 +80,75 @@ where
     pub(crate) fn reset_internal(&mut self) -> Result<(), Error<E>> {
         let config = self.ctrl_reg2.with_high(BitFlags::RST);
         self.write_reg(Register::CTRL_REG2, config.bits)?;
-        self.ctrl_reg1 = Config::default();
-        self.ctrl_reg2 = Config::default();
-        self.ctrl_reg3 = Config::default();
-        self.pl_cfg = Config {
-            bits: BitFlags::DBCNTM,
-        };
-        self.xyz_data_cfg = Config::default();
+        self.reset_shadow_registers();
         Ok(())
     }
 }
+
+pub(crate) fn get_scale_config(xyz_data_cfg: Config, scale: GScale) -> Config {
+    match scale {
+        GScale::G2 => xyz_data_cfg.with_low(BitFlags::FS1).with_low(BitFlags::FS0),
+        GScale::G4 => xyz_data_cfg
+            .with_low(BitFlags::FS1)
+            .with_high(BitFlags::FS0),
+        GScale::G8 => xyz_data_cfg
+            .with_high(BitFlags::FS1)
+            .with_low(BitFlags::FS0),
+    }
+}
+
+pub(crate) fn get_read_mode_config(ctrl_reg1: Config, mode: ReadMode) -> Config {
+    match mode {
+        ReadMode::Normal => ctrl_reg1.with_low(BitFlags::F_READ),
+        ReadMode::Fast => ctrl_reg1.with_high(BitFlags::F_READ),
+    }
+}
+
+pub(crate) fn get_data_rate_config(ctrl_reg1: Config, rate: OutputDataRate) -> Config {
+    let bits = ctrl_reg1.bits & !(BitFlags::ODR0 | BitFlags::ODR1 | BitFlags::ODR2);
+    let mask = match rate {
+        OutputDataRate::Hz800 => 0,
+        OutputDataRate::Hz400 => BitFlags::ODR0,
+        OutputDataRate::Hz200 => BitFlags::ODR1,
+        OutputDataRate::Hz100 => BitFlags::ODR1 | BitFlags::ODR0,
+        OutputDataRate::Hz50 => BitFlags::ODR2,
+        OutputDataRate::Hz12_5 => BitFlags::ODR2 | BitFlags::ODR0,
+        OutputDataRate::Hz6_25 => BitFlags::ODR2 | BitFlags::ODR1,
+        OutputDataRate::Hz1_56 => BitFlags::ODR2 | BitFlags::ODR1 | BitFlags::ODR0,
+    };
+    Config { bits: bits | mask }
+}
+
+pub(crate) fn get_data_rate(ctrl_reg1: u8) -> OutputDataRate {
+    match (ctrl_reg1 >> 3) & 0b111 {
+        0 => OutputDataRate::Hz800,
+        1 => OutputDataRate::Hz400,
+        2 => OutputDataRate::Hz200,
+        3 => OutputDataRate::Hz100,
+        4 => OutputDataRate::Hz50,
+        5 => OutputDataRate::Hz12_5,
+        6 => OutputDataRate::Hz6_25,
+        _ => OutputDataRate::Hz1_56,
+    }
+}
+
+/// Power mode from the MODS or SMODS bits shifted to the lowest position
+pub(crate) fn get_power_mode(mods: u8) -> PowerMode {
+    match mods & 0b11 {
+        0 => PowerMode::Normal,
+        1 => PowerMode::LowNoiseLowPower,
+        2 => PowerMode::HighResolution,
+        _ => PowerMode::LowPower,
+    }
+}
+
+pub(crate) fn get_wake_power_mode_config(ctrl_reg2: Config, power_mode: PowerMode) -> Config {
+    let bits = ctrl_reg2.bits & !(BitFlags::MODS0 | BitFlags::MODS1);
+    let mask = match power_mode {
+        PowerMode::Normal => 0,
+        PowerMode::LowNoiseLowPower => BitFlags::MODS0,
+        PowerMode::HighResolution => BitFlags::MODS1,
+        PowerMode::LowPower => BitFlags::MODS1 | BitFlags::MODS0,
+    };
+    Config { bits: bits | mask }
+}
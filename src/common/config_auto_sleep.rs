@@ -13,16 +13,9 @@ where
 {
     /// Set power mode in sleep mode
     pub fn set_sleep_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Error<E>> {
-        let bits = self.ctrl_reg2.bits & !(BitFlags::SMODS0 | BitFlags::SMODS1);
-        let mask = match power_mode {
-            PowerMode::Normal => 0,
-            PowerMode::LowNoiseLowPower => BitFlags::SMODS0,
-            PowerMode::HighResolution => BitFlags::SMODS1,
-            PowerMode::LowPower => BitFlags::SMODS1 | BitFlags::SMODS0,
-        };
-        let bits = bits | mask;
-        self.write_reg(Register::CTRL_REG2, bits)?;
-        self.ctrl_reg2 = Config { bits };
+        let config = get_sleep_power_mode_config(self.ctrl_reg2, power_mode);
+        self.write_reg(Register::CTRL_REG2, config.bits)?;
+        self.ctrl_reg2 = config;
         Ok(())
     }
 
@@ -44,16 +37,9 @@ where
 
     /// Set sampling rate used in auto-sleep mode
     pub fn set_auto_sleep_data_rate(&mut self, rate: AutoSleepDataRate) -> Result<(), Error<E>> {
-        let bits = self.ctrl_reg1.bits & !(BitFlags::ASLP_RATE1 | BitFlags::ASLP_RATE0);
-        let mask = match rate {
-            AutoSleepDataRate::Hz50 => 0,
-            AutoSleepDataRate::Hz12_5 => BitFlags::ASLP_RATE0,
-            AutoSleepDataRate::Hz6_25 => BitFlags::ASLP_RATE1,
-            AutoSleepDataRate::Hz1_56 => BitFlags::ASLP_RATE1 | BitFlags::ASLP_RATE0,
-        };
-        let bits = bits | mask;
-        self.write_reg(Register::CTRL_REG1, bits)?;
-        self.ctrl_reg1 = Config { bits };
+        let config = get_auto_sleep_data_rate_config(self.ctrl_reg1, rate);
+        self.write_reg(Register::CTRL_REG1, config.bits)?;
+        self.ctrl_reg1 = config;
         Ok(())
     }
 
@@ -62,3 +48,28 @@ where
         self.write_reg(Register::ASLP_COUNT, count)
     }
 }
+
+pub(crate) fn get_sleep_power_mode_config(ctrl_reg2: Config, power_mode: PowerMode) -> Config {
+    let bits = ctrl_reg2.bits & !(BitFlags::SMODS0 | BitFlags::SMODS1);
+    let mask = match power_mode {
+        PowerMode::Normal => 0,
+        PowerMode::LowNoiseLowPower => BitFlags::SMODS0,
+        PowerMode::HighResolution => BitFlags::SMODS1,
+        PowerMode::LowPower => BitFlags::SMODS1 | BitFlags::SMODS0,
+    };
+    Config { bits: bits | mask }
+}
+
+pub(crate) fn get_auto_sleep_data_rate_config(
+    ctrl_reg1: Config,
+    rate: AutoSleepDataRate,
+) -> Config {
+    let bits = ctrl_reg1.bits & !(BitFlags::ASLP_RATE1 | BitFlags::ASLP_RATE0);
+    let mask = match rate {
+        AutoSleepDataRate::Hz50 => 0,
+        AutoSleepDataRate::Hz12_5 => BitFlags::ASLP_RATE0,
+        AutoSleepDataRate::Hz6_25 => BitFlags::ASLP_RATE1,
+        AutoSleepDataRate::Hz1_56 => BitFlags::ASLP_RATE1 | BitFlags::ASLP_RATE0,
+    };
+    Config { bits: bits | mask }
+}
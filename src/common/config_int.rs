@@ -17,10 +17,7 @@ where
         &mut self,
         polarity: InterruptPinPolarity,
     ) -> Result<(), Error<E>> {
-        let config = match polarity {
-            InterruptPinPolarity::ActiveLow => self.ctrl_reg3.with_low(BitFlags::IPOL),
-            InterruptPinPolarity::ActiveHigh => self.ctrl_reg3.with_high(BitFlags::IPOL),
-        };
+        let config = get_pin_polarity_config(self.ctrl_reg3, polarity);
         self.write_reg(Register::CTRL_REG3, config.bits)?;
         self.ctrl_reg3 = config;
         Ok(())
@@ -31,10 +28,7 @@ where
         &mut self,
         configuration: InterruptPinConfiguration,
     ) -> Result<(), Error<E>> {
-        let config = match configuration {
-            InterruptPinConfiguration::PushPull => self.ctrl_reg3.with_low(BitFlags::PP_OD),
-            InterruptPinConfiguration::OpenDrain => self.ctrl_reg3.with_high(BitFlags::PP_OD),
-        };
+        let config = get_pin_configuration_config(self.ctrl_reg3, configuration);
         self.write_reg(Register::CTRL_REG3, config.bits)?;
         self.ctrl_reg3 = config;
         Ok(())
@@ -42,28 +36,61 @@ where
 
     /// Set enabled interrupts
     pub fn set_enabled_interrupts(&mut self, enabled: EnabledInterrupts) -> Result<(), Error<E>> {
-        self.write_reg(Register::CTRL_REG4, get_enabled_int_reg(enabled))
+        let config = Config {
+            bits: get_enabled_int_reg(enabled),
+        };
+        self.write_reg(Register::CTRL_REG4, config.bits)?;
+        self.ctrl_reg4 = config;
+        Ok(())
     }
 
     /// Set interrupt source pin routes
     pub fn set_interrupt_pin_routes(&mut self, routes: InterruptPinRoutes) -> Result<(), Error<E>> {
-        self.write_reg(Register::CTRL_REG5, get_int_routes_reg(routes))
+        let config = Config {
+            bits: get_int_routes_reg(routes),
+        };
+        self.write_reg(Register::CTRL_REG5, config.bits)?;
+        self.ctrl_reg5 = config;
+        Ok(())
     }
 
     /// Set interrupts that wake the device
     pub fn set_wake_interrupts(&mut self, interrupts: WakeInterrupts) -> Result<(), Error<E>> {
-        let config = self.ctrl_reg3.bits
-            & !(BitFlags::WAKE_FF_MT
-                | BitFlags::WAKE_LNDPRT
-                | BitFlags::WAKE_PULSE
-                | BitFlags::WAKE_TRANS);
-        let config = config | get_wake_int_mask(interrupts);
-        self.write_reg(Register::CTRL_REG3, config)?;
-        self.ctrl_reg3 = Config { bits: config };
+        let config = get_wake_ints_config(self.ctrl_reg3, interrupts);
+        self.write_reg(Register::CTRL_REG3, config.bits)?;
+        self.ctrl_reg3 = config;
         Ok(())
     }
 }
 
+pub(crate) fn get_pin_polarity_config(ctrl_reg3: Config, polarity: InterruptPinPolarity) -> Config {
+    match polarity {
+        InterruptPinPolarity::ActiveLow => ctrl_reg3.with_low(BitFlags::IPOL),
+        InterruptPinPolarity::ActiveHigh => ctrl_reg3.with_high(BitFlags::IPOL),
+    }
+}
+
+pub(crate) fn get_pin_configuration_config(
+    ctrl_reg3: Config,
+    configuration: InterruptPinConfiguration,
+) -> Config {
+    match configuration {
+        InterruptPinConfiguration::PushPull => ctrl_reg3.with_low(BitFlags::PP_OD),
+        InterruptPinConfiguration::OpenDrain => ctrl_reg3.with_high(BitFlags::PP_OD),
+    }
+}
+
+pub(crate) fn get_wake_ints_config(ctrl_reg3: Config, interrupts: WakeInterrupts) -> Config {
+    let bits = ctrl_reg3.bits
+        & !(BitFlags::WAKE_FF_MT
+            | BitFlags::WAKE_LNDPRT
+            | BitFlags::WAKE_PULSE
+            | BitFlags::WAKE_TRANS);
+    Config {
+        bits: bits | get_wake_int_mask(interrupts),
+    }
+}
+
 fn get_wake_int_mask(wake_ints: WakeInterrupts) -> u8 {
     (if wake_ints.transient {
         BitFlags::WAKE_TRANS
@@ -84,7 +111,7 @@ fn get_wake_int_mask(wake_ints: WakeInterrupts) -> u8 {
     })
 }
 
-fn get_int_routes_reg(routes: InterruptPinRoutes) -> u8 {
+pub(crate) fn get_int_routes_reg(routes: InterruptPinRoutes) -> u8 {
     get_int_source_pin_route_reg(routes.auto_sleep, BitFlags::INT_CFG_ASLP)
         | get_int_source_pin_route_reg(routes.fifo, BitFlags::INT_CFG_FIFO)
         | get_int_source_pin_route_reg(routes.transient, BitFlags::INT_CFG_TRANS)
@@ -101,7 +128,7 @@ fn get_int_source_pin_route_reg(route: InterruptSourcePinRoute, flag: u8) -> u8
     }
 }
 
-fn get_enabled_int_reg(en_int: EnabledInterrupts) -> u8 {
+pub(crate) fn get_enabled_int_reg(en_int: EnabledInterrupts) -> u8 {
     (if en_int.auto_sleep {
         BitFlags::INT_EN_ASLP
     } else {
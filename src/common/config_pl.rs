@@ -3,7 +3,8 @@ use embedded_hal::i2c::{I2c, SevenBitAddress};
 use crate::{
     ic, mode,
     register_access::{BitFlags, Register},
-    DebounceCounterMode, Error, Mma8x5x,
+    BackFrontTripAngle, Config, DebounceCounterMode, Error, Mma8x5x, PortraitLandscapeAngles,
+    PortraitLandscapeHysteresis, PortraitLandscapeThreshold, ZLockoutAngle,
 };
 
 /// Portrait/landscape detection configuration
@@ -29,10 +30,7 @@ where
 
     /// Set portrait/landscape debounce counter mode
     pub fn set_debounce_counter_mode(&mut self, mode: DebounceCounterMode) -> Result<(), Error<E>> {
-        let config = match mode {
-            DebounceCounterMode::Decrement => self.pl_cfg.with_low(BitFlags::DBCNTM),
-            DebounceCounterMode::Clear => self.pl_cfg.with_high(BitFlags::DBCNTM),
-        };
+        let config = get_debounce_counter_mode_config(self.pl_cfg, mode);
         self.write_reg(Register::PL_CFG, config.bits)?;
         self.pl_cfg = config;
         Ok(())
@@ -53,20 +51,136 @@ where
     }
 }
 
-macro_rules! set_allowed_in_active_mode {
-    ($ic:ident) => {
-        impl<E, I2C> Mma8x5x<I2C, ic::$ic, mode::Active>
-        where
-            I2C: I2c<SevenBitAddress, Error = E>,
-        {
-            /// Set portrait/landscape debounce counter
-            pub fn set_debounce_counter(&mut self, counter: u8) -> Result<(), Error<E>> {
-                self.set_debounce_counter_internal(counter)
-            }
-        }
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasProgrammableOrientation,
+{
+    /// Set portrait/landscape debounce counter
+    pub fn set_debounce_counter(&mut self, counter: u8) -> Result<(), Error<E>> {
+        self.set_debounce_counter_internal(counter)
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasProgrammableOrientation,
+{
+    /// Set portrait/landscape back/front trip, Z-lockout, threshold and hysteresis angles
+    pub fn set_portrait_landscape_angles(
+        &mut self,
+        angles: PortraitLandscapeAngles,
+    ) -> Result<(), Error<E>> {
+        let payload = [
+            Register::PL_BF_ZCOMP,
+            get_bf_zcomp_reg(angles.back_front_trip, angles.z_lockout),
+            get_pl_ths_reg(angles.threshold, angles.hysteresis),
+        ];
+        self.i2c.write(self.address, &payload).map_err(Error::I2C)
+    }
+}
+
+pub(crate) fn get_debounce_counter_mode_config(
+    pl_cfg: Config,
+    mode: DebounceCounterMode,
+) -> Config {
+    match mode {
+        DebounceCounterMode::Decrement => pl_cfg.with_low(BitFlags::DBCNTM),
+        DebounceCounterMode::Clear => pl_cfg.with_high(BitFlags::DBCNTM),
+    }
+}
+
+pub(crate) fn get_bf_zcomp_reg(back_front: BackFrontTripAngle, z_lockout: ZLockoutAngle) -> u8 {
+    let bkfr = match back_front {
+        BackFrontTripAngle::Deg80 => 0,
+        BackFrontTripAngle::Deg75 => 1,
+        BackFrontTripAngle::Deg70 => 2,
+        BackFrontTripAngle::Deg65 => 3,
+    };
+    let zlock = match z_lockout {
+        ZLockoutAngle::Deg14 => 0,
+        ZLockoutAngle::Deg18 => 1,
+        ZLockoutAngle::Deg21 => 2,
+        ZLockoutAngle::Deg25 => 3,
+        ZLockoutAngle::Deg29 => 4,
+        ZLockoutAngle::Deg33 => 5,
+        ZLockoutAngle::Deg37 => 6,
+        ZLockoutAngle::Deg42 => 7,
+    };
+    (bkfr << 6) | zlock
+}
+
+pub(crate) fn get_pl_ths_reg(
+    threshold: PortraitLandscapeThreshold,
+    hysteresis: PortraitLandscapeHysteresis,
+) -> u8 {
+    let ths = match threshold {
+        PortraitLandscapeThreshold::Deg15 => 0x07,
+        PortraitLandscapeThreshold::Deg20 => 0x09,
+        PortraitLandscapeThreshold::Deg30 => 0x0C,
+        PortraitLandscapeThreshold::Deg35 => 0x0D,
+        PortraitLandscapeThreshold::Deg40 => 0x0F,
+        PortraitLandscapeThreshold::Deg45 => 0x10,
+        PortraitLandscapeThreshold::Deg55 => 0x13,
+        PortraitLandscapeThreshold::Deg60 => 0x14,
+        PortraitLandscapeThreshold::Deg70 => 0x17,
+        PortraitLandscapeThreshold::Deg75 => 0x19,
+    };
+    let hys = match hysteresis {
+        PortraitLandscapeHysteresis::Deg0 => 0,
+        PortraitLandscapeHysteresis::Deg4 => 1,
+        PortraitLandscapeHysteresis::Deg7 => 2,
+        PortraitLandscapeHysteresis::Deg11 => 3,
+        PortraitLandscapeHysteresis::Deg14 => 4,
+        PortraitLandscapeHysteresis::Deg17 => 5,
+        PortraitLandscapeHysteresis::Deg21 => 6,
+        PortraitLandscapeHysteresis::Deg24 => 7,
     };
+    (ths << 3) | hys
 }
 
-// Only these two models allow changing these registers in active mode
-set_allowed_in_active_mode!(Mma8451);
-set_allowed_in_active_mode!(Mma8652);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_angles_match_reset_values() {
+        let angles = PortraitLandscapeAngles::default();
+        assert_eq!(
+            0x44,
+            get_bf_zcomp_reg(angles.back_front_trip, angles.z_lockout)
+        );
+        assert_eq!(0x84, get_pl_ths_reg(angles.threshold, angles.hysteresis));
+    }
+
+    #[test]
+    fn bf_zcomp_limits() {
+        assert_eq!(
+            0,
+            get_bf_zcomp_reg(BackFrontTripAngle::Deg80, ZLockoutAngle::Deg14)
+        );
+        assert_eq!(
+            0xC7,
+            get_bf_zcomp_reg(BackFrontTripAngle::Deg65, ZLockoutAngle::Deg42)
+        );
+    }
+
+    #[test]
+    fn pl_ths_limits() {
+        assert_eq!(
+            0x07 << 3,
+            get_pl_ths_reg(
+                PortraitLandscapeThreshold::Deg15,
+                PortraitLandscapeHysteresis::Deg0
+            )
+        );
+        assert_eq!(
+            (0x19 << 3) | 7,
+            get_pl_ths_reg(
+                PortraitLandscapeThreshold::Deg75,
+                PortraitLandscapeHysteresis::Deg24
+            )
+        );
+    }
+}
@@ -0,0 +1,764 @@
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    common::{
+        config::{
+            get_data_rate, get_data_rate_config, get_power_mode, get_read_mode_config,
+            get_scale_config, get_wake_power_mode_config,
+        },
+        config_auto_sleep::{get_auto_sleep_data_rate_config, get_sleep_power_mode_config},
+        config_int::{
+            get_enabled_int_reg, get_int_routes_reg, get_pin_polarity_config, get_wake_ints_config,
+        },
+        config_pl::{get_bf_zcomp_reg, get_pl_ths_reg},
+        fifo::get_fifo_mode_config,
+    },
+    ic::private::Sealed,
+    mode,
+    register_access::{BitFlags, Register},
+    AutoSleepDataRate, BackFrontTripAngle, Config, DetectedIc, DeviceConfig, EnabledInterrupts,
+    Error, FifoMode, GScale, InterruptPinPolarity, InterruptPinRoutes, InterruptSourcePinRoute,
+    Mma8x5x, OutputDataRate, PortraitLandscapeAngles, PortraitLandscapeHysteresis,
+    PortraitLandscapeThreshold, PowerMode, ReadMode, WakeInterrupts, ZLockoutAngle,
+};
+
+/// Version of the binary encoding
+const ENCODING_VERSION: u8 = 1;
+
+/// Configuration registers in the order of the fields and of the binary encoding
+const CONFIG_REGISTERS: [u8; 30] = [
+    Register::F_SETUP,
+    Register::TRIG_CFG,
+    Register::XYZ_DATA_CFG,
+    Register::HP_FILTER_CUTOFF,
+    Register::PL_CFG,
+    Register::PL_COUNT,
+    Register::PL_BF_ZCOMP,
+    Register::P_L_THS_REG,
+    Register::FF_MT_CFG,
+    Register::FF_MT_THS,
+    Register::FF_MT_COUNT,
+    Register::TRANSIENT_CFG,
+    Register::TRANSIENT_THS,
+    Register::TRANSIENT_COUNT,
+    Register::PULSE_CFG,
+    Register::PULSE_THSX,
+    Register::PULSE_THSY,
+    Register::PULSE_THSZ,
+    Register::PULSE_TMLT,
+    Register::PULSE_LTCY,
+    Register::PULSE_WIND,
+    Register::ASLP_COUNT,
+    Register::CTRL_REG1,
+    Register::CTRL_REG2,
+    Register::CTRL_REG3,
+    Register::CTRL_REG4,
+    Register::CTRL_REG5,
+    Register::OFF_X,
+    Register::OFF_Y,
+    Register::OFF_Z,
+];
+
+/// Register contents indexed by address
+pub(crate) type Registers = [u8; Register::OFF_Z as usize + 1];
+
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        DeviceConfig {
+            f_setup: 0,
+            trig_cfg: 0,
+            xyz_data_cfg: 0,
+            hp_filter_cutoff: 0,
+            pl_cfg: BitFlags::DBCNTM,
+            pl_count: 0,
+            pl_bf_zcomp: get_bf_zcomp_reg(Default::default(), Default::default()),
+            pl_ths: get_pl_ths_reg(Default::default(), Default::default()),
+            ff_mt_cfg: 0,
+            ff_mt_ths: 0,
+            ff_mt_count: 0,
+            transient_cfg: 0,
+            transient_ths: 0,
+            transient_count: 0,
+            pulse_cfg: 0,
+            pulse_thsx: 0,
+            pulse_thsy: 0,
+            pulse_thsz: 0,
+            pulse_tmlt: 0,
+            pulse_ltcy: 0,
+            pulse_wind: 0,
+            aslp_count: 0,
+            ctrl_reg1: 0,
+            ctrl_reg2: 0,
+            ctrl_reg3: 0,
+            ctrl_reg4: 0,
+            ctrl_reg5: 0,
+            offset_x: 0,
+            offset_y: 0,
+            offset_z: 0,
+        }
+    }
+}
+
+fn cfg(bits: u8) -> Config {
+    Config { bits }
+}
+
+fn with_flag(bits: u8, flag: u8, high: bool) -> u8 {
+    if high {
+        bits | flag
+    } else {
+        bits & !flag
+    }
+}
+
+fn checksum(data: &[u8]) -> u8 {
+    data.iter()
+        .fold(0u8, |sum, b| sum.wrapping_add(*b))
+        .wrapping_neg()
+}
+
+impl DeviceConfig {
+    /// Size of the binary encoding in bytes
+    pub const ENCODED_SIZE: usize = 32;
+
+    /// G scale
+    pub fn scale(&self) -> GScale {
+        match self.xyz_data_cfg & (BitFlags::FS1 | BitFlags::FS0) {
+            0 => GScale::G2,
+            BitFlags::FS0 => GScale::G4,
+            _ => GScale::G8,
+        }
+    }
+
+    /// Set the G scale
+    pub fn set_scale(&mut self, scale: GScale) {
+        self.xyz_data_cfg = get_scale_config(cfg(self.xyz_data_cfg), scale).bits;
+    }
+
+    /// Output data rate
+    pub fn data_rate(&self) -> OutputDataRate {
+        get_data_rate(self.ctrl_reg1)
+    }
+
+    /// Set the output data rate
+    pub fn set_data_rate(&mut self, rate: OutputDataRate) {
+        self.ctrl_reg1 = get_data_rate_config(cfg(self.ctrl_reg1), rate).bits;
+    }
+
+    /// Read mode
+    pub fn read_mode(&self) -> ReadMode {
+        if (self.ctrl_reg1 & BitFlags::F_READ) != 0 {
+            ReadMode::Fast
+        } else {
+            ReadMode::Normal
+        }
+    }
+
+    /// Set the read mode
+    pub fn set_read_mode(&mut self, mode: ReadMode) {
+        self.ctrl_reg1 = get_read_mode_config(cfg(self.ctrl_reg1), mode).bits;
+    }
+
+    /// Power mode in wake mode
+    pub fn wake_power_mode(&self) -> PowerMode {
+        get_power_mode(self.ctrl_reg2)
+    }
+
+    /// Set the power mode in wake mode
+    pub fn set_wake_power_mode(&mut self, power_mode: PowerMode) {
+        self.ctrl_reg2 = get_wake_power_mode_config(cfg(self.ctrl_reg2), power_mode).bits;
+    }
+
+    /// Power mode in sleep mode
+    pub fn sleep_power_mode(&self) -> PowerMode {
+        get_power_mode(self.ctrl_reg2 >> 3)
+    }
+
+    /// Set the power mode in sleep mode
+    pub fn set_sleep_power_mode(&mut self, power_mode: PowerMode) {
+        self.ctrl_reg2 = get_sleep_power_mode_config(cfg(self.ctrl_reg2), power_mode).bits;
+    }
+
+    /// Whether auto-sleep is enabled
+    pub fn auto_sleep_enabled(&self) -> bool {
+        (self.ctrl_reg2 & BitFlags::SLPE) != 0
+    }
+
+    /// Enable or disable auto-sleep
+    pub fn set_auto_sleep_enabled(&mut self, enabled: bool) {
+        self.ctrl_reg2 = with_flag(self.ctrl_reg2, BitFlags::SLPE, enabled);
+    }
+
+    /// Data rate in sleep mode
+    pub fn auto_sleep_data_rate(&self) -> AutoSleepDataRate {
+        match self.ctrl_reg1 >> 6 {
+            0 => AutoSleepDataRate::Hz50,
+            1 => AutoSleepDataRate::Hz12_5,
+            2 => AutoSleepDataRate::Hz6_25,
+            _ => AutoSleepDataRate::Hz1_56,
+        }
+    }
+
+    /// Set the data rate in sleep mode
+    pub fn set_auto_sleep_data_rate(&mut self, rate: AutoSleepDataRate) {
+        self.ctrl_reg1 = get_auto_sleep_data_rate_config(cfg(self.ctrl_reg1), rate).bits;
+    }
+
+    /// Auto-sleep count (ASLP_COUNT register)
+    pub fn auto_sleep_count(&self) -> u8 {
+        self.aslp_count
+    }
+
+    /// Set the auto-sleep count (ASLP_COUNT register)
+    pub fn set_auto_sleep_count(&mut self, count: u8) {
+        self.aslp_count = count;
+    }
+
+    /// Enabled interrupts
+    pub fn enabled_interrupts(&self) -> EnabledInterrupts {
+        let r = self.ctrl_reg4;
+        EnabledInterrupts {
+            auto_sleep: (r & BitFlags::INT_EN_ASLP) != 0,
+            fifo: (r & BitFlags::INT_EN_FIFO) != 0,
+            transient: (r & BitFlags::INT_EN_TRANS) != 0,
+            portrait_landscape: (r & BitFlags::INT_EN_LNDPRT) != 0,
+            pulse: (r & BitFlags::INT_EN_PULSE) != 0,
+            freefall_motion: (r & BitFlags::INT_EN_FF_MT) != 0,
+            data_ready: (r & BitFlags::INT_EN_DRDY) != 0,
+        }
+    }
+
+    /// Set the enabled interrupts
+    pub fn set_enabled_interrupts(&mut self, enabled: EnabledInterrupts) {
+        self.ctrl_reg4 = get_enabled_int_reg(enabled);
+    }
+
+    /// Interrupt pin routes
+    pub fn interrupt_pin_routes(&self) -> InterruptPinRoutes {
+        let route = |flag: u8| {
+            if (self.ctrl_reg5 & flag) != 0 {
+                InterruptSourcePinRoute::Int1
+            } else {
+                InterruptSourcePinRoute::Int2
+            }
+        };
+        InterruptPinRoutes {
+            auto_sleep: route(BitFlags::INT_CFG_ASLP),
+            fifo: route(BitFlags::INT_CFG_FIFO),
+            transient: route(BitFlags::INT_CFG_TRANS),
+            portrait_landscape: route(BitFlags::INT_CFG_LNDPRT),
+            pulse: route(BitFlags::INT_CFG_PULSE),
+            freefall_motion: route(BitFlags::INT_CFG_FF_MT),
+            data_ready: route(BitFlags::INT_CFG_DRDY),
+        }
+    }
+
+    /// Set the interrupt pin routes
+    pub fn set_interrupt_pin_routes(&mut self, routes: InterruptPinRoutes) {
+        self.ctrl_reg5 = get_int_routes_reg(routes);
+    }
+
+    /// Interrupts that wake the device from sleep
+    pub fn wake_interrupts(&self) -> WakeInterrupts {
+        let r = self.ctrl_reg3;
+        WakeInterrupts {
+            transient: (r & BitFlags::WAKE_TRANS) != 0,
+            portrait_landscape: (r & BitFlags::WAKE_LNDPRT) != 0,
+            pulse: (r & BitFlags::WAKE_PULSE) != 0,
+            freefall_motion: (r & BitFlags::WAKE_FF_MT) != 0,
+        }
+    }
+
+    /// Set the interrupts that wake the device from sleep
+    pub fn set_wake_interrupts(&mut self, interrupts: WakeInterrupts) {
+        self.ctrl_reg3 = get_wake_ints_config(cfg(self.ctrl_reg3), interrupts).bits;
+    }
+
+    /// Interrupt pin polarity
+    pub fn interrupt_pin_polarity(&self) -> InterruptPinPolarity {
+        if (self.ctrl_reg3 & BitFlags::IPOL) != 0 {
+            InterruptPinPolarity::ActiveHigh
+        } else {
+            InterruptPinPolarity::ActiveLow
+        }
+    }
+
+    /// Set the interrupt pin polarity
+    pub fn set_interrupt_pin_polarity(&mut self, polarity: InterruptPinPolarity) {
+        self.ctrl_reg3 = get_pin_polarity_config(cfg(self.ctrl_reg3), polarity).bits;
+    }
+
+    /// Whether portrait/landscape detection is enabled
+    pub fn portrait_landscape_enabled(&self) -> bool {
+        (self.pl_cfg & BitFlags::PL_EN) != 0
+    }
+
+    /// Enable or disable portrait/landscape detection
+    pub fn set_portrait_landscape_enabled(&mut self, enabled: bool) {
+        self.pl_cfg = with_flag(self.pl_cfg, BitFlags::PL_EN, enabled);
+    }
+
+    /// Portrait/landscape detection angles
+    ///
+    /// Returns `None` if the threshold does not correspond to any of the
+    /// [`PortraitLandscapeThreshold`] angles.
+    pub fn portrait_landscape_angles(&self) -> Option<PortraitLandscapeAngles> {
+        let back_front_trip = match self.pl_bf_zcomp >> 6 {
+            0 => BackFrontTripAngle::Deg80,
+            1 => BackFrontTripAngle::Deg75,
+            2 => BackFrontTripAngle::Deg70,
+            _ => BackFrontTripAngle::Deg65,
+        };
+        let z_lockout = match self.pl_bf_zcomp & 0b111 {
+            0 => ZLockoutAngle::Deg14,
+            1 => ZLockoutAngle::Deg18,
+            2 => ZLockoutAngle::Deg21,
+            3 => ZLockoutAngle::Deg25,
+            4 => ZLockoutAngle::Deg29,
+            5 => ZLockoutAngle::Deg33,
+            6 => ZLockoutAngle::Deg37,
+            _ => ZLockoutAngle::Deg42,
+        };
+        let threshold = match self.pl_ths >> 3 {
+            0x07 => PortraitLandscapeThreshold::Deg15,
+            0x09 => PortraitLandscapeThreshold::Deg20,
+            0x0C => PortraitLandscapeThreshold::Deg30,
+            0x0D => PortraitLandscapeThreshold::Deg35,
+            0x0F => PortraitLandscapeThreshold::Deg40,
+            0x10 => PortraitLandscapeThreshold::Deg45,
+            0x13 => PortraitLandscapeThreshold::Deg55,
+            0x14 => PortraitLandscapeThreshold::Deg60,
+            0x17 => PortraitLandscapeThreshold::Deg70,
+            0x19 => PortraitLandscapeThreshold::Deg75,
+            _ => return None,
+        };
+        let hysteresis = match self.pl_ths & 0b111 {
+            0 => PortraitLandscapeHysteresis::Deg0,
+            1 => PortraitLandscapeHysteresis::Deg4,
+            2 => PortraitLandscapeHysteresis::Deg7,
+            3 => PortraitLandscapeHysteresis::Deg11,
+            4 => PortraitLandscapeHysteresis::Deg14,
+            5 => PortraitLandscapeHysteresis::Deg17,
+            6 => PortraitLandscapeHysteresis::Deg21,
+            _ => PortraitLandscapeHysteresis::Deg24,
+        };
+        Some(PortraitLandscapeAngles {
+            back_front_trip,
+            z_lockout,
+            threshold,
+            hysteresis,
+        })
+    }
+
+    /// Set the portrait/landscape detection angles
+    ///
+    /// Only applied on devices with programmable orientation detection.
+    pub fn set_portrait_landscape_angles(&mut self, angles: PortraitLandscapeAngles) {
+        self.pl_bf_zcomp = get_bf_zcomp_reg(angles.back_front_trip, angles.z_lockout);
+        self.pl_ths = get_pl_ths_reg(angles.threshold, angles.hysteresis);
+    }
+
+    /// FIFO mode
+    pub fn fifo_mode(&self) -> FifoMode {
+        match self.f_setup >> 6 {
+            0 => FifoMode::Disabled,
+            1 => FifoMode::Circular,
+            2 => FifoMode::StopOnOverflow,
+            _ => FifoMode::Trigger,
+        }
+    }
+
+    /// Set the FIFO mode
+    ///
+    /// Only applied on devices with a FIFO.
+    pub fn set_fifo_mode(&mut self, mode: FifoMode) {
+        self.f_setup = get_fifo_mode_config(cfg(self.f_setup), mode).bits;
+    }
+
+    /// Offset correction of the X, Y and Z axes
+    pub fn offset_correction(&self) -> (i8, i8, i8) {
+        (self.offset_x, self.offset_y, self.offset_z)
+    }
+
+    /// Set the offset correction of the X, Y and Z axes
+    pub fn set_offset_correction(&mut self, x: i8, y: i8, z: i8) {
+        self.offset_x = x;
+        self.offset_y = y;
+        self.offset_z = z;
+    }
+
+    /// Encode the configuration into a fixed-size binary form.
+    ///
+    /// The encoding consists of a format version byte, the register values
+    /// in the order of the fields and a checksum byte.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_SIZE] {
+        let registers = self.to_registers();
+        let mut data = [0; Self::ENCODED_SIZE];
+        data[0] = ENCODING_VERSION;
+        for (byte, register) in data[1..].iter_mut().zip(CONFIG_REGISTERS) {
+            *byte = registers[usize::from(register)];
+        }
+        data[Self::ENCODED_SIZE - 1] = checksum(&data[..Self::ENCODED_SIZE - 1]);
+        data
+    }
+
+    /// Decode a configuration encoded with [`to_bytes()`](#method.to_bytes).
+    ///
+    /// Returns `None` if the format version or the checksum do not match.
+    pub fn from_bytes(data: &[u8; Self::ENCODED_SIZE]) -> Option<Self> {
+        let (payload, sum) = data.split_at(Self::ENCODED_SIZE - 1);
+        if payload[0] != ENCODING_VERSION || checksum(payload) != sum[0] {
+            return None;
+        }
+        let mut registers = [0; Register::OFF_Z as usize + 1];
+        for (byte, register) in payload[1..].iter().zip(CONFIG_REGISTERS) {
+            registers[usize::from(register)] = *byte;
+        }
+        Some(Self::from_registers(&registers))
+    }
+
+    /// Assemble the configuration from the register contents
+    ///
+    /// The ACTIVE, RST and ST bits are not part of the configuration.
+    pub(crate) fn from_registers(r: &Registers) -> Self {
+        let reg = |register: u8| r[usize::from(register)];
+        DeviceConfig {
+            f_setup: reg(Register::F_SETUP),
+            trig_cfg: reg(Register::TRIG_CFG),
+            xyz_data_cfg: reg(Register::XYZ_DATA_CFG),
+            hp_filter_cutoff: reg(Register::HP_FILTER_CUTOFF),
+            pl_cfg: reg(Register::PL_CFG),
+            pl_count: reg(Register::PL_COUNT),
+            pl_bf_zcomp: reg(Register::PL_BF_ZCOMP),
+            pl_ths: reg(Register::P_L_THS_REG),
+            ff_mt_cfg: reg(Register::FF_MT_CFG),
+            ff_mt_ths: reg(Register::FF_MT_THS),
+            ff_mt_count: reg(Register::FF_MT_COUNT),
+            transient_cfg: reg(Register::TRANSIENT_CFG),
+            transient_ths: reg(Register::TRANSIENT_THS),
+            transient_count: reg(Register::TRANSIENT_COUNT),
+            pulse_cfg: reg(Register::PULSE_CFG),
+            pulse_thsx: reg(Register::PULSE_THSX),
+            pulse_thsy: reg(Register::PULSE_THSY),
+            pulse_thsz: reg(Register::PULSE_THSZ),
+            pulse_tmlt: reg(Register::PULSE_TMLT),
+            pulse_ltcy: reg(Register::PULSE_LTCY),
+            pulse_wind: reg(Register::PULSE_WIND),
+            aslp_count: reg(Register::ASLP_COUNT),
+            ctrl_reg1: reg(Register::CTRL_REG1) & !BitFlags::ACTIVE,
+            ctrl_reg2: reg(Register::CTRL_REG2) & !(BitFlags::RST | BitFlags::ST),
+            ctrl_reg3: reg(Register::CTRL_REG3),
+            ctrl_reg4: reg(Register::CTRL_REG4),
+            ctrl_reg5: reg(Register::CTRL_REG5),
+            offset_x: reg(Register::OFF_X) as i8,
+            offset_y: reg(Register::OFF_Y) as i8,
+            offset_z: reg(Register::OFF_Z) as i8,
+        }
+    }
+
+    /// Register contents of the configuration
+    ///
+    /// The ACTIVE, RST and ST bits are cleared.
+    pub(crate) fn to_registers(self) -> Registers {
+        let mut r = [0; Register::OFF_Z as usize + 1];
+        let values = [
+            self.f_setup,
+            self.trig_cfg,
+            self.xyz_data_cfg,
+            self.hp_filter_cutoff,
+            self.pl_cfg,
+            self.pl_count,
+            self.pl_bf_zcomp,
+            self.pl_ths,
+            self.ff_mt_cfg,
+            self.ff_mt_ths,
+            self.ff_mt_count,
+            self.transient_cfg,
+            self.transient_ths,
+            self.transient_count,
+            self.pulse_cfg,
+            self.pulse_thsx,
+            self.pulse_thsy,
+            self.pulse_thsz,
+            self.pulse_tmlt,
+            self.pulse_ltcy,
+            self.pulse_wind,
+            self.aslp_count,
+            self.ctrl_reg1 & !BitFlags::ACTIVE,
+            self.ctrl_reg2 & !(BitFlags::RST | BitFlags::ST),
+            self.ctrl_reg3,
+            self.ctrl_reg4,
+            self.ctrl_reg5,
+            self.offset_x as u8,
+            self.offset_y as u8,
+            self.offset_z as u8,
+        ];
+        for (register, value) in CONFIG_REGISTERS.into_iter().zip(values) {
+            r[usize::from(register)] = value;
+        }
+        r
+    }
+}
+
+/// Blocks of configuration registers present in the device as (first register, count)
+///
+/// The source registers in between are skipped because reading them clears
+/// the latched events. CTRL_REG1 comes last so that the other registers are
+/// written while the device is in standby mode.
+pub(crate) fn get_config_blocks(ic: DetectedIc) -> impl Iterator<Item = (u8, usize)> {
+    let fifo = usize::from(ic.has_fifo());
+    let hpf = usize::from(ic.has_high_pass_filter());
+    let pl_len = if ic.has_programmable_orientation() {
+        4
+    } else {
+        2
+    };
+    [
+        (Register::F_SETUP, 2 * fifo),
+        (Register::XYZ_DATA_CFG, 1 + hpf),
+        (Register::PL_CFG, pl_len),
+        (Register::FF_MT_CFG, 1),
+        (Register::FF_MT_THS, 2),
+        (Register::TRANSIENT_CFG, hpf),
+        (Register::TRANSIENT_THS, 2 * hpf),
+        (Register::PULSE_CFG, hpf),
+        (Register::PULSE_THSX, 6 * hpf),
+        (Register::ASLP_COUNT, 1),
+        (Register::CTRL_REG2, 7),
+        (Register::CTRL_REG1, 1),
+    ]
+    .into_iter()
+    .filter(|(_, len)| *len != 0)
+}
+
+impl<I2C, IC: Sealed> Mma8x5x<I2C, IC, mode::Standby> {
+    /// Update the driver state after applying a configuration
+    pub(crate) fn set_config_state(&mut self, config: &DeviceConfig) {
+        let registers = config.to_registers();
+        let reg = |register: u8| Config {
+            bits: registers[usize::from(register)],
+        };
+        if IC::MODEL.has_fifo() {
+            self.f_setup = reg(Register::F_SETUP);
+        }
+        if IC::MODEL.has_high_pass_filter() {
+            self.hp_filter_cutoff = reg(Register::HP_FILTER_CUTOFF);
+        }
+        self.xyz_data_cfg = reg(Register::XYZ_DATA_CFG);
+        self.pl_cfg = reg(Register::PL_CFG);
+        self.ctrl_reg1 = reg(Register::CTRL_REG1);
+        self.ctrl_reg2 = reg(Register::CTRL_REG2);
+        self.ctrl_reg3 = reg(Register::CTRL_REG3);
+        self.ctrl_reg4 = reg(Register::CTRL_REG4);
+        self.ctrl_reg5 = reg(Register::CTRL_REG5);
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the current device configuration.
+    pub fn read_config(&mut self) -> Result<DeviceConfig, Error<E>> {
+        let mut registers = DeviceConfig::default().to_registers();
+        for (register, len) in get_config_blocks(IC::MODEL) {
+            let start = usize::from(register);
+            self.i2c
+                .write_read(
+                    self.address,
+                    &[register],
+                    &mut registers[start..start + len],
+                )
+                .map_err(Error::I2C)?;
+        }
+        Ok(DeviceConfig::from_registers(&registers))
+    }
+
+    /// Apply a device configuration.
+    ///
+    /// The device stays in standby mode. Only the registers present in
+    /// the device are written.
+    pub fn apply_config(&mut self, config: &DeviceConfig) -> Result<(), Error<E>> {
+        let registers = config.to_registers();
+        for (register, len) in get_config_blocks(IC::MODEL) {
+            let start = usize::from(register);
+            let mut payload = [0; 8];
+            payload[0] = register;
+            payload[1..=len].copy_from_slice(&registers[start..start + len]);
+            self.i2c
+                .write(self.address, &payload[..=len])
+                .map_err(Error::I2C)?;
+        }
+        self.set_config_state(config);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_reset_configuration() {
+        let config = DeviceConfig::default();
+        assert_eq!(0x80, config.pl_cfg);
+        assert_eq!(0x44, config.pl_bf_zcomp);
+        assert_eq!(0x84, config.pl_ths);
+    }
+
+    #[test]
+    fn can_encode_and_decode() {
+        let config = DeviceConfig {
+            f_setup: 0x54,
+            xyz_data_cfg: 0x12,
+            hp_filter_cutoff: 0x13,
+            pulse_wind: 0x20,
+            ctrl_reg1: 0x1A,
+            ctrl_reg4: 0x80,
+            offset_x: -3,
+            offset_z: 127,
+            ..DeviceConfig::default()
+        };
+        let data = config.to_bytes();
+        assert_eq!(1, data[0]);
+        assert_eq!(0xFD, data[28]);
+        assert_eq!(Some(config), DeviceConfig::from_bytes(&data));
+    }
+
+    #[test]
+    fn rejects_corrupted_encoding() {
+        let mut data = DeviceConfig::default().to_bytes();
+        data[3] ^= 1;
+        assert_eq!(None, DeviceConfig::from_bytes(&data));
+    }
+
+    #[test]
+    fn rejects_unknown_encoding_version() {
+        let mut data = DeviceConfig::default().to_bytes();
+        data[0] = 2;
+        data[31] = data[31].wrapping_sub(1);
+        assert_eq!(None, DeviceConfig::from_bytes(&data));
+    }
+
+    #[test]
+    fn excludes_active_reset_and_self_test_bits() {
+        let mut registers = [0; Register::OFF_Z as usize + 1];
+        registers[usize::from(Register::CTRL_REG1)] = 0x1B;
+        registers[usize::from(Register::CTRL_REG2)] = 0xC2;
+        let config = DeviceConfig::from_registers(&registers);
+        assert_eq!(0x1A, config.ctrl_reg1);
+        assert_eq!(0x02, config.ctrl_reg2);
+        let config = DeviceConfig {
+            ctrl_reg2: 0xC2,
+            ..DeviceConfig::default()
+        };
+        assert_eq!(
+            0x02,
+            config.to_registers()[usize::from(Register::CTRL_REG2)]
+        );
+    }
+
+    #[test]
+    fn default_accessors_match_reset_configuration() {
+        let config = DeviceConfig::default();
+        assert_eq!(GScale::G2, config.scale());
+        assert_eq!(OutputDataRate::Hz800, config.data_rate());
+        assert_eq!(ReadMode::Normal, config.read_mode());
+        assert_eq!(PowerMode::Normal, config.wake_power_mode());
+        assert_eq!(PowerMode::Normal, config.sleep_power_mode());
+        assert!(!config.auto_sleep_enabled());
+        assert_eq!(AutoSleepDataRate::Hz50, config.auto_sleep_data_rate());
+        assert_eq!(EnabledInterrupts::default(), config.enabled_interrupts());
+        assert_eq!(InterruptPinRoutes::default(), config.interrupt_pin_routes());
+        assert_eq!(WakeInterrupts::default(), config.wake_interrupts());
+        assert_eq!(
+            InterruptPinPolarity::ActiveLow,
+            config.interrupt_pin_polarity()
+        );
+        assert!(!config.portrait_landscape_enabled());
+        assert_eq!(
+            Some(PortraitLandscapeAngles::default()),
+            config.portrait_landscape_angles()
+        );
+        assert_eq!(FifoMode::Disabled, config.fifo_mode());
+        assert_eq!((0, 0, 0), config.offset_correction());
+    }
+
+    #[test]
+    fn can_set_typed_settings() {
+        let mut config = DeviceConfig::default();
+        let enabled = EnabledInterrupts {
+            pulse: true,
+            data_ready: true,
+            ..EnabledInterrupts::default()
+        };
+        let routes = InterruptPinRoutes {
+            fifo: InterruptSourcePinRoute::Int1,
+            ..InterruptPinRoutes::default()
+        };
+        let wake = WakeInterrupts {
+            transient: true,
+            ..WakeInterrupts::default()
+        };
+        let angles = PortraitLandscapeAngles {
+            back_front_trip: BackFrontTripAngle::Deg65,
+            z_lockout: ZLockoutAngle::Deg42,
+            threshold: PortraitLandscapeThreshold::Deg30,
+            hysteresis: PortraitLandscapeHysteresis::Deg0,
+        };
+        config.set_scale(GScale::G8);
+        config.set_data_rate(OutputDataRate::Hz12_5);
+        config.set_read_mode(ReadMode::Fast);
+        config.set_wake_power_mode(PowerMode::LowPower);
+        config.set_sleep_power_mode(PowerMode::HighResolution);
+        config.set_auto_sleep_enabled(true);
+        config.set_auto_sleep_data_rate(AutoSleepDataRate::Hz6_25);
+        config.set_auto_sleep_count(5);
+        config.set_enabled_interrupts(enabled);
+        config.set_interrupt_pin_routes(routes);
+        config.set_wake_interrupts(wake);
+        config.set_interrupt_pin_polarity(InterruptPinPolarity::ActiveHigh);
+        config.set_portrait_landscape_enabled(true);
+        config.set_portrait_landscape_angles(angles);
+        config.set_fifo_mode(FifoMode::Circular);
+        config.set_offset_correction(-1, 2, -3);
+
+        assert_eq!(0x02, config.xyz_data_cfg);
+        assert_eq!(0xAA, config.ctrl_reg1);
+        assert_eq!(0x17, config.ctrl_reg2);
+        assert_eq!(0x42, config.ctrl_reg3);
+        assert_eq!(0x09, config.ctrl_reg4);
+        assert_eq!(0x40, config.ctrl_reg5);
+        assert_eq!(0xC0, config.pl_cfg);
+        assert_eq!(0x40, config.f_setup);
+        assert_eq!(GScale::G8, config.scale());
+        assert_eq!(OutputDataRate::Hz12_5, config.data_rate());
+        assert_eq!(ReadMode::Fast, config.read_mode());
+        assert_eq!(PowerMode::LowPower, config.wake_power_mode());
+        assert_eq!(PowerMode::HighResolution, config.sleep_power_mode());
+        assert!(config.auto_sleep_enabled());
+        assert_eq!(AutoSleepDataRate::Hz6_25, config.auto_sleep_data_rate());
+        assert_eq!(5, config.auto_sleep_count());
+        assert_eq!(enabled, config.enabled_interrupts());
+        assert_eq!(routes, config.interrupt_pin_routes());
+        assert_eq!(wake, config.wake_interrupts());
+        assert_eq!(
+            InterruptPinPolarity::ActiveHigh,
+            config.interrupt_pin_polarity()
+        );
+        assert!(config.portrait_landscape_enabled());
+        assert_eq!(Some(angles), config.portrait_landscape_angles());
+        assert_eq!(FifoMode::Circular, config.fifo_mode());
+        assert_eq!((-1, 2, -3), config.offset_correction());
+    }
+
+    #[test]
+    fn unknown_portrait_landscape_threshold_has_no_angles() {
+        let config = DeviceConfig {
+            pl_ths: 0x08 << 3,
+            ..DeviceConfig::default()
+        };
+        assert_eq!(None, config.portrait_landscape_angles());
+    }
+}
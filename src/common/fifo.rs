@@ -0,0 +1,256 @@
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    common::read_status::get_system_mode,
+    conversion::raw_from_bytes,
+    ic, mode,
+    register_access::{BitFlags, Register},
+    Config, Error, FifoMode, FifoStatus, FifoTriggerSources, Mma8x5x, SystemStatus,
+    UnscaledMeasurement,
+};
+
+pub(crate) const FIFO_SIZE: usize = 32;
+
+/// FIFO configuration
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasFifo,
+{
+    /// Set FIFO mode
+    pub fn set_fifo_mode(&mut self, mode: FifoMode) -> Result<(), Error<E>> {
+        let config = get_fifo_mode_config(self.f_setup, mode);
+        self.write_reg(Register::F_SETUP, config.bits)?;
+        self.f_setup = config;
+        Ok(())
+    }
+
+    /// Set FIFO sample count watermark [0-32]
+    ///
+    /// A value of 0 disables the watermark.
+    pub fn set_fifo_watermark(&mut self, watermark: u8) -> Result<(), Error<E>> {
+        let config =
+            get_fifo_watermark_config(self.f_setup, watermark).ok_or(Error::InvalidInputData)?;
+        self.write_reg(Register::F_SETUP, config.bits)?;
+        self.f_setup = config;
+        Ok(())
+    }
+
+    /// Set interrupt sources that trigger the FIFO in trigger mode
+    pub fn set_fifo_trigger_sources(
+        &mut self,
+        sources: FifoTriggerSources,
+    ) -> Result<(), Error<E>> {
+        self.write_reg(Register::TRIG_CFG, get_trigger_sources_reg(sources))
+    }
+
+    /// Enable the FIFO gate
+    ///
+    /// The FIFO input is blocked on wake/sleep transitions until the FIFO
+    /// has been read, instead of flushing it.
+    pub fn enable_fifo_gate(&mut self) -> Result<(), Error<E>> {
+        let config = self.ctrl_reg3.with_high(BitFlags::FGATE);
+        self.write_reg(Register::CTRL_REG3, config.bits)?;
+        self.ctrl_reg3 = config;
+        Ok(())
+    }
+
+    /// Disable the FIFO gate (default)
+    ///
+    /// The FIFO is flushed on wake/sleep transitions.
+    pub fn disable_fifo_gate(&mut self) -> Result<(), Error<E>> {
+        let config = self.ctrl_reg3.with_low(BitFlags::FGATE);
+        self.write_reg(Register::CTRL_REG3, config.bits)?;
+        self.ctrl_reg3 = config;
+        Ok(())
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5x<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasFifo,
+{
+    /// Read current FIFO status
+    ///
+    /// Note: This is only valid while the FIFO is enabled.
+    pub fn fifo_status(&mut self) -> Result<FifoStatus, Error<E>> {
+        let st = self.read_reg(Register::STATUS)?;
+        Ok(get_fifo_status(st))
+    }
+
+    /// Read current system status including the FIFO gate error
+    pub fn system_status(&mut self) -> Result<SystemStatus, Error<E>> {
+        let sysmod = self.read_reg(Register::SYSMOD)?;
+        Ok(get_system_status(sysmod))
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasFifo,
+{
+    /// Read the unscaled samples stored in the FIFO into the provided buffer.
+    ///
+    /// Reads as many samples as are available and fit in the buffer
+    /// in a single burst and returns the number of samples read.
+    pub fn read_fifo(&mut self, buffer: &mut [UnscaledMeasurement]) -> Result<usize, Error<E>> {
+        let status = self.fifo_status()?;
+        let count = usize::from(status.count).min(FIFO_SIZE).min(buffer.len());
+        if count == 0 {
+            return Ok(0);
+        }
+        let fast_read = self.ctrl_reg1.is_high(BitFlags::F_READ);
+        let sample_len = if fast_read { 3 } else { 6 };
+        let mut data = [0; FIFO_SIZE * 6];
+        let data = &mut data[..count * sample_len];
+        self.i2c
+            .write_read(self.address, &[Register::OUT_X_H], data)
+            .map_err(Error::I2C)?;
+        for (m, sample) in buffer.iter_mut().zip(data.chunks(sample_len)) {
+            let (x, y, z) = raw_from_bytes(sample, fast_read);
+            *m = IC::convert(x, y, z);
+        }
+        Ok(count)
+    }
+}
+
+pub(crate) fn get_fifo_mode_config(f_setup: Config, mode: FifoMode) -> Config {
+    let bits = f_setup.bits & !(BitFlags::F_MODE1 | BitFlags::F_MODE0);
+    let mask = match mode {
+        FifoMode::Disabled => 0,
+        FifoMode::Circular => BitFlags::F_MODE0,
+        FifoMode::StopOnOverflow => BitFlags::F_MODE1,
+        FifoMode::Trigger => BitFlags::F_MODE1 | BitFlags::F_MODE0,
+    };
+    Config { bits: bits | mask }
+}
+
+pub(crate) fn get_fifo_watermark_config(f_setup: Config, watermark: u8) -> Option<Config> {
+    if usize::from(watermark) > FIFO_SIZE {
+        return None;
+    }
+    Some(Config {
+        bits: (f_setup.bits & (BitFlags::F_MODE1 | BitFlags::F_MODE0)) | watermark,
+    })
+}
+
+pub(crate) fn get_fifo_status(f_status: u8) -> FifoStatus {
+    FifoStatus {
+        overflow: (f_status & BitFlags::F_OVF) != 0,
+        watermark: (f_status & BitFlags::F_WMRK_FLAG) != 0,
+        count: f_status & !(BitFlags::F_OVF | BitFlags::F_WMRK_FLAG),
+    }
+}
+
+pub(crate) fn get_system_status(sysmod: u8) -> SystemStatus {
+    SystemStatus {
+        mode: get_system_mode(sysmod),
+        fifo_gate_error: (sysmod & BitFlags::FGERR) != 0,
+        fifo_gate_time: (sysmod & !BitFlags::FGERR) >> 2,
+    }
+}
+
+pub(crate) fn get_trigger_sources_reg(sources: FifoTriggerSources) -> u8 {
+    (if sources.transient {
+        BitFlags::TRIG_TRANS
+    } else {
+        0
+    } | if sources.portrait_landscape {
+        BitFlags::TRIG_LNDPRT
+    } else {
+        0
+    } | if sources.pulse {
+        BitFlags::TRIG_PULSE
+    } else {
+        0
+    } | if sources.freefall_motion {
+        BitFlags::TRIG_FF_MT
+    } else {
+        0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SystemMode;
+
+    #[test]
+    fn fifo_status_default() {
+        assert_eq!(FifoStatus::default(), get_fifo_status(0));
+    }
+
+    #[test]
+    fn fifo_status_all() {
+        assert_eq!(
+            FifoStatus {
+                overflow: true,
+                watermark: true,
+                count: 32
+            },
+            get_fifo_status(BitFlags::F_OVF | BitFlags::F_WMRK_FLAG | 32)
+        );
+    }
+
+    #[test]
+    fn system_status_default() {
+        assert_eq!(
+            SystemStatus {
+                mode: SystemMode::Standby,
+                fifo_gate_error: false,
+                fifo_gate_time: 0
+            },
+            get_system_status(0)
+        );
+    }
+
+    #[test]
+    fn system_status_all() {
+        assert_eq!(
+            SystemStatus {
+                mode: SystemMode::Sleep,
+                fifo_gate_error: true,
+                fifo_gate_time: 31
+            },
+            get_system_status(0xFF)
+        );
+    }
+
+    #[test]
+    fn system_status_fifo_gate_time() {
+        assert_eq!(
+            SystemStatus {
+                mode: SystemMode::Wake,
+                fifo_gate_error: false,
+                fifo_gate_time: 5
+            },
+            get_system_status((5 << 2) | 1)
+        );
+    }
+
+    #[test]
+    fn trigger_sources_default() {
+        assert_eq!(0, get_trigger_sources_reg(FifoTriggerSources::default()));
+    }
+
+    macro_rules! trigger_source_test {
+        ($name:ident, $bit_flag:ident) => {
+            #[test]
+            fn $name() {
+                assert_eq!(
+                    BitFlags::$bit_flag,
+                    get_trigger_sources_reg(FifoTriggerSources {
+                        $name: true,
+                        ..FifoTriggerSources::default()
+                    })
+                );
+            }
+        };
+    }
+    trigger_source_test!(transient, TRIG_TRANS);
+    trigger_source_test!(portrait_landscape, TRIG_LNDPRT);
+    trigger_source_test!(pulse, TRIG_PULSE);
+    trigger_source_test!(freefall_motion, TRIG_FF_MT);
+}
@@ -0,0 +1,143 @@
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    conversion::{counter_time_step_ms, get_polarity, to_counts},
+    mode,
+    register_access::{BitFlags as BF, Register},
+    DebounceCounterMode, Error, FfMtSource, FreefallMotionConfig, FreefallMotionMode, Mma8x5x,
+};
+
+const THRESHOLD_RESOLUTION: f32 = 0.063;
+
+/// Freefall/motion detection configuration
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Set freefall/motion detection configuration
+    ///
+    /// The debounce time is converted into counts based on the currently
+    /// configured output data rate and wake power mode so these should
+    /// be set beforehand.
+    pub fn set_freefall_motion_config(
+        &mut self,
+        config: FreefallMotionConfig,
+    ) -> Result<(), Error<E>> {
+        let step = counter_time_step_ms(self.data_rate(), self.wake_power_mode());
+        let (cfg, payload) = get_ff_mt_regs(&config, step).ok_or(Error::InvalidInputData)?;
+        self.write_reg(Register::FF_MT_CFG, cfg)?;
+        self.i2c.write(self.address, &payload).map_err(Error::I2C)
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5x<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Read freefall/motion detection source
+    ///
+    /// Reading this clears the freefall/motion interrupt and the latched events.
+    pub fn freefall_motion_source(&mut self) -> Result<FfMtSource, Error<E>> {
+        let src = self.read_reg(Register::FF_MT_SRC)?;
+        Ok(get_ff_mt_source(src))
+    }
+}
+
+/// Return the FF_MT_CFG register value and the write payload for the
+/// FF_MT_THS and FF_MT_COUNT registers or `None` if any value is out of range.
+pub(crate) fn get_ff_mt_regs(config: &FreefallMotionConfig, step: f32) -> Option<(u8, [u8; 3])> {
+    let threshold = to_counts(config.threshold, THRESHOLD_RESOLUTION, 0x7F)?;
+    let count = to_counts(config.debounce_time_ms, step, 0xFF)?;
+    let ths = match config.debounce_counter_mode {
+        DebounceCounterMode::Decrement => threshold,
+        DebounceCounterMode::Clear => threshold | BF::FF_MT_DBCNTM,
+    };
+    Some((get_ff_mt_cfg_reg(config), [Register::FF_MT_THS, ths, count]))
+}
+
+fn get_ff_mt_cfg_reg(config: &FreefallMotionConfig) -> u8 {
+    (if config.latch { BF::FF_MT_ELE } else { 0 }
+        | match config.mode {
+            FreefallMotionMode::Freefall => 0,
+            FreefallMotionMode::Motion => BF::OAE,
+        }
+        | if config.z_axis { BF::ZEFE } else { 0 }
+        | if config.y_axis { BF::YEFE } else { 0 }
+        | if config.x_axis { BF::XEFE } else { 0 })
+}
+
+pub(crate) fn get_ff_mt_source(src: u8) -> FfMtSource {
+    FfMtSource {
+        event_active: (src & BF::FF_MT_EA) != 0,
+        x_event: (src & BF::XHE) != 0,
+        x_polarity: get_polarity(src, BF::XHP),
+        y_event: (src & BF::YHE) != 0,
+        y_polarity: get_polarity(src, BF::YHP),
+        z_event: (src & BF::ZHE) != 0,
+        z_polarity: get_polarity(src, BF::ZHP),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventPolarity;
+
+    #[test]
+    fn cfg_default() {
+        assert_eq!(0, get_ff_mt_cfg_reg(&FreefallMotionConfig::default()));
+    }
+
+    #[test]
+    fn cfg_all() {
+        assert_eq!(
+            BF::FF_MT_ELE | BF::OAE | BF::ZEFE | BF::YEFE | BF::XEFE,
+            get_ff_mt_cfg_reg(&FreefallMotionConfig {
+                mode: FreefallMotionMode::Motion,
+                x_axis: true,
+                y_axis: true,
+                z_axis: true,
+                latch: true,
+                ..FreefallMotionConfig::default()
+            })
+        );
+    }
+
+    #[test]
+    fn source_default() {
+        assert_eq!(FfMtSource::default(), get_ff_mt_source(0));
+    }
+
+    #[test]
+    fn source_all() {
+        assert_eq!(
+            FfMtSource {
+                event_active: true,
+                x_event: true,
+                x_polarity: EventPolarity::Negative,
+                y_event: true,
+                y_polarity: EventPolarity::Negative,
+                z_event: true,
+                z_polarity: EventPolarity::Negative,
+            },
+            get_ff_mt_source(0xBF)
+        );
+    }
+
+    #[test]
+    fn source_unaffected_by_others() {
+        assert_eq!(FfMtSource::default(), get_ff_mt_source(1 << 6));
+    }
+
+    #[test]
+    fn source_y_positive() {
+        assert_eq!(
+            FfMtSource {
+                event_active: true,
+                y_event: true,
+                ..FfMtSource::default()
+            },
+            get_ff_mt_source(BF::FF_MT_EA | BF::YHE)
+        );
+    }
+}
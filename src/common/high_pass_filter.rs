@@ -0,0 +1,116 @@
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    ic, mode,
+    register_access::{BitFlags, Register},
+    Config, Error, HighPassConfig, HighPassFilterCutoff, Mma8x5x,
+};
+
+/// High-pass filter configuration
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasHighPassFilter,
+{
+    /// Set high-pass filter configuration
+    ///
+    /// When the output is enabled, the acceleration data returned by
+    /// [`read()`](#method.read) and [`read_unscaled()`](#method.read_unscaled)
+    /// is high-pass filtered.
+    pub fn set_high_pass_filter(&mut self, config: HighPassConfig) -> Result<(), Error<E>> {
+        let (xyz_data_cfg, hp_filter_cutoff) =
+            get_high_pass_filter_configs(self.xyz_data_cfg, &config);
+        self.write_reg(Register::XYZ_DATA_CFG, xyz_data_cfg.bits)?;
+        self.xyz_data_cfg = xyz_data_cfg;
+        self.write_reg(Register::HP_FILTER_CUTOFF, hp_filter_cutoff.bits)?;
+        self.hp_filter_cutoff = hp_filter_cutoff;
+        Ok(())
+    }
+}
+
+/// Return the new XYZ_DATA_CFG and HP_FILTER_CUTOFF register configurations
+pub(crate) fn get_high_pass_filter_configs(
+    xyz_data_cfg: Config,
+    config: &HighPassConfig,
+) -> (Config, Config) {
+    let xyz_data_cfg = if config.output_enabled {
+        xyz_data_cfg.with_high(BitFlags::HPF_OUT)
+    } else {
+        xyz_data_cfg.with_low(BitFlags::HPF_OUT)
+    };
+    let hp_filter_cutoff = Config {
+        bits: get_hp_filter_cutoff_reg(config),
+    };
+    (xyz_data_cfg, hp_filter_cutoff)
+}
+
+fn get_hp_filter_cutoff_reg(config: &HighPassConfig) -> u8 {
+    let sel = match config.cutoff {
+        HighPassFilterCutoff::Highest => 0,
+        HighPassFilterCutoff::High => BitFlags::SEL0,
+        HighPassFilterCutoff::Low => BitFlags::SEL1,
+        HighPassFilterCutoff::Lowest => BitFlags::SEL1 | BitFlags::SEL0,
+    };
+    sel | if config.pulse_hpf_bypass {
+        BitFlags::PULSE_HPF_BYP
+    } else {
+        0
+    } | if config.pulse_lpf_enabled {
+        BitFlags::PULSE_LPF_EN
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default() {
+        assert_eq!(0, get_hp_filter_cutoff_reg(&HighPassConfig::default()));
+    }
+
+    #[test]
+    fn output_is_ignored() {
+        assert_eq!(
+            0,
+            get_hp_filter_cutoff_reg(&HighPassConfig {
+                output_enabled: true,
+                ..HighPassConfig::default()
+            })
+        );
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(
+            BitFlags::PULSE_HPF_BYP | BitFlags::PULSE_LPF_EN | BitFlags::SEL1 | BitFlags::SEL0,
+            get_hp_filter_cutoff_reg(&HighPassConfig {
+                output_enabled: true,
+                cutoff: HighPassFilterCutoff::Lowest,
+                pulse_hpf_bypass: true,
+                pulse_lpf_enabled: true,
+            })
+        );
+    }
+
+    macro_rules! cutoff_test {
+        ($name:ident, $variant:ident, $expected:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!(
+                    $expected,
+                    get_hp_filter_cutoff_reg(&HighPassConfig {
+                        cutoff: HighPassFilterCutoff::$variant,
+                        ..HighPassConfig::default()
+                    })
+                );
+            }
+        };
+    }
+    cutoff_test!(cutoff_highest, Highest, 0);
+    cutoff_test!(cutoff_high, High, BitFlags::SEL0);
+    cutoff_test!(cutoff_low, Low, BitFlags::SEL1);
+    cutoff_test!(cutoff_lowest, Lowest, BitFlags::SEL1 | BitFlags::SEL0);
+}
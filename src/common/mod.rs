@@ -1,10 +1,21 @@
 //! Common functions
 
+pub(crate) mod calibration;
 mod common_impl;
-mod config;
-mod config_auto_sleep;
-mod config_int;
-mod config_pl;
+pub(crate) mod config;
+pub(crate) mod config_auto_sleep;
+pub(crate) mod config_int;
+pub(crate) mod config_pl;
+pub(crate) mod device_config;
+pub(crate) mod fifo;
+pub(crate) mod freefall_motion;
+pub(crate) mod high_pass_filter;
+pub(crate) mod init;
 mod mode_change;
-mod read_measurement;
-mod read_status;
+pub(crate) mod pulse;
+pub(crate) mod read_measurement;
+pub(crate) mod read_status;
+pub(crate) mod sampling;
+pub(crate) mod self_test;
+mod sync;
+pub(crate) mod transient;
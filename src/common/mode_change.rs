@@ -3,9 +3,8 @@ use embedded_hal::i2c::{I2c, SevenBitAddress};
 use crate::{
     mode,
     register_access::{BitFlags, Register},
-    Config, Mma8x5x, ModeChangeError,
+    Mma8x5x, ModeChangeError,
 };
-use core::marker::PhantomData;
 
 impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
 where
@@ -18,17 +17,10 @@ where
         let config = self.ctrl_reg1.with_high(BitFlags::ACTIVE);
         match self.write_reg(Register::CTRL_REG1, config.bits) {
             Err(error) => Err(ModeChangeError { error, dev: self }),
-            Ok(_) => Ok(Mma8x5x {
-                i2c: self.i2c,
-                address: self.address,
-                ctrl_reg1: config,
-                ctrl_reg2: self.ctrl_reg2,
-                ctrl_reg3: self.ctrl_reg3,
-                pl_cfg: self.pl_cfg,
-                xyz_data_cfg: self.xyz_data_cfg,
-                _ic: PhantomData,
-                _mode: PhantomData,
-            }),
+            Ok(_) => {
+                self.ctrl_reg1 = config;
+                Ok(self.into_mode())
+            }
         }
     }
 }
@@ -44,17 +36,10 @@ where
         let config = self.ctrl_reg1.with_low(BitFlags::ACTIVE);
         match self.write_reg(Register::CTRL_REG1, config.bits) {
             Err(error) => Err(ModeChangeError { error, dev: self }),
-            Ok(_) => Ok(Mma8x5x {
-                i2c: self.i2c,
-                address: self.address,
-                ctrl_reg1: config,
-                ctrl_reg2: self.ctrl_reg2,
-                ctrl_reg3: self.ctrl_reg3,
-                pl_cfg: self.pl_cfg,
-                xyz_data_cfg: self.xyz_data_cfg,
-                _ic: PhantomData,
-                _mode: PhantomData,
-            }),
+            Ok(_) => {
+                self.ctrl_reg1 = config;
+                Ok(self.into_mode())
+            }
         }
     }
 
@@ -62,19 +47,7 @@ where
     pub fn reset(mut self) -> Result<Mma8x5x<I2C, IC, mode::Standby>, ModeChangeError<E, Self>> {
         match self.reset_internal() {
             Err(error) => Err(ModeChangeError { error, dev: self }),
-            Ok(_) => Ok(Mma8x5x {
-                i2c: self.i2c,
-                address: self.address,
-                ctrl_reg1: Config::default(),
-                ctrl_reg2: Config::default(),
-                ctrl_reg3: Config::default(),
-                pl_cfg: Config {
-                    bits: BitFlags::DBCNTM,
-                },
-                xyz_data_cfg: Config::default(),
-                _ic: PhantomData,
-                _mode: PhantomData,
-            }),
+            Ok(_) => Ok(self.into_mode()),
         }
     }
 }
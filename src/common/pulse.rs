@@ -0,0 +1,179 @@
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    conversion::{get_polarity, pulse_time_step_ms, to_counts},
+    ic, mode,
+    register_access::{BitFlags as BF, Register},
+    Error, Mma8x5x, PulseConfig, PulseSource,
+};
+
+const THRESHOLD_RESOLUTION: f32 = 0.063;
+
+/// Pulse (tap) detection configuration
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasPulse,
+{
+    /// Set pulse (tap) detection configuration
+    ///
+    /// The times are converted into counts based on the currently
+    /// configured output data rate, wake power mode and pulse low-pass
+    /// filter setting so these should be set beforehand.
+    pub fn set_pulse_config(&mut self, config: PulseConfig) -> Result<(), Error<E>> {
+        let step = pulse_time_step_ms(
+            self.data_rate(),
+            self.wake_power_mode(),
+            self.hp_filter_cutoff.is_high(BF::PULSE_LPF_EN),
+        );
+        let (cfg, payload) = get_pulse_regs(&config, step).ok_or(Error::InvalidInputData)?;
+        self.write_reg(Register::PULSE_CFG, cfg)?;
+        self.i2c.write(self.address, &payload).map_err(Error::I2C)
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5x<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasPulse,
+{
+    /// Read pulse (tap) detection source
+    ///
+    /// Reading this clears the pulse interrupt and the latched events.
+    pub fn pulse_source(&mut self) -> Result<PulseSource, Error<E>> {
+        let src = self.read_reg(Register::PULSE_SRC)?;
+        Ok(get_pulse_source(src))
+    }
+}
+
+/// Return the PULSE_CFG register value and the write payload for the
+/// PULSE_THSX to PULSE_WIND registers or `None` if any value is out of range.
+pub(crate) fn get_pulse_regs(config: &PulseConfig, step: f32) -> Option<(u8, [u8; 7])> {
+    let threshold = |g| to_counts(g, THRESHOLD_RESOLUTION, 0x7F);
+    let time = |ms, step| to_counts(ms, step, 0xFF);
+    let payload = [
+        Register::PULSE_THSX,
+        threshold(config.x_threshold)?,
+        threshold(config.y_threshold)?,
+        threshold(config.z_threshold)?,
+        time(config.time_limit_ms, step)?,
+        time(config.latency_ms, 2.0 * step)?,
+        time(config.window_ms, 2.0 * step)?,
+    ];
+    Some((get_pulse_cfg_reg(config), payload))
+}
+
+fn get_pulse_cfg_reg(config: &PulseConfig) -> u8 {
+    (if config.double_pulse_abort {
+        BF::DPA
+    } else {
+        0
+    } | if config.latch { BF::PULSE_ELE } else { 0 }
+        | if config.z_double { BF::ZDPEFE } else { 0 }
+        | if config.z_single { BF::ZSPEFE } else { 0 }
+        | if config.y_double { BF::YDPEFE } else { 0 }
+        | if config.y_single { BF::YSPEFE } else { 0 }
+        | if config.x_double { BF::XDPEFE } else { 0 }
+        | if config.x_single { BF::XSPEFE } else { 0 })
+}
+
+pub(crate) fn get_pulse_source(src: u8) -> PulseSource {
+    PulseSource {
+        event_active: (src & BF::PULSE_EA) != 0,
+        double_pulse: (src & BF::DPE) != 0,
+        x_event: (src & BF::AX_X) != 0,
+        x_polarity: get_polarity(src, BF::POL_X),
+        y_event: (src & BF::AX_Y) != 0,
+        y_polarity: get_polarity(src, BF::POL_Y),
+        z_event: (src & BF::AX_Z) != 0,
+        z_polarity: get_polarity(src, BF::POL_Z),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventPolarity;
+
+    #[test]
+    fn cfg_default() {
+        assert_eq!(0, get_pulse_cfg_reg(&PulseConfig::default()));
+    }
+
+    #[test]
+    fn cfg_all() {
+        assert_eq!(
+            0xFF,
+            get_pulse_cfg_reg(&PulseConfig {
+                x_single: true,
+                x_double: true,
+                y_single: true,
+                y_double: true,
+                z_single: true,
+                z_double: true,
+                latch: true,
+                double_pulse_abort: true,
+                ..PulseConfig::default()
+            })
+        );
+    }
+
+    macro_rules! cfg_test {
+        ($name:ident, $bit_flag:ident) => {
+            #[test]
+            fn $name() {
+                assert_eq!(
+                    BF::$bit_flag,
+                    get_pulse_cfg_reg(&PulseConfig {
+                        $name: true,
+                        ..PulseConfig::default()
+                    })
+                );
+            }
+        };
+    }
+    cfg_test!(x_single, XSPEFE);
+    cfg_test!(x_double, XDPEFE);
+    cfg_test!(y_single, YSPEFE);
+    cfg_test!(y_double, YDPEFE);
+    cfg_test!(z_single, ZSPEFE);
+    cfg_test!(z_double, ZDPEFE);
+    cfg_test!(latch, PULSE_ELE);
+    cfg_test!(double_pulse_abort, DPA);
+
+    #[test]
+    fn source_default() {
+        assert_eq!(PulseSource::default(), get_pulse_source(0));
+    }
+
+    #[test]
+    fn source_all() {
+        assert_eq!(
+            PulseSource {
+                event_active: true,
+                double_pulse: true,
+                x_event: true,
+                x_polarity: EventPolarity::Negative,
+                y_event: true,
+                y_polarity: EventPolarity::Negative,
+                z_event: true,
+                z_polarity: EventPolarity::Negative,
+            },
+            get_pulse_source(0xFF)
+        );
+    }
+
+    #[test]
+    fn source_double_tap_z_negative() {
+        assert_eq!(
+            PulseSource {
+                event_active: true,
+                double_pulse: true,
+                z_event: true,
+                z_polarity: EventPolarity::Negative,
+                ..PulseSource::default()
+            },
+            get_pulse_source(BF::PULSE_EA | BF::DPE | BF::AX_Z | BF::POL_Z)
+        );
+    }
+}
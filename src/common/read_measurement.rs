@@ -3,84 +3,252 @@
 use embedded_hal::i2c::{I2c, SevenBitAddress};
 
 use crate::{
-    conversion::{convert_10bit, convert_12bit, convert_14bit},
-    ic, mode,
+    common::read_status::get_data_status,
+    conversion::raw_from_bytes,
+    ic::private::Sealed,
+    mode,
     register_access::{BitFlags, Register},
-    Error, Measurement, Mma8x5x, UnscaledMeasurement,
+    DataStatus, Error, Measurement, MilliGMeasurement, Mma8x5x, UnscaledMeasurement,
 };
+#[cfg(feature = "fixed")]
+use {crate::FixedMeasurement, fixed::types::I16F16};
 
-impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Active>
-where
-    I2C: I2c<SevenBitAddress, Error = E>,
-{
-    pub(crate) fn scale_measurement(&self, unscaled: UnscaledMeasurement, max: f32) -> Measurement {
+impl<I2C, IC: Sealed> Mma8x5x<I2C, IC, mode::Active> {
+    /// Return the sensitivity in the configured G scale as the base-2 logarithm
+    /// of counts/g.
+    pub(crate) fn sensitivity_shift(&self) -> u8 {
         match self.xyz_data_cfg.bits & 0b11 {
-            0 => scale(unscaled, max / 2.0),
-            1 => scale(unscaled, max / 4.0),
-            _ => scale(unscaled, max / 8.0),
+            0 => IC::SENSITIVITY_SHIFT,
+            1 => IC::SENSITIVITY_SHIFT - 1,
+            _ => IC::SENSITIVITY_SHIFT - 2,
         }
     }
+}
 
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
     pub(crate) fn read_raw(&mut self) -> Result<(u16, u16, u16), Error<E>> {
-        if self.ctrl_reg1.is_high(BitFlags::F_READ) {
-            let mut data = [0; 3];
-            self.i2c
-                .write_read(self.address, &[Register::OUT_X_H], &mut data)
-                .map_err(Error::I2C)?;
-            Ok((
-                (u16::from(data[0]) << 8),
-                (u16::from(data[1]) << 8),
-                (u16::from(data[2]) << 8),
-            ))
+        let fast_read = self.ctrl_reg1.is_high(BitFlags::F_READ);
+        let mut data = [0; 6];
+        let data = if fast_read {
+            &mut data[..3]
         } else {
-            let mut data = [0; 6];
-            self.i2c
-                .write_read(self.address, &[Register::OUT_X_H], &mut data)
-                .map_err(Error::I2C)?;
-            Ok((
-                (u16::from(data[0]) << 8) | u16::from(data[1]),
-                (u16::from(data[2]) << 8) | u16::from(data[3]),
-                (u16::from(data[4]) << 8) | u16::from(data[5]),
-            ))
-        }
+            &mut data[..]
+        };
+        self.i2c
+            .write_read(self.address, &[Register::OUT_X_H], data)
+            .map_err(Error::I2C)?;
+        Ok(raw_from_bytes(data, fast_read))
     }
 }
 
-fn scale(unscaled: UnscaledMeasurement, max: f32) -> Measurement {
+/// Standard gravity in m/s²
+const STANDARD_GRAVITY: f32 = 9.80665;
+
+/// Scale to G given the sensitivity as the base-2 logarithm of counts/g
+pub(crate) fn scale(unscaled: UnscaledMeasurement, shift: u8) -> Measurement {
+    let counts_per_g = f32::from(1_u16 << shift);
     Measurement {
-        x: f32::from(unscaled.x) / max,
-        y: f32::from(unscaled.y) / max,
-        z: f32::from(unscaled.z) / max,
+        x: f32::from(unscaled.x) / counts_per_g,
+        y: f32::from(unscaled.y) / counts_per_g,
+        z: f32::from(unscaled.z) / counts_per_g,
     }
 }
 
-macro_rules! read_impl {
-    ($ic:ident, $converter:ident, $max:expr) => {
-        impl<E, I2C> Mma8x5x<I2C, ic::$ic, mode::Active>
-        where
-            I2C: I2c<SevenBitAddress, Error = E>,
-        {
-            /// Read unscaled acceleration sensor data.
-            ///
-            /// Note: The values are only valid when in active mode or during self-test
-            pub fn read_unscaled(&mut self) -> Result<UnscaledMeasurement, Error<E>> {
-                let m = self.read_raw()?;
-                Ok($converter(m.0, m.1, m.2))
-            }
-
-            /// Read acceleration sensor data scaled to G.
-            ///
-            /// Note: The values are only valid when in active mode or during self-test
-            pub fn read(&mut self) -> Result<Measurement, Error<E>> {
-                let unscaled = self.read_unscaled()?;
-                Ok(self.scale_measurement(unscaled, $max))
-            }
-        }
-    };
+/// Scale to m/s² given the sensitivity as the base-2 logarithm of counts/g
+pub(crate) fn scale_to_ms2(unscaled: UnscaledMeasurement, shift: u8) -> Measurement {
+    let m = scale(unscaled, shift);
+    Measurement {
+        x: m.x * STANDARD_GRAVITY,
+        y: m.y * STANDARD_GRAVITY,
+        z: m.z * STANDARD_GRAVITY,
+    }
+}
+
+/// Convert counts to milli-g rounding to the nearest value (half away from zero)
+fn counts_to_mg(counts: i16, shift: u8) -> i16 {
+    let mg = i32::from(counts) * 1000;
+    let half = 1 << (shift - 1);
+    let rounded = if mg < 0 { mg - half } else { mg + half };
+    (rounded / (1 << shift)) as i16
+}
+
+/// Scale to milli-g given the sensitivity as the base-2 logarithm of counts/g
+pub(crate) fn scale_to_mg(unscaled: UnscaledMeasurement, shift: u8) -> MilliGMeasurement {
+    MilliGMeasurement {
+        x: counts_to_mg(unscaled.x, shift),
+        y: counts_to_mg(unscaled.y, shift),
+        z: counts_to_mg(unscaled.z, shift),
+    }
+}
+
+/// Scale to G in fixed-point given the sensitivity as the base-2 logarithm of counts/g
+///
+/// The sensitivity is always a power of two so this is exact.
+#[cfg(feature = "fixed")]
+pub(crate) fn scale_to_fixed(unscaled: UnscaledMeasurement, shift: u8) -> FixedMeasurement {
+    let to_fixed = |counts: i16| I16F16::from_bits(i32::from(counts) << (16 - shift));
+    FixedMeasurement {
+        x: to_fixed(unscaled.x),
+        y: to_fixed(unscaled.y),
+        z: to_fixed(unscaled.z),
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read unscaled acceleration sensor data.
+    ///
+    /// Note: The values are only valid when in active mode or during self-test
+    pub fn read_unscaled(&mut self) -> Result<UnscaledMeasurement, Error<E>> {
+        let (x, y, z) = self.read_raw()?;
+        Ok(IC::convert(x, y, z))
+    }
+
+    /// Read the data status and the unscaled acceleration sensor data
+    /// in a single transaction.
+    ///
+    /// The status corresponds to the sample returned, so that overwritten
+    /// samples can be detected. If the FIFO is enabled, the status
+    /// register contains the FIFO status instead.
+    ///
+    /// Note: The values are only valid when in active mode or during self-test
+    pub fn read_with_status(&mut self) -> Result<(DataStatus, UnscaledMeasurement), Error<E>> {
+        let fast_read = self.ctrl_reg1.is_high(BitFlags::F_READ);
+        let mut data = [0; 7];
+        let data = if fast_read {
+            &mut data[..4]
+        } else {
+            &mut data[..]
+        };
+        self.i2c
+            .write_read(self.address, &[Register::STATUS], data)
+            .map_err(Error::I2C)?;
+        let (x, y, z) = raw_from_bytes(&data[1..], fast_read);
+        Ok((get_data_status(data[0]), IC::convert(x, y, z)))
+    }
+
+    /// Read acceleration sensor data scaled to G.
+    ///
+    /// Note: The values are only valid when in active mode or during self-test
+    pub fn read(&mut self) -> Result<Measurement, Error<E>> {
+        let unscaled = self.read_unscaled()?;
+        Ok(scale(unscaled, self.sensitivity_shift()))
+    }
+
+    /// Read acceleration sensor data in m/s².
+    ///
+    /// Note: The values are only valid when in active mode or during self-test
+    pub fn read_ms2(&mut self) -> Result<Measurement, Error<E>> {
+        let unscaled = self.read_unscaled()?;
+        Ok(scale_to_ms2(unscaled, self.sensitivity_shift()))
+    }
+
+    /// Read acceleration sensor data in milli-g.
+    ///
+    /// The values are computed with integer arithmetic and rounded to
+    /// the nearest milli-g.
+    ///
+    /// Note: The values are only valid when in active mode or during self-test
+    pub fn read_mg(&mut self) -> Result<MilliGMeasurement, Error<E>> {
+        let unscaled = self.read_unscaled()?;
+        Ok(scale_to_mg(unscaled, self.sensitivity_shift()))
+    }
+
+    /// Read acceleration sensor data scaled to G in fixed-point.
+    ///
+    /// The values are exact.
+    ///
+    /// Note: The values are only valid when in active mode or during self-test
+    #[cfg(feature = "fixed")]
+    pub fn read_fixed(&mut self) -> Result<FixedMeasurement, Error<E>> {
+        let unscaled = self.read_unscaled()?;
+        Ok(scale_to_fixed(unscaled, self.sensitivity_shift()))
+    }
 }
 
-read_impl!(Mma8451, convert_14bit, 4096.0);
-read_impl!(Mma8452, convert_12bit, 1024.0);
-read_impl!(Mma8453, convert_10bit, 256.0);
-read_impl!(Mma8652, convert_12bit, 1024.0);
-read_impl!(Mma8653, convert_10bit, 256.0);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_G_14BIT_2G: i16 = 4096;
+
+    #[test]
+    fn can_scale() {
+        let m = scale(
+            UnscaledMeasurement {
+                x: ONE_G_14BIT_2G,
+                y: -ONE_G_14BIT_2G / 2,
+                z: 0,
+            },
+            12,
+        );
+        assert_eq!((1.0, -0.5, 0.0), (m.x, m.y, m.z));
+    }
+
+    #[test]
+    fn can_scale_to_ms2() {
+        let m = scale_to_ms2(
+            UnscaledMeasurement {
+                x: 64,
+                y: -128,
+                z: 0,
+            },
+            6,
+        );
+        assert_eq!((9.80665, -19.6133, 0.0), (m.x, m.y, m.z));
+    }
+
+    #[test]
+    fn can_scale_to_mg() {
+        let m = scale_to_mg(
+            UnscaledMeasurement {
+                x: ONE_G_14BIT_2G,
+                y: -ONE_G_14BIT_2G,
+                z: 8191,
+            },
+            12,
+        );
+        assert_eq!(
+            MilliGMeasurement {
+                x: 1000,
+                y: -1000,
+                z: 2000
+            },
+            m
+        );
+    }
+
+    #[test]
+    fn rounds_mg_to_nearest() {
+        // 0.244 mg/count
+        assert_eq!(0, counts_to_mg(2, 12));
+        assert_eq!(1, counts_to_mg(3, 12));
+        assert_eq!(-1, counts_to_mg(-3, 12));
+        // 15.6 mg/count
+        assert_eq!(16, counts_to_mg(1, 6));
+        assert_eq!(-7984, counts_to_mg(-511, 6));
+        assert_eq!(-8000, counts_to_mg(-512, 6));
+    }
+
+    #[cfg(feature = "fixed")]
+    #[test]
+    fn can_scale_to_fixed() {
+        let m = scale_to_fixed(
+            UnscaledMeasurement {
+                x: 1,
+                y: -ONE_G_14BIT_2G,
+                z: -512,
+            },
+            12,
+        );
+        assert_eq!(I16F16::from_num(1.0 / 4096.0), m.x);
+        assert_eq!(I16F16::from_num(-1), m.y);
+        assert_eq!(I16F16::from_num(-0.125), m.z);
+    }
+}
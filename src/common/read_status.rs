@@ -14,26 +14,13 @@ where
     /// Read current system mode
     pub fn system_mode(&mut self) -> Result<SystemMode, Error<E>> {
         let sysmod = self.read_reg(Register::SYSMOD)?;
-        match sysmod & 0b11 {
-            0 => Ok(SystemMode::Standby),
-            1 => Ok(SystemMode::Wake),
-            _ => Ok(SystemMode::Sleep),
-        }
+        Ok(get_system_mode(sysmod))
     }
 
     /// Read current data status
     pub fn data_status(&mut self) -> Result<DataStatus, Error<E>> {
         let st = self.read_reg(Register::STATUS)?;
-        Ok(DataStatus {
-            xyz_overwrite: (st & BF::XYZOW) != 0,
-            z_overwrite: (st & BF::ZOW) != 0,
-            y_overwrite: (st & BF::YOW) != 0,
-            x_overwrite: (st & BF::XOW) != 0,
-            xyz_new_data: (st & BF::XYZDR) != 0,
-            z_new_data: (st & BF::ZDR) != 0,
-            y_new_data: (st & BF::YDR) != 0,
-            x_new_data: (st & BF::XDR) != 0,
-        })
+        Ok(get_data_status(st))
     }
 
     /// Read current portrait/landscape status
@@ -49,7 +36,28 @@ where
     }
 }
 
-fn get_pl_status(pl_status: u8) -> PortraitLandscapeStatus {
+pub(crate) fn get_system_mode(sysmod: u8) -> SystemMode {
+    match sysmod & 0b11 {
+        0 => SystemMode::Standby,
+        1 => SystemMode::Wake,
+        _ => SystemMode::Sleep,
+    }
+}
+
+pub(crate) fn get_data_status(st: u8) -> DataStatus {
+    DataStatus {
+        xyz_overwrite: (st & BF::XYZOW) != 0,
+        z_overwrite: (st & BF::ZOW) != 0,
+        y_overwrite: (st & BF::YOW) != 0,
+        x_overwrite: (st & BF::XOW) != 0,
+        xyz_new_data: (st & BF::XYZDR) != 0,
+        z_new_data: (st & BF::ZDR) != 0,
+        y_new_data: (st & BF::YDR) != 0,
+        x_new_data: (st & BF::XDR) != 0,
+    }
+}
+
+pub(crate) fn get_pl_status(pl_status: u8) -> PortraitLandscapeStatus {
     let pl = match pl_status & (BF::LAPO0 | BF::LAPO1) {
         0 => PortraitLandscapeOrientation::PortraitUp,
         BF::LAPO0 => PortraitLandscapeOrientation::PortraitDown,
@@ -69,7 +77,7 @@ fn get_pl_status(pl_status: u8) -> PortraitLandscapeStatus {
     }
 }
 
-fn get_interrupt_status(int_src: u8) -> InterruptStatus {
+pub(crate) fn get_interrupt_status(int_src: u8) -> InterruptStatus {
     InterruptStatus {
         auto_sleep: (int_src & BF::SRC_ASLP) != 0,
         fifo: (int_src & BF::SRC_FIFO) != 0,
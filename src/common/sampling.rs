@@ -0,0 +1,90 @@
+//! Averaged sampling in a fixed configuration, used by the self-test and
+//! the offset calibration
+
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use crate::{
+    common::config::get_data_rate_config,
+    conversion::raw_from_bytes,
+    ic::private::Sealed,
+    mode,
+    register_access::{BitFlags, Register},
+    Config, Error, Mma8x5x, OutputDataRate, UnscaledMeasurement,
+};
+
+/// Time to wait after entering active mode before taking samples
+pub(crate) const SAMPLING_SETTLE_MS: u32 = 20;
+/// Time between samples (output data rate of 100Hz)
+pub(crate) const SAMPLING_PERIOD_MS: u32 = 10;
+
+/// CTRL_REG1 configuration used while sampling (standby)
+pub(crate) fn get_sampling_ctrl_reg1() -> Config {
+    get_data_rate_config(Config::default(), OutputDataRate::Hz100)
+}
+
+pub(crate) fn add_to_sum(sum: &mut [i32; 3], m: UnscaledMeasurement) {
+    sum[0] += i32::from(m.x);
+    sum[1] += i32::from(m.y);
+    sum[2] += i32::from(m.z);
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Select +/-2g with the high-pass filter output and the FIFO disabled
+    /// and normal power mode.
+    ///
+    /// The shadow registers are not modified.
+    pub(crate) fn prepare_sampling(&mut self) -> Result<(), Error<E>> {
+        self.write_reg(Register::XYZ_DATA_CFG, 0)?;
+        self.write_reg(Register::CTRL_REG2, 0)?;
+        if IC::MODEL.has_fifo() {
+            self.write_reg(Register::F_SETUP, 0)?;
+        }
+        Ok(())
+    }
+
+    /// Go active, add up the samples and return to standby.
+    pub(crate) fn sample_output<D: DelayNs>(
+        &mut self,
+        samples: u8,
+        delay: &mut D,
+    ) -> Result<[i32; 3], Error<E>> {
+        let ctrl_reg1 = get_sampling_ctrl_reg1();
+        self.write_reg(
+            Register::CTRL_REG1,
+            ctrl_reg1.with_high(BitFlags::ACTIVE).bits,
+        )?;
+        delay.delay_ms(SAMPLING_SETTLE_MS);
+        let mut sum = [0; 3];
+        for _ in 0..samples {
+            delay.delay_ms(SAMPLING_PERIOD_MS);
+            let mut data = [0; 6];
+            self.i2c
+                .write_read(self.address, &[Register::OUT_X_H], &mut data)
+                .map_err(Error::I2C)?;
+            let (x, y, z) = raw_from_bytes(&data, false);
+            add_to_sum(&mut sum, IC::convert(x, y, z));
+        }
+        self.write_reg(Register::CTRL_REG1, ctrl_reg1.bits)?;
+        Ok(sum)
+    }
+
+    /// Write the configuration stored in the shadow registers back.
+    ///
+    /// CTRL_REG1 goes first so that the device is in standby mode.
+    pub(crate) fn restore_configuration(&mut self) -> Result<(), Error<E>> {
+        self.write_reg(Register::CTRL_REG1, self.ctrl_reg1.bits)?;
+        self.write_reg(Register::CTRL_REG2, self.ctrl_reg2.bits)?;
+        self.write_reg(Register::XYZ_DATA_CFG, self.xyz_data_cfg.bits)?;
+        if IC::MODEL.has_fifo() {
+            self.write_reg(Register::F_SETUP, self.f_setup.bits)?;
+        }
+        Ok(())
+    }
+}
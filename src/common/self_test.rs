@@ -0,0 +1,171 @@
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use crate::{
+    ic::private::Sealed,
+    mode,
+    register_access::{BitFlags, Register},
+    DetectedIc, Error, Mma8x5x, SelfTestAxis, SelfTestReport,
+};
+
+/// Number of samples averaged with the self-test disabled and enabled
+pub(crate) const SELF_TEST_SAMPLES: u8 = 8;
+
+/// Typical self-test output change in LSB of the X, Y and Z axes at +/-2g
+///
+/// From the table "Mechanical characteristics" of the MMA8451Q, MMA8452Q,
+/// MMA8453Q, MMA8652FC and MMA8653FC datasheets. The datasheets give no
+/// minimum or maximum, so the self-test limits are heuristic bounds that
+/// accept between half and twice the typical value.
+const fn get_typical_self_test_output_change(ic: DetectedIc) -> [i16; 3] {
+    match ic {
+        DetectedIc::Mma8451 => [181, 255, 1680],
+        DetectedIc::Mma8452 => [44, 61, 392],
+        DetectedIc::Mma8453 => [11, 17, 98],
+        DetectedIc::Mma8652 => [48, 52, 356],
+        DetectedIc::Mma8653 => [12, 13, 89],
+    }
+}
+
+/// Self-test output change limits (minimum, maximum) of the X, Y and Z axes at +/-2g
+pub(crate) const fn get_self_test_limits(ic: DetectedIc) -> [(i16, i16); 3] {
+    let [x, y, z] = get_typical_self_test_output_change(ic);
+    [(x / 2, x * 2), (y / 2, y * 2), (z / 2, z * 2)]
+}
+
+fn get_axis_result(off: i32, on: i32, samples: u8, (min, max): (i16, i16)) -> SelfTestAxis {
+    let delta = ((on - off) / i32::from(samples)) as i16;
+    SelfTestAxis {
+        delta,
+        min,
+        max,
+        passed: delta >= min && delta <= max,
+    }
+}
+
+/// Evaluate the sums of the samples taken with the self-test disabled and enabled
+pub(crate) fn get_self_test_report(
+    off: [i32; 3],
+    on: [i32; 3],
+    samples: u8,
+    limits: [(i16, i16); 3],
+) -> SelfTestReport {
+    let x = get_axis_result(off[0], on[0], samples, limits[0]);
+    let y = get_axis_result(off[1], on[1], samples, limits[1]);
+    let z = get_axis_result(off[2], on[2], samples, limits[2]);
+    SelfTestReport {
+        x,
+        y,
+        z,
+        passed: x.passed && y.passed && z.passed,
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Run the self-test and evaluate the result.
+    ///
+    /// Averages samples with the self-test disabled and enabled at
+    /// +/-2g and 100Hz and compares the output change of each axis
+    /// with the limits for this device. The datasheets only give
+    /// typical values, so the limits are heuristic bounds: an axis
+    /// passes if its output change is between half and twice the
+    /// typical value of this device.
+    ///
+    /// This takes about 200ms. The previous configuration is restored
+    /// afterwards, also if an error occurs.
+    pub fn run_self_test<D: DelayNs>(&mut self, delay: &mut D) -> Result<SelfTestReport, Error<E>> {
+        let result = self.self_test_sequence(delay);
+        let restored = self.restore_configuration();
+        let (off, on) = result?;
+        restored?;
+        Ok(get_self_test_report(
+            off,
+            on,
+            SELF_TEST_SAMPLES,
+            IC::SELF_TEST_LIMITS,
+        ))
+    }
+
+    fn self_test_sequence<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<([i32; 3], [i32; 3]), Error<E>> {
+        self.prepare_sampling()?;
+        let off = self.sample_output(SELF_TEST_SAMPLES, delay)?;
+        self.write_reg(Register::CTRL_REG2, BitFlags::ST)?;
+        let on = self.sample_output(SELF_TEST_SAMPLES, delay)?;
+        Ok((off, on))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_evaluate_passed_self_test() {
+        let report = get_self_test_report(
+            [0, 0, 8 * 1024],
+            [8 * 44, 8 * 61, 8 * (1024 + 392)],
+            8,
+            get_self_test_limits(DetectedIc::Mma8452),
+        );
+        assert_eq!(
+            SelfTestAxis {
+                delta: 392,
+                min: 196,
+                max: 784,
+                passed: true
+            },
+            report.z
+        );
+        assert_eq!(44, report.x.delta);
+        assert_eq!(61, report.y.delta);
+        assert!(report.passed);
+    }
+
+    #[test]
+    fn can_evaluate_failed_self_test() {
+        let report = get_self_test_report(
+            [0, 0, 8 * 1024],
+            [8 * 44, -8 * 61, 8 * 1024],
+            8,
+            get_self_test_limits(DetectedIc::Mma8452),
+        );
+        assert!(report.x.passed);
+        assert!(!report.y.passed);
+        assert!(!report.z.passed);
+        assert!(!report.passed);
+    }
+
+    #[test]
+    fn limits_are_half_and_twice_the_typical_value() {
+        assert_eq!(
+            [(90, 362), (127, 510), (840, 3360)],
+            get_self_test_limits(DetectedIc::Mma8451)
+        );
+        assert_eq!(
+            [(24, 96), (26, 104), (178, 712)],
+            get_self_test_limits(DetectedIc::Mma8652)
+        );
+    }
+
+    #[test]
+    fn rejects_too_large_output_change() {
+        let report = get_self_test_report(
+            [0; 3],
+            [8 * 23, 8 * 35, 8 * 197],
+            8,
+            get_self_test_limits(DetectedIc::Mma8453),
+        );
+        assert!(!report.x.passed);
+        assert!(!report.y.passed);
+        assert!(!report.z.passed);
+    }
+}
@@ -0,0 +1,109 @@
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    ic::private::Sealed,
+    mode,
+    register_access::{BitFlags, Register},
+    Config, Error, Mma8x5x, ModeChangeError,
+};
+
+impl<E, I2C, IC, MODE> Mma8x5x<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the configuration from the device and put it in active or standby mode.
+    fn sync_internal(&mut self, active: bool) -> Result<(), Error<E>> {
+        let mut ctrl = [0; 5];
+        self.i2c
+            .write_read(self.address, &[Register::CTRL_REG1], &mut ctrl)
+            .map_err(Error::I2C)?;
+        // HP_FILTER_CUTOFF follows XYZ_DATA_CFG
+        let mut data_cfg = [0; 2];
+        let len = if IC::MODEL.has_high_pass_filter() {
+            2
+        } else {
+            1
+        };
+        self.i2c
+            .write_read(
+                self.address,
+                &[Register::XYZ_DATA_CFG],
+                &mut data_cfg[..len],
+            )
+            .map_err(Error::I2C)?;
+        let pl_cfg = self.read_reg(Register::PL_CFG)?;
+        let f_setup = if IC::MODEL.has_fifo() {
+            self.read_reg(Register::F_SETUP)?
+        } else {
+            0
+        };
+        self.ctrl_reg1 = Config { bits: ctrl[0] };
+        self.ctrl_reg2 = Config {
+            bits: ctrl[1] & !BitFlags::RST,
+        };
+        self.ctrl_reg3 = Config { bits: ctrl[2] };
+        self.ctrl_reg4 = Config { bits: ctrl[3] };
+        self.ctrl_reg5 = Config { bits: ctrl[4] };
+        self.xyz_data_cfg = Config { bits: data_cfg[0] };
+        self.hp_filter_cutoff = Config { bits: data_cfg[1] };
+        self.pl_cfg = Config { bits: pl_cfg };
+        self.f_setup = Config { bits: f_setup };
+        if self.ctrl_reg1.is_high(BitFlags::ACTIVE) != active {
+            let config = if active {
+                self.ctrl_reg1.with_high(BitFlags::ACTIVE)
+            } else {
+                self.ctrl_reg1.with_low(BitFlags::ACTIVE)
+            };
+            self.write_reg(Register::CTRL_REG1, config.bits)?;
+            self.ctrl_reg1 = config;
+        }
+        Ok(())
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the configuration back from the device into the driver.
+    ///
+    /// The driver keeps a copy of some configuration registers.
+    /// These can get out of sync with the device, for example after
+    /// a microcontroller reset while the device stayed powered, or
+    /// if another bus master changes the configuration.
+    /// This reads `CTRL_REG1` to `CTRL_REG5`, `XYZ_DATA_CFG`, `PL_CFG`
+    /// and, where present, `HP_FILTER_CUTOFF` and `F_SETUP`.
+    ///
+    /// If the device is in active mode, it is put into standby mode.
+    /// To keep it active, use the `attach_*` methods to get a driver
+    /// in active mode instead.
+    pub fn sync_from_device(&mut self) -> Result<(), Error<E>> {
+        self.sync_internal(false)
+    }
+
+    /// Read the configuration from the device and make sure it is active.
+    pub(crate) fn attach(
+        mut self,
+    ) -> Result<Mma8x5x<I2C, IC, mode::Active>, ModeChangeError<E, Self>> {
+        if let Err(error) = self.sync_internal(true) {
+            return Err(ModeChangeError { error, dev: self });
+        }
+        Ok(self.into_mode())
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the configuration back from the device into the driver.
+    ///
+    /// If the device is in standby mode, it is put into active mode.
+    /// Otherwise this works as in standby mode.
+    pub fn sync_from_device(&mut self) -> Result<(), Error<E>> {
+        self.sync_internal(true)
+    }
+}
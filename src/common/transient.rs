@@ -0,0 +1,152 @@
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    conversion::{counter_time_step_ms, get_polarity, to_counts},
+    ic, mode,
+    register_access::{BitFlags as BF, Register},
+    DebounceCounterMode, Error, Mma8x5x, TransientConfig, TransientSource,
+};
+
+const THRESHOLD_RESOLUTION: f32 = 0.063;
+
+/// Transient detection configuration
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Standby>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasTransient,
+{
+    /// Set transient detection configuration
+    ///
+    /// The debounce time is converted into counts based on the currently
+    /// configured output data rate and wake power mode so these should
+    /// be set beforehand.
+    pub fn set_transient_config(&mut self, config: TransientConfig) -> Result<(), Error<E>> {
+        let step = counter_time_step_ms(self.data_rate(), self.wake_power_mode());
+        let (cfg, ths, count) = get_transient_regs(&config, step).ok_or(Error::InvalidInputData)?;
+        self.write_reg(Register::TRANSIENT_CFG, cfg)?;
+        self.write_reg(Register::TRANSIENT_THS, ths)?;
+        self.write_reg(Register::TRANSIENT_COUNT, count)
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5x<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasTransient,
+{
+    /// Read transient detection source
+    ///
+    /// Reading this clears the transient interrupt and the latched events.
+    pub fn transient_source(&mut self) -> Result<TransientSource, Error<E>> {
+        let src = self.read_reg(Register::TRANSIENT_SRC)?;
+        Ok(get_transient_source(src))
+    }
+}
+
+/// Return the TRANSIENT_CFG, TRANSIENT_THS and TRANSIENT_COUNT register values
+/// or `None` if the threshold or debounce time are out of range.
+pub(crate) fn get_transient_regs(config: &TransientConfig, step: f32) -> Option<(u8, u8, u8)> {
+    let threshold = to_counts(config.threshold, THRESHOLD_RESOLUTION, 0x7F)?;
+    let count = to_counts(config.debounce_time_ms, step, 0xFF)?;
+    let ths = match config.debounce_counter_mode {
+        DebounceCounterMode::Decrement => threshold,
+        DebounceCounterMode::Clear => threshold | BF::TRANS_DBCNTM,
+    };
+    Some((get_transient_cfg_reg(config), ths, count))
+}
+
+fn get_transient_cfg_reg(config: &TransientConfig) -> u8 {
+    (if config.latch { BF::TRANS_ELE } else { 0 }
+        | if config.z_axis { BF::ZTEFE } else { 0 }
+        | if config.y_axis { BF::YTEFE } else { 0 }
+        | if config.x_axis { BF::XTEFE } else { 0 }
+        | if config.bypass_high_pass_filter {
+            BF::HPF_BYP
+        } else {
+            0
+        })
+}
+
+pub(crate) fn get_transient_source(src: u8) -> TransientSource {
+    TransientSource {
+        event_active: (src & BF::TRANS_EA) != 0,
+        x_event: (src & BF::XTRANSE) != 0,
+        x_polarity: get_polarity(src, BF::X_TRANS_POL),
+        y_event: (src & BF::YTRANSE) != 0,
+        y_polarity: get_polarity(src, BF::Y_TRANS_POL),
+        z_event: (src & BF::ZTRANSE) != 0,
+        z_polarity: get_polarity(src, BF::Z_TRANS_POL),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventPolarity;
+
+    #[test]
+    fn cfg_default() {
+        assert_eq!(0, get_transient_cfg_reg(&TransientConfig::default()));
+    }
+
+    #[test]
+    fn cfg_all() {
+        assert_eq!(
+            BF::TRANS_ELE | BF::ZTEFE | BF::YTEFE | BF::XTEFE | BF::HPF_BYP,
+            get_transient_cfg_reg(&TransientConfig {
+                x_axis: true,
+                y_axis: true,
+                z_axis: true,
+                latch: true,
+                bypass_high_pass_filter: true,
+                ..TransientConfig::default()
+            })
+        );
+    }
+
+    #[test]
+    fn source_default() {
+        assert_eq!(TransientSource::default(), get_transient_source(0));
+    }
+
+    #[test]
+    fn source_all() {
+        assert_eq!(
+            TransientSource {
+                event_active: true,
+                x_event: true,
+                x_polarity: EventPolarity::Negative,
+                y_event: true,
+                y_polarity: EventPolarity::Negative,
+                z_event: true,
+                z_polarity: EventPolarity::Negative,
+            },
+            get_transient_source(0x7F)
+        );
+    }
+
+    #[test]
+    fn source_x_positive() {
+        assert_eq!(
+            TransientSource {
+                event_active: true,
+                x_event: true,
+                ..TransientSource::default()
+            },
+            get_transient_source(BF::TRANS_EA | BF::XTRANSE)
+        );
+    }
+
+    #[test]
+    fn source_z_negative() {
+        assert_eq!(
+            TransientSource {
+                event_active: true,
+                z_event: true,
+                z_polarity: EventPolarity::Negative,
+                ..TransientSource::default()
+            },
+            get_transient_source(BF::TRANS_EA | BF::ZTRANSE | BF::Z_TRANS_POL)
+        );
+    }
+}
@@ -1,4 +1,105 @@
-use crate::UnscaledMeasurement;
+use crate::{EventPolarity, OutputDataRate, PowerMode, UnscaledMeasurement};
+
+pub fn get_polarity(src: u8, flag: u8) -> EventPolarity {
+    if (src & flag) != 0 {
+        EventPolarity::Negative
+    } else {
+        EventPolarity::Positive
+    }
+}
+
+/// Time steps in milliseconds at 800, 400, 200, 100, 50, 12.5, 6.25 and 1.56 Hz
+/// in normal, low noise low power, high resolution and low power modes
+type TimeSteps = [[f32; 8]; 4];
+
+/// Time steps of the debounce counters
+///
+/// See the FF_MT_COUNT and TRANSIENT_COUNT relationship with ODR tables in the datasheet.
+const COUNTER_TIME_STEPS: TimeSteps = [
+    [1.25, 2.5, 5.0, 10.0, 20.0, 20.0, 20.0, 20.0],
+    [1.25, 2.5, 5.0, 10.0, 20.0, 80.0, 80.0, 80.0],
+    [1.25, 2.5, 2.5, 2.5, 2.5, 2.5, 2.5, 2.5],
+    [1.25, 2.5, 5.0, 10.0, 20.0, 80.0, 160.0, 640.0],
+];
+
+fn get_time_step(steps: &TimeSteps, rate: OutputDataRate, power_mode: PowerMode) -> f32 {
+    let mode = match power_mode {
+        PowerMode::Normal => 0,
+        PowerMode::LowNoiseLowPower => 1,
+        PowerMode::HighResolution => 2,
+        PowerMode::LowPower => 3,
+    };
+    let rate = match rate {
+        OutputDataRate::Hz800 => 0,
+        OutputDataRate::Hz400 => 1,
+        OutputDataRate::Hz200 => 2,
+        OutputDataRate::Hz100 => 3,
+        OutputDataRate::Hz50 => 4,
+        OutputDataRate::Hz12_5 => 5,
+        OutputDataRate::Hz6_25 => 6,
+        OutputDataRate::Hz1_56 => 7,
+    };
+    steps[mode][rate]
+}
+
+/// Time step of the freefall/motion and transient debounce counters in milliseconds
+pub fn counter_time_step_ms(rate: OutputDataRate, power_mode: PowerMode) -> f32 {
+    get_time_step(&COUNTER_TIME_STEPS, rate, power_mode)
+}
+
+/// Time steps of the pulse time limit counter with the pulse low-pass filter disabled
+///
+/// See the PULSE_TMLT time step tables in the datasheet. With the low-pass
+/// filter enabled, the time steps are those of the debounce counters.
+const PULSE_TIME_STEPS_LPF_DISABLED: TimeSteps = [
+    [0.625, 0.625, 1.25, 2.5, 5.0, 5.0, 5.0, 5.0],
+    [0.625, 0.625, 1.25, 2.5, 5.0, 20.0, 20.0, 20.0],
+    [0.625, 0.625, 0.625, 0.625, 0.625, 0.625, 0.625, 0.625],
+    [0.625, 1.25, 2.5, 5.0, 10.0, 40.0, 40.0, 40.0],
+];
+
+/// Time step of the pulse time limit counter in milliseconds
+///
+/// The latency and window counters have twice this time step.
+pub fn pulse_time_step_ms(rate: OutputDataRate, power_mode: PowerMode, lpf_enabled: bool) -> f32 {
+    let steps = if lpf_enabled {
+        &COUNTER_TIME_STEPS
+    } else {
+        &PULSE_TIME_STEPS_LPF_DISABLED
+    };
+    get_time_step(steps, rate, power_mode)
+}
+
+/// Convert a value into the closest register count for the given resolution.
+///
+/// Returns `None` if the value is negative or the count is greater than `max`.
+pub fn to_counts(value: f32, resolution: f32, max: u8) -> Option<u8> {
+    if value.is_nan() || value < 0.0 {
+        return None;
+    }
+    let counts = value / resolution + 0.5;
+    if counts >= f32::from(max) + 1.0 {
+        None
+    } else {
+        Some(counts as u8)
+    }
+}
+
+pub fn raw_from_bytes(data: &[u8], fast_read: bool) -> (u16, u16, u16) {
+    if fast_read {
+        (
+            (u16::from(data[0]) << 8),
+            (u16::from(data[1]) << 8),
+            (u16::from(data[2]) << 8),
+        )
+    } else {
+        (
+            (u16::from(data[0]) << 8) | u16::from(data[1]),
+            (u16::from(data[2]) << 8) | u16::from(data[3]),
+            (u16::from(data[4]) << 8) | u16::from(data[5]),
+        )
+    }
+}
 
 pub fn convert_10bit(x: u16, y: u16, z: u16) -> UnscaledMeasurement {
     UnscaledMeasurement {
@@ -28,6 +129,113 @@ pub fn convert_14bit(x: u16, y: u16, z: u16) -> UnscaledMeasurement {
 mod tests {
     use super::*;
 
+    #[test]
+    fn can_convert_to_counts() {
+        assert_eq!(Some(0), to_counts(0.0, 0.063, 127));
+        assert_eq!(Some(16), to_counts(1.0, 0.063, 127));
+        assert_eq!(Some(127), to_counts(8.0, 0.063, 127));
+        assert_eq!(Some(255), to_counts(318.75, 1.25, 255));
+    }
+
+    #[test]
+    fn cannot_convert_invalid_values_to_counts() {
+        assert_eq!(None, to_counts(-1.0, 0.063, 127));
+        assert_eq!(None, to_counts(f32::NAN, 0.063, 127));
+        assert_eq!(None, to_counts(8.1, 0.063, 127));
+        assert_eq!(None, to_counts(320.0, 1.25, 255));
+    }
+
+    const RATES: [OutputDataRate; 8] = [
+        OutputDataRate::Hz800,
+        OutputDataRate::Hz400,
+        OutputDataRate::Hz200,
+        OutputDataRate::Hz100,
+        OutputDataRate::Hz50,
+        OutputDataRate::Hz12_5,
+        OutputDataRate::Hz6_25,
+        OutputDataRate::Hz1_56,
+    ];
+
+    const LPF_ENABLED_TABLE: [(PowerMode, [f32; 8]); 4] = [
+        (
+            PowerMode::Normal,
+            [1.25, 2.5, 5.0, 10.0, 20.0, 20.0, 20.0, 20.0],
+        ),
+        (
+            PowerMode::LowNoiseLowPower,
+            [1.25, 2.5, 5.0, 10.0, 20.0, 80.0, 80.0, 80.0],
+        ),
+        (
+            PowerMode::HighResolution,
+            [1.25, 2.5, 2.5, 2.5, 2.5, 2.5, 2.5, 2.5],
+        ),
+        (
+            PowerMode::LowPower,
+            [1.25, 2.5, 5.0, 10.0, 20.0, 80.0, 160.0, 640.0],
+        ),
+    ];
+
+    fn check_time_steps(
+        table: &[(PowerMode, [f32; 8])],
+        time_step: impl Fn(OutputDataRate, PowerMode) -> f32,
+    ) {
+        for &(power_mode, steps) in table {
+            for (rate, step) in RATES.into_iter().zip(steps) {
+                assert_eq!(step, time_step(rate, power_mode), "{rate:?} {power_mode:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn can_get_counter_time_step() {
+        check_time_steps(&LPF_ENABLED_TABLE, counter_time_step_ms);
+    }
+
+    #[test]
+    fn can_get_pulse_time_step_with_lpf_enabled() {
+        check_time_steps(&LPF_ENABLED_TABLE, |rate, power_mode| {
+            pulse_time_step_ms(rate, power_mode, true)
+        });
+    }
+
+    #[test]
+    fn can_get_pulse_time_step_with_lpf_disabled() {
+        let table = [
+            (
+                PowerMode::Normal,
+                [0.625, 0.625, 1.25, 2.5, 5.0, 5.0, 5.0, 5.0],
+            ),
+            (
+                PowerMode::LowNoiseLowPower,
+                [0.625, 0.625, 1.25, 2.5, 5.0, 20.0, 20.0, 20.0],
+            ),
+            (PowerMode::HighResolution, [0.625; 8]),
+            (
+                PowerMode::LowPower,
+                [0.625, 1.25, 2.5, 5.0, 10.0, 40.0, 40.0, 40.0],
+            ),
+        ];
+        check_time_steps(&table, |rate, power_mode| {
+            pulse_time_step_ms(rate, power_mode, false)
+        });
+    }
+
+    #[test]
+    fn can_get_raw_from_bytes() {
+        assert_eq!(
+            (0x4140, 0x4280, 0x43C0),
+            raw_from_bytes(&[0x41, 0x40, 0x42, 0x80, 0x43, 0xC0], false)
+        );
+    }
+
+    #[test]
+    fn can_get_raw_from_fast_read_bytes() {
+        assert_eq!(
+            (0x4100, 0x4200, 0x4300),
+            raw_from_bytes(&[0x41, 0x42, 0x43], true)
+        );
+    }
+
     #[test]
     fn can_convert_positive_10bit() {
         assert_eq!(
@@ -0,0 +1,105 @@
+//! Automatic device detection
+
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    ic, mode, register_access::Register, DetectedIc, DetectionError, Mma8x5x, SlaveAddr,
+    MMA845X_BASE_ADDR,
+};
+
+/// Driver for a device found with [`detect()`](#method.detect)
+///
+/// Contains a driver in standby mode for the detected device model.
+#[derive(Debug)]
+pub enum DetectedDevice<I2C> {
+    /// MMA8451 device
+    Mma8451(Mma8x5x<I2C, ic::Mma8451, mode::Standby>),
+    /// MMA8452 device
+    Mma8452(Mma8x5x<I2C, ic::Mma8452, mode::Standby>),
+    /// MMA8453 device
+    Mma8453(Mma8x5x<I2C, ic::Mma8453, mode::Standby>),
+    /// MMA8652 device
+    Mma8652(Mma8x5x<I2C, ic::Mma8652, mode::Standby>),
+    /// MMA8653 device
+    Mma8653(Mma8x5x<I2C, ic::Mma8653, mode::Standby>),
+}
+
+impl<E, I2C> DetectedDevice<I2C>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Detect the device connected to the bus
+    ///
+    /// Reads the device ID (Who am I) at the addresses 0x1C and 0x1D and
+    /// creates a driver for the first supported device found.
+    /// The MMA8652 and MMA8653 are only detected at 0x1D, which is their
+    /// only address. Addresses that do not respond are skipped.
+    ///
+    /// If no supported device is found, the I²C bus is returned in the error.
+    pub fn detect(mut i2c: I2C) -> Result<Self, DetectionError<I2C>> {
+        let mut unknown_id = None;
+        for address in [SlaveAddr::Default, SlaveAddr::Alternative(true)] {
+            let mut id = [0];
+            if i2c
+                .write_read(
+                    address.addr(MMA845X_BASE_ADDR),
+                    &[Register::WHO_AM_I],
+                    &mut id,
+                )
+                .is_err()
+            {
+                continue;
+            }
+            let device = match (DetectedIc::from_device_id(id[0]), address) {
+                (Some(DetectedIc::Mma8451), _) => {
+                    DetectedDevice::Mma8451(Mma8x5x::new_mma8451(i2c, address))
+                }
+                (Some(DetectedIc::Mma8452), _) => {
+                    DetectedDevice::Mma8452(Mma8x5x::new_mma8452(i2c, address))
+                }
+                (Some(DetectedIc::Mma8453), _) => {
+                    DetectedDevice::Mma8453(Mma8x5x::new_mma8453(i2c, address))
+                }
+                (Some(DetectedIc::Mma8652), SlaveAddr::Alternative(true)) => {
+                    DetectedDevice::Mma8652(Mma8x5x::new_mma8652(i2c))
+                }
+                (Some(DetectedIc::Mma8653), SlaveAddr::Alternative(true)) => {
+                    DetectedDevice::Mma8653(Mma8x5x::new_mma8653(i2c))
+                }
+                _ => {
+                    unknown_id = Some(id[0]);
+                    continue;
+                }
+            };
+            return Ok(device);
+        }
+        Err(DetectionError {
+            i2c,
+            device_id: unknown_id,
+        })
+    }
+}
+
+impl<I2C> DetectedDevice<I2C> {
+    /// Detected device model
+    pub fn ic(&self) -> DetectedIc {
+        match self {
+            DetectedDevice::Mma8451(_) => DetectedIc::Mma8451,
+            DetectedDevice::Mma8452(_) => DetectedIc::Mma8452,
+            DetectedDevice::Mma8453(_) => DetectedIc::Mma8453,
+            DetectedDevice::Mma8652(_) => DetectedIc::Mma8652,
+            DetectedDevice::Mma8653(_) => DetectedIc::Mma8653,
+        }
+    }
+
+    /// Destroy driver instance, return I²C bus instance.
+    pub fn destroy(self) -> I2C {
+        match self {
+            DetectedDevice::Mma8451(dev) => dev.destroy(),
+            DetectedDevice::Mma8452(dev) => dev.destroy(),
+            DetectedDevice::Mma8453(dev) => dev.destroy(),
+            DetectedDevice::Mma8652(dev) => dev.destroy(),
+            DetectedDevice::Mma8653(dev) => dev.destroy(),
+        }
+    }
+}
@@ -0,0 +1,371 @@
+//! Decoding of the pending interrupt sources into events
+
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    ic::private::Sealed,
+    interrupt_handler::SOURCES,
+    mode,
+    register_access::{BitFlags, Register},
+    Axis, DecodedEvent, Error, Event, EventAxes, FfMtSource, Mma8x5x, PulseSource, TransientSource,
+};
+
+/// Maximum number of events decoded at once.
+///
+/// Every source produces one event except the FIFO, which can signal a
+/// watermark and an overflow, and the pulse detection, which can signal
+/// a pulse on each axis.
+const MAX_EVENTS: usize = 10;
+
+/// Iterator over the events decoded from the pending interrupt sources
+///
+/// See [`next_events()`](struct.Mma8x5x.html#method.next_events).
+#[derive(Debug, Clone)]
+pub struct Events {
+    events: [Option<DecodedEvent>; MAX_EVENTS],
+    len: usize,
+    index: usize,
+}
+
+impl Default for Events {
+    fn default() -> Self {
+        Events {
+            events: [None; MAX_EVENTS],
+            len: 0,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for Events {
+    type Item = DecodedEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        self.index += 1;
+        self.events[self.index - 1]
+    }
+}
+
+/// Error while reading the pending events
+///
+/// See [`next_events()`](struct.Mma8x5x.html#method.next_events).
+#[derive(Debug)]
+pub struct EventsError<E> {
+    /// I²C bus error
+    pub error: Error<E>,
+    /// Events decoded before the error. Their sources have been cleared.
+    pub events: Events,
+}
+
+impl<E> From<Error<E>> for EventsError<E> {
+    fn from(error: Error<E>) -> Self {
+        EventsError {
+            error,
+            events: Events::default(),
+        }
+    }
+}
+
+impl Events {
+    /// Decode an interrupt event into the events it contains.
+    ///
+    /// This is the decoding done by [`next_events()`](struct.Mma8x5x.html#method.next_events)
+    /// and can be used for the events returned by
+    /// [`InterruptHandler::service()`](struct.InterruptHandler.html#method.service).
+    /// `motion` selects whether a freefall/motion event is a motion event,
+    /// as configured with [`FreefallMotionMode`](enum.FreefallMotionMode.html).
+    pub fn from_event(event: Event, motion: bool) -> Self {
+        let mut events = Events::default();
+        events.decode(event, motion);
+        events
+    }
+
+    fn push(&mut self, event: DecodedEvent) {
+        self.events[self.len] = Some(event);
+        self.len += 1;
+    }
+
+    /// Decode the content of a source register into events.
+    pub(crate) fn decode(&mut self, event: Event, motion: bool) {
+        match event {
+            Event::AutoSleep(mode) => self.push(DecodedEvent::SleepWakeTransition(mode)),
+            Event::Fifo(status) => {
+                if status.watermark {
+                    self.push(DecodedEvent::FifoWatermark(status.count));
+                }
+                if status.overflow {
+                    self.push(DecodedEvent::FifoOverflow(status.count));
+                }
+            }
+            Event::Transient(src) => {
+                if src.event_active {
+                    self.push(DecodedEvent::Transient {
+                        axes: get_transient_axes(&src),
+                    });
+                }
+            }
+            Event::PortraitLandscape(status) => self.push(DecodedEvent::OrientationChanged(status)),
+            Event::Pulse(src) => self.decode_pulse(&src),
+            Event::FreefallMotion(src) => {
+                if src.event_active {
+                    self.push(if motion {
+                        DecodedEvent::Motion {
+                            axes: get_ff_mt_axes(&src),
+                        }
+                    } else {
+                        DecodedEvent::Freefall
+                    });
+                }
+            }
+            Event::DataReady(m) => self.push(DecodedEvent::DataReady(m)),
+        }
+    }
+
+    fn decode_pulse(&mut self, src: &PulseSource) {
+        if !src.event_active {
+            return;
+        }
+        let axes = [
+            (Axis::X, src.x_event, src.x_polarity),
+            (Axis::Y, src.y_event, src.y_polarity),
+            (Axis::Z, src.z_event, src.z_polarity),
+        ];
+        for (axis, _, polarity) in axes.into_iter().filter(|(_, event, _)| *event) {
+            self.push(DecodedEvent::Tap {
+                axis,
+                double: src.double_pulse,
+                polarity,
+            });
+        }
+    }
+}
+
+fn get_transient_axes(src: &TransientSource) -> EventAxes {
+    EventAxes {
+        x: src.x_event,
+        y: src.y_event,
+        z: src.z_event,
+    }
+}
+
+fn get_ff_mt_axes(src: &FfMtSource) -> EventAxes {
+    EventAxes {
+        x: src.x_event,
+        y: src.y_event,
+        z: src.z_event,
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the pending interrupt sources and return the decoded events.
+    ///
+    /// Reads the interrupt source and then the source register of each
+    /// pending interrupt exactly once, which clears it. The sources are read
+    /// in the order: auto-sleep, FIFO, transient, portrait/landscape, pulse,
+    /// freefall/motion and data ready.
+    ///
+    /// To tell freefall and motion events apart, the freefall/motion
+    /// configuration is read as well if such an event is pending.
+    ///
+    /// If a read fails, no further sources are read and the error contains
+    /// the events decoded until then.
+    pub fn next_events(&mut self) -> Result<Events, EventsError<E>> {
+        let int_src = self.read_reg(Register::INT_SOURCE)?;
+        let mut events = Events::default();
+        for source in SOURCES.into_iter().filter(|source| (int_src & source) != 0) {
+            match self.read_source_event(source) {
+                Ok((event, motion)) => events.decode(event, motion),
+                Err(error) => return Err(EventsError { error, events }),
+            }
+        }
+        Ok(events)
+    }
+
+    /// Read the event of a source and whether a freefall/motion event is a motion event.
+    fn read_source_event(&mut self, source: u8) -> Result<(Event, bool), Error<E>> {
+        let motion = source == BitFlags::SRC_FF_MT
+            && (self.read_reg(Register::FF_MT_CFG)? & BitFlags::OAE) != 0;
+        Ok((self.read_event(source)?, motion))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventPolarity, FifoStatus, SystemMode, UnscaledMeasurement};
+
+    fn decode(event: Event, motion: bool) -> Events {
+        Events::from_event(event, motion)
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(None, Events::default().next());
+    }
+
+    #[test]
+    fn sleep_wake_transition() {
+        let mut events = decode(Event::AutoSleep(SystemMode::Wake), false);
+        assert_eq!(
+            Some(DecodedEvent::SleepWakeTransition(SystemMode::Wake)),
+            events.next()
+        );
+        assert_eq!(None, events.next());
+    }
+
+    #[test]
+    fn fifo_watermark_and_overflow() {
+        let mut events = decode(
+            Event::Fifo(FifoStatus {
+                overflow: true,
+                watermark: true,
+                count: 32,
+            }),
+            false,
+        );
+        assert_eq!(Some(DecodedEvent::FifoWatermark(32)), events.next());
+        assert_eq!(Some(DecodedEvent::FifoOverflow(32)), events.next());
+        assert_eq!(None, events.next());
+    }
+
+    #[test]
+    fn inactive_transient() {
+        let mut events = decode(Event::Transient(TransientSource::default()), false);
+        assert_eq!(None, events.next());
+    }
+
+    #[test]
+    fn transient() {
+        let mut events = decode(
+            Event::Transient(TransientSource {
+                event_active: true,
+                y_event: true,
+                ..TransientSource::default()
+            }),
+            false,
+        );
+        assert_eq!(
+            Some(DecodedEvent::Transient {
+                axes: EventAxes {
+                    y: true,
+                    ..EventAxes::default()
+                }
+            }),
+            events.next()
+        );
+    }
+
+    #[test]
+    fn tap_on_each_axis() {
+        let mut events = decode(
+            Event::Pulse(PulseSource {
+                event_active: true,
+                double_pulse: true,
+                x_event: true,
+                z_event: true,
+                z_polarity: EventPolarity::Negative,
+                ..PulseSource::default()
+            }),
+            false,
+        );
+        assert_eq!(
+            Some(DecodedEvent::Tap {
+                axis: Axis::X,
+                double: true,
+                polarity: EventPolarity::Positive
+            }),
+            events.next()
+        );
+        assert_eq!(
+            Some(DecodedEvent::Tap {
+                axis: Axis::Z,
+                double: true,
+                polarity: EventPolarity::Negative
+            }),
+            events.next()
+        );
+        assert_eq!(None, events.next());
+    }
+
+    #[test]
+    fn freefall() {
+        let src = FfMtSource {
+            event_active: true,
+            ..FfMtSource::default()
+        };
+        let mut events = decode(Event::FreefallMotion(src), false);
+        assert_eq!(Some(DecodedEvent::Freefall), events.next());
+    }
+
+    #[test]
+    fn motion() {
+        let src = FfMtSource {
+            event_active: true,
+            x_event: true,
+            ..FfMtSource::default()
+        };
+        let mut events = decode(Event::FreefallMotion(src), true);
+        assert_eq!(
+            Some(DecodedEvent::Motion {
+                axes: EventAxes {
+                    x: true,
+                    ..EventAxes::default()
+                }
+            }),
+            events.next()
+        );
+    }
+
+    #[test]
+    fn data_ready() {
+        let m = UnscaledMeasurement { x: 1, y: 2, z: 3 };
+        let mut events = decode(Event::DataReady(m), false);
+        assert_eq!(Some(DecodedEvent::DataReady(m)), events.next());
+    }
+
+    #[test]
+    fn all_sources_fit() {
+        let mut events = Events::default();
+        events.decode(Event::AutoSleep(SystemMode::Sleep), false);
+        events.decode(
+            Event::Fifo(FifoStatus {
+                overflow: true,
+                watermark: true,
+                count: 32,
+            }),
+            false,
+        );
+        let all_axes = PulseSource {
+            event_active: true,
+            x_event: true,
+            y_event: true,
+            z_event: true,
+            ..PulseSource::default()
+        };
+        events.decode(Event::Pulse(all_axes), false);
+        events.decode(
+            Event::Transient(TransientSource {
+                event_active: true,
+                ..TransientSource::default()
+            }),
+            false,
+        );
+        events.decode(Event::PortraitLandscape(Default::default()), false);
+        events.decode(
+            Event::FreefallMotion(FfMtSource {
+                event_active: true,
+                ..FfMtSource::default()
+            }),
+            true,
+        );
+        events.decode(Event::DataReady(UnscaledMeasurement::default()), false);
+        assert_eq!(MAX_EVENTS, events.count());
+    }
+}
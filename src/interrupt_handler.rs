@@ -0,0 +1,231 @@
+//! Interrupt servicing bound to the INT pins
+
+use embedded_hal::{
+    digital::{Error as _, ErrorKind, InputPin},
+    i2c::{I2c, SevenBitAddress},
+};
+
+use crate::{
+    common::{fifo::get_fifo_status, pulse::get_pulse_source, transient::get_transient_source},
+    ic::private::Sealed,
+    mode,
+    register_access::{BitFlags, Register},
+    Config, Error, Event, Mma8x5x,
+};
+
+/// INT1 pin connected to the device
+#[derive(Debug)]
+pub struct Int1<P>(pub P);
+
+/// INT2 pin connected to the device
+#[derive(Debug)]
+pub struct Int2<P>(pub P);
+
+/// Interrupt pins connected to the device
+///
+/// Implemented for [`Int1`], [`Int2`] and `(Int1, Int2)` if both pins are connected.
+pub trait InputPins {
+    /// Return whether the INT1 and INT2 pins are high or `None` if a pin is not connected.
+    fn levels(&mut self) -> Result<(Option<bool>, Option<bool>), ErrorKind>;
+}
+
+impl<P: InputPin> InputPins for Int1<P> {
+    fn levels(&mut self) -> Result<(Option<bool>, Option<bool>), ErrorKind> {
+        let int1 = self.0.is_high().map_err(|e| e.kind())?;
+        Ok((Some(int1), None))
+    }
+}
+
+impl<P: InputPin> InputPins for Int2<P> {
+    fn levels(&mut self) -> Result<(Option<bool>, Option<bool>), ErrorKind> {
+        let int2 = self.0.is_high().map_err(|e| e.kind())?;
+        Ok((None, Some(int2)))
+    }
+}
+
+impl<P1: InputPin, P2: InputPin> InputPins for (Int1<P1>, Int2<P2>) {
+    fn levels(&mut self) -> Result<(Option<bool>, Option<bool>), ErrorKind> {
+        let int1 = self.0 .0.is_high().map_err(|e| e.kind())?;
+        let int2 = self.1 .0.is_high().map_err(|e| e.kind())?;
+        Ok((Some(int1), Some(int2)))
+    }
+}
+
+/// Interrupt handler
+///
+/// Owns the interrupt pins and knows the interrupt pin polarity, enabled
+/// interrupts and pin routes configured through the driver when it was created.
+/// See [`interrupt_handler()`](struct.Mma8x5x.html#method.interrupt_handler).
+#[derive(Debug)]
+pub struct InterruptHandler<PINS> {
+    pins: PINS,
+    ctrl_reg3: Config,
+    ctrl_reg4: Config,
+    ctrl_reg5: Config,
+}
+
+impl<PINS> InterruptHandler<PINS> {
+    /// Destroy the interrupt handler, return the interrupt pins.
+    pub fn destroy(self) -> PINS {
+        self.pins
+    }
+}
+
+impl<PINS: InputPins> InterruptHandler<PINS> {
+    /// Return the enabled interrupt sources routed to a pin at its active level.
+    pub(crate) fn pending_sources<E>(&mut self) -> Result<u8, Error<E>> {
+        let (int1, int2) = self.pins.levels().map_err(Error::Pin)?;
+        let active_level = Some(self.ctrl_reg3.is_high(BitFlags::IPOL));
+        Ok(get_pending_sources(
+            self.ctrl_reg4.bits,
+            self.ctrl_reg5.bits,
+            int1 == active_level,
+            int2 == active_level,
+        ))
+    }
+
+    /// Service a pending interrupt
+    ///
+    /// If an interrupt pin is at its active level, reads the interrupt source
+    /// and the source register of the interrupt, which clears it, and returns
+    /// the corresponding event. Returns `None` if no interrupt is pending.
+    ///
+    /// If several interrupts are pending, only one is serviced per call.
+    /// The sources are serviced in the order: auto-sleep, FIFO, transient,
+    /// portrait/landscape, pulse, freefall/motion and data ready.
+    pub fn service<E, I2C, IC>(
+        &mut self,
+        sensor: &mut Mma8x5x<I2C, IC, mode::Active>,
+    ) -> Result<Option<Event>, Error<E>>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        IC: Sealed,
+    {
+        let pending = self.pending_sources()?;
+        if pending == 0 {
+            return Ok(None);
+        }
+        let int_src = sensor.read_reg(Register::INT_SOURCE)?;
+        match get_next_source(int_src & pending) {
+            Some(source) => sensor.read_event(source).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<I2C, IC, MODE> Mma8x5x<I2C, IC, MODE> {
+    /// Create an interrupt handler owning the given interrupt pins.
+    ///
+    /// The interrupt pin polarity, enabled interrupts and pin routes are
+    /// taken from the current configuration, so the interrupts should be
+    /// configured beforehand.
+    pub fn interrupt_handler<PINS: InputPins>(&self, pins: PINS) -> InterruptHandler<PINS> {
+        InterruptHandler {
+            pins,
+            ctrl_reg3: self.ctrl_reg3,
+            ctrl_reg4: self.ctrl_reg4,
+            ctrl_reg5: self.ctrl_reg5,
+        }
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read the source register of the given interrupt source, which clears it.
+    pub(crate) fn read_event(&mut self, source: u8) -> Result<Event, Error<E>> {
+        let event = match source {
+            BitFlags::SRC_ASLP => Event::AutoSleep(self.system_mode()?),
+            BitFlags::SRC_FIFO => Event::Fifo(get_fifo_status(self.read_reg(Register::STATUS)?)),
+            BitFlags::SRC_TRANS => Event::Transient(get_transient_source(
+                self.read_reg(Register::TRANSIENT_SRC)?,
+            )),
+            BitFlags::SRC_LNDPRT => Event::PortraitLandscape(self.portrait_landscape_status()?),
+            BitFlags::SRC_PULSE => {
+                Event::Pulse(get_pulse_source(self.read_reg(Register::PULSE_SRC)?))
+            }
+            BitFlags::SRC_FF_MT => Event::FreefallMotion(self.freefall_motion_source()?),
+            _ => {
+                let (x, y, z) = self.read_raw()?;
+                Event::DataReady(IC::convert(x, y, z))
+            }
+        };
+        Ok(event)
+    }
+}
+
+/// Interrupt sources in the order they are serviced
+pub(crate) const SOURCES: [u8; 7] = [
+    BitFlags::SRC_ASLP,
+    BitFlags::SRC_FIFO,
+    BitFlags::SRC_TRANS,
+    BitFlags::SRC_LNDPRT,
+    BitFlags::SRC_PULSE,
+    BitFlags::SRC_FF_MT,
+    BitFlags::SRC_DRDY,
+];
+
+/// Return the enabled interrupt sources routed to the active pins.
+///
+/// The bits in CTRL_REG4 (enable), CTRL_REG5 (route) and INT_SOURCE match.
+/// A set bit in CTRL_REG5 routes the source to INT1, otherwise to INT2.
+pub(crate) fn get_pending_sources(
+    ctrl_reg4: u8,
+    ctrl_reg5: u8,
+    int1_active: bool,
+    int2_active: bool,
+) -> u8 {
+    let int1 = if int1_active { ctrl_reg5 } else { 0 };
+    let int2 = if int2_active { !ctrl_reg5 } else { 0 };
+    ctrl_reg4 & (int1 | int2)
+}
+
+/// Return the interrupt source to service next, if any.
+pub(crate) fn get_next_source(int_src: u8) -> Option<u8> {
+    SOURCES.into_iter().find(|source| (int_src & source) != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_pending_sources_if_pins_inactive() {
+        assert_eq!(0, get_pending_sources(0xFF, 0xF0, false, false));
+    }
+
+    #[test]
+    fn pending_sources_follow_routes() {
+        assert_eq!(0xF0, get_pending_sources(0xFF, 0xF0, true, false));
+        assert_eq!(0x0F, get_pending_sources(0xFF, 0xF0, false, true));
+        assert_eq!(0xFF, get_pending_sources(0xFF, 0xF0, true, true));
+    }
+
+    #[test]
+    fn pending_sources_are_enabled() {
+        assert_eq!(
+            BitFlags::SRC_DRDY,
+            get_pending_sources(BitFlags::INT_EN_DRDY, 0, true, true)
+        );
+    }
+
+    #[test]
+    fn no_next_source() {
+        assert_eq!(None, get_next_source(0));
+    }
+
+    #[test]
+    fn next_source_follows_order() {
+        assert_eq!(Some(BitFlags::SRC_ASLP), get_next_source(0xFF));
+        assert_eq!(
+            Some(BitFlags::SRC_PULSE),
+            get_next_source(BitFlags::SRC_DRDY | BitFlags::SRC_PULSE)
+        );
+        assert_eq!(
+            Some(BitFlags::SRC_DRDY),
+            get_next_source(BitFlags::SRC_DRDY)
+        );
+    }
+}
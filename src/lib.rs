@@ -7,7 +7,10 @@
 //! - Change mode to active/standby. See: [`into_active()`].
 //! - Read raw unscaled measurement. See: [`read_unscaled()`].
 //! - Read measurement. See: [`read()`].
+//! - Read measurement in milli-g, m/s² or fixed-point (requires the `fixed` feature).
+//!   See: [`read_mg()`], [`read_ms2()`] and [`read_fixed()`].
 //! - Read data status. See: [`data_status()`].
+//! - Read data status and measurement in a single transaction. See: [`read_with_status()`].
 //! - Read system operating mode. See: [`system_mode()`].
 //! - Set G scale. See: [`set_scale()`].
 //! - Set data rate. See [`set_data_rate()`].
@@ -15,9 +18,20 @@
 //! - Set sleep power mode. See [`set_sleep_power_mode()`].
 //! - Set read mode. See: [`set_read_mode()`].
 //! - Set offset correction. See: [`set_offset_correction()`].
+//! - Calibrate the offset correction automatically. See: [`calibrate_offsets()`].
+//! - Read, store and apply the device configuration. See: [`read_config()`] and [`apply_config()`].
+//! - Read the configuration back from the device. See: [`sync_from_device()`].
+//! - Read and decode all the registers and compare the register contents. See: [`dump_registers()`].
+//! - Take over a device which is already active. See: [`attach_mma8451()`].
 //! - Read the device ID. See: [`device_id()`].
+//! - Detect which device is connected and create the right driver. See: [`DetectedDevice::detect()`].
+//! - Use different device models through a single driver type with the model and mode
+//!   selected at runtime. See: [`AnyMma8x5x`].
 //! - Reset device. See: [`reset()`].
+//! - Verify the device ID and reset the device. See: [`init()`].
 //! - Enable/disable self-test mode. See: [`enable_self_test()`].
+//! - Run the self-test and check the result against limits derived from the typical
+//!   datasheet values. See: [`run_self_test()`].
 //! - Auto-sleep/wake:
 //!     - Enable/disable auto-sleep/wake. See: [`enable_auto_sleep()`].
 //!     - Set auto-sleep data rate. See: [`set_auto_sleep_data_rate()`].
@@ -27,6 +41,7 @@
 //!     - Set debounce counter mode. See: [`set_debounce_counter_mode()`].
 //!     - Set debounce counter. See: [`set_debounce_counter()`].
 //!     - Read portrait/landscape status. See: [`portrait_landscape_status()`].
+//!     - Set trip, lockout and hysteresis angles (only MMA8451 and MMA8652). See: [`set_portrait_landscape_angles()`].
 //! - Interrupts:
 //!     - Enable/disable interrupts. See: [`set_enabled_interrupts()`].
 //!     - Set interrupt pin routes. See: [`set_interrupt_pin_routes()`].
@@ -34,11 +49,45 @@
 //!     - Set interrupt pin configuration. See: [`set_interrupt_pin_configuration()`].
 //!     - Set interrupts that wake the device from sleep. See: [`set_wake_interrupts()`].
 //!     - Read interrupt status. See: [`interrupt_status()`].
+//!     - Service the interrupts signalled on the INT pins. See: [`InterruptHandler`].
+//!     - Read and decode all pending interrupt sources. See: [`next_events()`].
+//! - FIFO (only MMA8451 and MMA8652):
+//!     - Set FIFO mode. See: [`set_fifo_mode()`].
+//!     - Set FIFO watermark. See: [`set_fifo_watermark()`].
+//!     - Set FIFO trigger sources. See: [`set_fifo_trigger_sources()`].
+//!     - Read FIFO status. See: [`fifo_status()`].
+//!     - Read FIFO samples. See: [`read_fifo()`].
+//!     - Enable/disable the FIFO gate. See: [`enable_fifo_gate()`].
+//!     - Read system status including the FIFO gate error. See: [`system_status()`].
+//! - Transient detection (not available on MMA8653):
+//!     - Set transient detection configuration. See: [`set_transient_config()`].
+//!     - Read transient detection source. See: [`transient_source()`].
+//! - Pulse (tap) detection (not available on MMA8653):
+//!     - Set single/double pulse detection configuration. See: [`set_pulse_config()`].
+//!     - Read pulse detection source. See: [`pulse_source()`].
+//! - Freefall/motion detection:
+//!     - Set freefall/motion detection configuration. See: [`set_freefall_motion_config()`].
+//!     - Read freefall/motion detection source. See: [`freefall_motion_source()`].
+//! - High-pass filter (not available on MMA8653):
+//!     - Set high-pass filter configuration and enable filtered output data. See: [`set_high_pass_filter()`].
+//! - Asynchronous operation (requires the `async` feature):
+//!     - Use all the functions above through `embedded-hal-async`. See: [`Mma8x5xAsync`].
+//!     - Wait for an interrupt pin to become active. See: [`wait_for_interrupt()`].
+//! - Simulation (requires the `sim` feature):
+//!     - Test application code against a simulated device on a host. See: [`SimulatedMma8x5x`].
+//!     - Simulate the embedded functions, auto-sleep and the INT pins while advancing the time.
+//! - Tracing (requires the `trace` feature):
+//!     - Record the I²C transactions with timestamps as text. See: [`RecordingI2c`].
+//!     - Replay a recorded trace in tests. See: [`ReplayI2c`].
 //!
 //! [`into_active()`]: struct.Mma8x5x.html#method.into_active
 //! [`read_unscaled()`]: struct.Mma8x5x.html#method.read_unscaled
 //! [`read()`]: struct.Mma8x5x.html#method.read
+//! [`read_mg()`]: struct.Mma8x5x.html#method.read_mg
+//! [`read_ms2()`]: struct.Mma8x5x.html#method.read_ms2
+//! [`read_fixed()`]: struct.Mma8x5x.html#method.read_fixed
 //! [`data_status()`]: struct.Mma8x5x.html#method.data_status
+//! [`read_with_status()`]: struct.Mma8x5x.html#method.read_with_status
 //! [`system_mode()`]: struct.Mma8x5x.html#method.system_mode
 //! [`set_scale()`]: struct.Mma8x5x.html#method.set_scale
 //! [`set_data_rate()`]: struct.Mma8x5x.html#method.set_data_rate
@@ -46,9 +95,19 @@
 //! [`set_sleep_power_mode()`]: struct.Mma8x5x.html#method.set_sleep_power_mode
 //! [`set_read_mode()`]: struct.Mma8x5x.html#method.set_read_mode
 //! [`set_offset_correction()`]: struct.Mma8x5x.html#method.set_offset_correction
+//! [`calibrate_offsets()`]: struct.Mma8x5x.html#method.calibrate_offsets
+//! [`read_config()`]: struct.Mma8x5x.html#method.read_config
+//! [`apply_config()`]: struct.Mma8x5x.html#method.apply_config
+//! [`sync_from_device()`]: struct.Mma8x5x.html#method.sync_from_device
+//! [`dump_registers()`]: struct.Mma8x5x.html#method.dump_registers
+//! [`attach_mma8451()`]: struct.Mma8x5x.html#method.attach_mma8451
 //! [`device_id()`]: struct.Mma8x5x.html#method.device_id
+//! [`DetectedDevice::detect()`]: enum.DetectedDevice.html#method.detect
+//! [`AnyMma8x5x`]: struct.AnyMma8x5x.html
 //! [`reset()`]: struct.Mma8x5x.html#method.reset
+//! [`init()`]: struct.Mma8x5x.html#method.init
 //! [`enable_self_test()`]: struct.Mma8x5x.html#method.enable_self_test
+//! [`run_self_test()`]: struct.Mma8x5x.html#method.run_self_test
 //! [`enable_auto_sleep()`]: struct.Mma8x5x.html#method.enable_auto_sleep
 //! [`set_auto_sleep_data_rate()`]: struct.Mma8x5x.html#method.set_auto_sleep_data_rate
 //! [`set_auto_sleep_count()`]: struct.Mma8x5x.html#method.set_auto_sleep_count
@@ -56,12 +115,34 @@
 //! [`set_debounce_counter_mode()`]: struct.Mma8x5x.html#method.set_debounce_counter_mode
 //! [`set_debounce_counter()`]: struct.Mma8x5x.html#method.set_debounce_counter
 //! [`portrait_landscape_status()`]: struct.Mma8x5x.html#method.portrait_landscape_status
+//! [`set_portrait_landscape_angles()`]: struct.Mma8x5x.html#method.set_portrait_landscape_angles
 //! [`set_enabled_interrupts()`]: struct.Mma8x5x.html#method.set_enabled_interrupts
 //! [`set_interrupt_pin_routes()`]: struct.Mma8x5x.html#method.set_interrupt_pin_routes
 //! [`set_interrupt_pin_polarity()`]: struct.Mma8x5x.html#method.set_interrupt_pin_polarity
 //! [`set_interrupt_pin_configuration()`]: struct.Mma8x5x.html#method.set_interrupt_pin_configuration
 //! [`set_wake_interrupts()`]: struct.Mma8x5x.html#method.set_wake_interrupts
 //! [`interrupt_status()`]: struct.Mma8x5x.html#method.interrupt_status
+//! [`InterruptHandler`]: struct.InterruptHandler.html
+//! [`next_events()`]: struct.Mma8x5x.html#method.next_events
+//! [`set_fifo_mode()`]: struct.Mma8x5x.html#method.set_fifo_mode
+//! [`set_fifo_watermark()`]: struct.Mma8x5x.html#method.set_fifo_watermark
+//! [`set_fifo_trigger_sources()`]: struct.Mma8x5x.html#method.set_fifo_trigger_sources
+//! [`fifo_status()`]: struct.Mma8x5x.html#method.fifo_status
+//! [`read_fifo()`]: struct.Mma8x5x.html#method.read_fifo
+//! [`enable_fifo_gate()`]: struct.Mma8x5x.html#method.enable_fifo_gate
+//! [`system_status()`]: struct.Mma8x5x.html#method.system_status
+//! [`set_transient_config()`]: struct.Mma8x5x.html#method.set_transient_config
+//! [`transient_source()`]: struct.Mma8x5x.html#method.transient_source
+//! [`set_pulse_config()`]: struct.Mma8x5x.html#method.set_pulse_config
+//! [`pulse_source()`]: struct.Mma8x5x.html#method.pulse_source
+//! [`set_freefall_motion_config()`]: struct.Mma8x5x.html#method.set_freefall_motion_config
+//! [`freefall_motion_source()`]: struct.Mma8x5x.html#method.freefall_motion_source
+//! [`set_high_pass_filter()`]: struct.Mma8x5x.html#method.set_high_pass_filter
+//! [`Mma8x5xAsync`]: struct.Mma8x5xAsync.html
+//! [`wait_for_interrupt()`]: struct.Mma8x5xAsync.html#method.wait_for_interrupt
+//! [`SimulatedMma8x5x`]: sim/struct.SimulatedMma8x5x.html
+//! [`RecordingI2c`]: trace/struct.RecordingI2c.html
+//! [`ReplayI2c`]: trace/struct.ReplayI2c.html
 //!
 //! <!-- TODO
 //! [Introductory blog post](TODO)
@@ -104,6 +185,20 @@
 //!
 //! (Unavailable features are marked with "-" as this is more easily readable than Yes/No)
 //!
+//! The features are also available as capability traits implemented by the
+//! IC markers, for example [`ic::HasFifo`]. The functions specific to a feature
+//! only exist for the devices supporting it, so this does not compile:
+//!
+//! ```compile_fail
+//! # use linux_embedded_hal::I2cdev;
+//! # use mma8x5x::{Mma8x5x, TransientConfig};
+//! #
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8653(dev);
+//! // The MMA8653 has no transient detection
+//! sensor.set_transient_config(TransientConfig::default()).unwrap();
+//! ```
+//!
 //! Documentation:
 //! - Datasheets:
 //!     - [MMA8451Q](https://www.nxp.com/docs/en/data-sheet/MMA8451Q.pdf)
@@ -221,6 +316,407 @@
 //! }
 //! ```
 //!
+//! ### Read samples from the FIFO
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! use mma8x5x::{Mma8x5x, FifoMode, OutputDataRate, UnscaledMeasurement};
+//!
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8652(dev);
+//! sensor.set_data_rate(OutputDataRate::Hz400).unwrap();
+//! sensor.set_fifo_watermark(16).unwrap();
+//! sensor.set_fifo_mode(FifoMode::Circular).unwrap();
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! let mut buffer = [UnscaledMeasurement::default(); 32];
+//! loop {
+//!     let count = sensor.read_fifo(&mut buffer).unwrap();
+//!     for accel in &buffer[..count] {
+//!         println!("Raw acceleration: {:?}", accel);
+//!     }
+//! }
+//! ```
+//!
+//! ### Wake up from sleep on transient motion
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! use mma8x5x::{Mma8x5x, EnabledInterrupts, TransientConfig, WakeInterrupts};
+//!
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8452(dev, Default::default());
+//! sensor.set_transient_config(TransientConfig {
+//!     x_axis: true,
+//!     y_axis: true,
+//!     z_axis: true,
+//!     latch: true,
+//!     threshold: 0.5,
+//!     debounce_time_ms: 10.0,
+//!     ..TransientConfig::default()
+//! }).unwrap();
+//! sensor.set_enabled_interrupts(EnabledInterrupts {
+//!     transient: true,
+//!     ..EnabledInterrupts::default()
+//! }).unwrap();
+//! sensor.set_wake_interrupts(WakeInterrupts {
+//!     transient: true,
+//!     ..WakeInterrupts::default()
+//! }).unwrap();
+//! sensor.enable_auto_sleep().unwrap();
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! loop {
+//!     let source = sensor.transient_source().unwrap();
+//!     if source.event_active {
+//!         println!("Transient: {:?}", source);
+//!     }
+//! }
+//! ```
+//!
+//! ### Detect a drop with freefall detection
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! use mma8x5x::{Mma8x5x, FreefallMotionConfig, FreefallMotionMode, OutputDataRate};
+//!
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8653(dev);
+//! sensor.set_data_rate(OutputDataRate::Hz100).unwrap();
+//! sensor.set_freefall_motion_config(FreefallMotionConfig {
+//!     mode: FreefallMotionMode::Freefall,
+//!     x_axis: true,
+//!     y_axis: true,
+//!     z_axis: true,
+//!     latch: true,
+//!     threshold: 0.2,
+//!     debounce_time_ms: 120.0,
+//!     ..FreefallMotionConfig::default()
+//! }).unwrap();
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! loop {
+//!     let source = sensor.freefall_motion_source().unwrap();
+//!     if source.event_active {
+//!         println!("Freefall detected");
+//!     }
+//! }
+//! ```
+//!
+//! ### Verify the device and bring it to a known state
+//!
+//! ```no_run
+//! # use linux_embedded_hal::{Delay, I2cdev};
+//! use mma8x5x::{Error, Mma8x5x};
+//!
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8652(dev);
+//! match sensor.init(&mut Delay) {
+//!     Err(Error::InvalidDevice(id)) => println!("Wrong device found. Device ID: {}", id),
+//!     Err(e) => println!("Error: {:?}", e),
+//!     Ok(()) => println!("MMA8652 ready"),
+//! }
+//! ```
+//!
+//! ### Detect the connected device
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! use mma8x5x::DetectedDevice;
+//!
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! match DetectedDevice::detect(dev) {
+//!     Ok(DetectedDevice::Mma8452(sensor)) => {
+//!         let mut sensor = sensor.into_active().ok().unwrap();
+//!         println!("MMA8452: {:?}", sensor.read().unwrap());
+//!     }
+//!     Ok(DetectedDevice::Mma8652(sensor)) => {
+//!         let mut sensor = sensor.into_active().ok().unwrap();
+//!         println!("MMA8652: {:?}", sensor.read().unwrap());
+//!     }
+//!     Ok(other) => println!("Unexpected device: {:?}", other.ic()),
+//!     Err(e) => println!("No device found. Device ID: {:?}", e.device_id),
+//! }
+//! ```
+//!
+//! ### Use the asynchronous API and wait for interrupts
+//!
+//! With the `async` feature enabled, the same functionality is available
+//! through [`Mma8x5xAsync`] using any `embedded-hal-async` implementation,
+//! for example on Embassy.
+//!
+//! ```ignore
+//! use mma8x5x::{EnabledInterrupts, InterruptPinRoutes, InterruptSourcePinRoute, Mma8x5xAsync};
+//!
+//! // `i2c` implements `embedded_hal_async::i2c::I2c` and
+//! // `int1` implements `embedded_hal_async::digital::Wait`
+//! let mut sensor = Mma8x5xAsync::new_mma8652(i2c);
+//! sensor.set_enabled_interrupts(EnabledInterrupts {
+//!     data_ready: true,
+//!     ..EnabledInterrupts::default()
+//! }).await.unwrap();
+//! sensor.set_interrupt_pin_routes(InterruptPinRoutes {
+//!     data_ready: InterruptSourcePinRoute::Int1,
+//!     ..InterruptPinRoutes::default()
+//! }).await.unwrap();
+//! let mut sensor = sensor.into_active().await.ok().unwrap();
+//! loop {
+//!     sensor.wait_for_interrupt(&mut int1).await.unwrap();
+//!     let accel = sensor.read().await.unwrap();
+//!     println!("Acceleration: {:?}", accel);
+//! }
+//! ```
+//!
+//! ### Calibrate the offset correction
+//!
+//! ```no_run
+//! # use linux_embedded_hal::{Delay, I2cdev};
+//! # use mma8x5x::{CalibrationOrientation, Mma8x5x};
+//! #
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8652(dev);
+//! // The device is lying flat, face up
+//! let calibration = sensor
+//!     .calibrate_offsets(CalibrationOrientation::ZUp, &mut Delay)
+//!     .unwrap();
+//! println!("Offsets: {:?}", calibration);
+//! ```
+//!
+//! ### Store the device configuration and apply it again
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! # use mma8x5x::{DeviceConfig, Mma8x5x};
+//! #
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8652(dev);
+//! let data: [u8; DeviceConfig::ENCODED_SIZE] = sensor.read_config().unwrap().to_bytes();
+//! // Store `data` in EEPROM. At boot:
+//! if let Some(config) = DeviceConfig::from_bytes(&data) {
+//!     sensor.apply_config(&config).unwrap();
+//! }
+//! ```
+//!
+//! ### Take over a device which is already running
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! # use mma8x5x::Mma8x5x;
+//! #
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! // The device stayed powered and active while the microcontroller restarted
+//! let mut sensor = Mma8x5x::attach_mma8652(dev).ok().unwrap();
+//! let accel = sensor.read().unwrap();
+//! println!("Acceleration: {:?}", accel);
+//! ```
+//!
+//! ### Write code generic over the devices supporting a feature
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! # use mma8x5x::{ic::HasFifo, mode, FifoMode, Mma8x5x};
+//! # use embedded_hal::i2c::I2c;
+//! #
+//! fn enable_fifo<I2C: I2c, IC: HasFifo>(sensor: &mut Mma8x5x<I2C, IC, mode::Standby>) {
+//!     sensor.set_fifo_mode(FifoMode::Circular).unwrap();
+//!     sensor.set_fifo_watermark(16).unwrap();
+//! }
+//!
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8652(dev);
+//! enable_fifo(&mut sensor);
+//! ```
+//!
+//! ### Use different device models through the same driver type
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! # use mma8x5x::{AnyMma8x5x, Error, TransientConfig};
+//! #
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = AnyMma8x5x::detect(dev).ok().unwrap();
+//! match sensor.set_transient_config(TransientConfig::default()) {
+//!     Err(Error::Unsupported) => println!("No transient detection on {:?}", sensor.ic()),
+//!     result => result.unwrap(),
+//! }
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! let accel = sensor.read().unwrap();
+//! println!("Acceleration: {:?}", accel);
+//! ```
+//!
+//! ### Read the acceleration in milli-g without floating-point arithmetic
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! # use mma8x5x::Mma8x5x;
+//! #
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let sensor = Mma8x5x::new_mma8652(dev);
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! let accel = sensor.read_mg().unwrap();
+//! println!("Acceleration: {} {} {} mg", accel.x, accel.y, accel.z);
+//! ```
+//!
+//! ### Service the interrupts signalled on the INT1 pin
+//!
+//! ```no_run
+//! # use linux_embedded_hal::{I2cdev, SysfsPin};
+//! use mma8x5x::{
+//!     EnabledInterrupts, Event, Int1, InterruptPinRoutes, InterruptSourcePinRoute, Mma8x5x,
+//! };
+//!
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! # let int1 = SysfsPin::new(17);
+//! let mut sensor = Mma8x5x::new_mma8652(dev);
+//! sensor.set_enabled_interrupts(EnabledInterrupts {
+//!     data_ready: true,
+//!     freefall_motion: true,
+//!     ..EnabledInterrupts::default()
+//! }).ok().unwrap();
+//! sensor.set_interrupt_pin_routes(InterruptPinRoutes {
+//!     data_ready: InterruptSourcePinRoute::Int1,
+//!     freefall_motion: InterruptSourcePinRoute::Int1,
+//!     ..InterruptPinRoutes::default()
+//! }).ok().unwrap();
+//! let mut handler = sensor.interrupt_handler(Int1(int1));
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! loop {
+//!     match handler.service(&mut sensor).ok().unwrap() {
+//!         Some(Event::DataReady(accel)) => println!("Acceleration: {:?}", accel),
+//!         Some(Event::FreefallMotion(source)) => println!("Freefall: {:?}", source),
+//!         _ => (),
+//!     }
+//! }
+//! ```
+//!
+//! ### Handle taps and orientation changes
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! use mma8x5x::{DecodedEvent, EnabledInterrupts, Mma8x5x, PulseConfig};
+//!
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8452(dev, Default::default());
+//! sensor.set_pulse_config(PulseConfig::default()).ok().unwrap();
+//! sensor.enable_portrait_landscape_detection().ok().unwrap();
+//! sensor.set_enabled_interrupts(EnabledInterrupts {
+//!     pulse: true,
+//!     portrait_landscape: true,
+//!     ..EnabledInterrupts::default()
+//! }).ok().unwrap();
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! loop {
+//!     for event in sensor.next_events().ok().unwrap() {
+//!         match event {
+//!             DecodedEvent::Tap { axis, double, .. } => {
+//!                 println!("Tap on {:?} axis (double: {})", axis, double)
+//!             }
+//!             DecodedEvent::OrientationChanged(status) => println!("Orientation: {:?}", status),
+//!             _ => (),
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ### Test application code without hardware
+//!
+//! With the `sim` feature enabled, a simulated device can be used instead of
+//! the I²C bus. The acceleration in G is provided by a closure.
+//!
+//! ```ignore
+//! use mma8x5x::{sim::SimulatedMma8x5x, Measurement, Mma8x5x};
+//!
+//! let sim = SimulatedMma8x5x::new_mma8652(|| Measurement {
+//!     x: 0.0,
+//!     y: 0.0,
+//!     z: 1.0,
+//! });
+//! let sensor = Mma8x5x::new_mma8652(sim);
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! let accel = sensor.read_unscaled().unwrap();
+//! assert_eq!(1024, accel.z);
+//! ```
+//!
+//! The simulated device also runs the embedded functions on the samples taken
+//! as the time is advanced and drives simulated INT pins, which can be passed
+//! to the interrupt handler.
+//!
+//! ```ignore
+//! use core::{cell::Cell, time::Duration};
+//! use mma8x5x::{
+//!     sim::SimulatedMma8x5x, EnabledInterrupts, Event, Int2, Measurement, Mma8x5x,
+//! };
+//!
+//! let z = Cell::new(1.0);
+//! let sim = SimulatedMma8x5x::new_mma8652(|| Measurement { x: 0.0, y: 0.0, z: z.get() });
+//! let mut sensor = Mma8x5x::new_mma8652(&sim);
+//! sensor.enable_portrait_landscape_detection().unwrap();
+//! sensor.set_enabled_interrupts(EnabledInterrupts {
+//!     portrait_landscape: true,
+//!     ..EnabledInterrupts::default()
+//! }).unwrap();
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! let mut handler = sensor.interrupt_handler(Int2(sim.int2()));
+//! // Turn the device upside down
+//! z.set(-1.0);
+//! sim.advance(Duration::from_millis(10));
+//! if let Some(Event::PortraitLandscape(status)) = handler.service(&mut sensor).unwrap() {
+//!     println!("Orientation: {:?}", status);
+//! }
+//! ```
+//!
+//! ### Record and replay the I²C transactions
+//!
+//! With the `trace` feature enabled, the transactions of a device in the field
+//! can be recorded as text and replayed later in a test.
+//!
+//! ```ignore
+//! use linux_embedded_hal::I2cdev;
+//! use mma8x5x::{trace::{RecordingI2c, ReplayI2c}, Mma8x5x};
+//! use std::time::Instant;
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let start = Instant::now();
+//! let i2c = RecordingI2c::new(dev, String::new(), || start.elapsed().as_micros() as u64);
+//! let sensor = Mma8x5x::new_mma8652(i2c);
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! let accel = sensor.read_unscaled().unwrap();
+//! let (_dev, trace) = sensor.destroy().destroy();
+//!
+//! let sensor = Mma8x5x::new_mma8652(ReplayI2c::new(&trace));
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! assert_eq!(accel, sensor.read_unscaled().unwrap());
+//! ```
+//!
+//! ### Inspect the register contents
+//!
+//! ```no_run
+//! # use linux_embedded_hal::I2cdev;
+//! # use mma8x5x::{Mma8x5x, OutputDataRate};
+//! #
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8652(dev);
+//! let before = sensor.dump_registers().unwrap();
+//! // Prints each register with its decoded fields, for example:
+//! // CTRL_REG1: ODR=800Hz ASLP_RATE=50Hz F_READ=0 ACTIVE=0
+//! println!("{}", before);
+//! sensor.set_data_rate(OutputDataRate::Hz50).unwrap();
+//! let after = sensor.dump_registers().unwrap();
+//! // Prints the changed fields: CTRL_REG1: ODR=800Hz->50Hz
+//! println!("{}", before.diff(&after));
+//! ```
+//!
+//! ### Run the self-test
+//!
+//! ```no_run
+//! # use linux_embedded_hal::{Delay, I2cdev};
+//! # use mma8x5x::Mma8x5x;
+//! #
+//! # let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Mma8x5x::new_mma8652(dev);
+//! let report = sensor.run_self_test(&mut Delay).unwrap();
+//! if !report.passed {
+//!     println!("Self-test failed: {:?}", report);
+//! }
+//! ```
+//!
 //! ### Enable self-test
 //!
 //! ```no_run
@@ -242,19 +738,45 @@
 
 use core::marker::PhantomData;
 mod types;
+#[cfg(feature = "fixed")]
+pub use crate::types::FixedMeasurement;
 use crate::types::MMA845X_BASE_ADDR;
 pub use crate::types::{
-    ic, mode, AutoSleepDataRate, DataStatus, DebounceCounterMode, EnabledInterrupts, Error,
-    FrontBackOrientation, GScale, InterruptPinConfiguration, InterruptPinPolarity,
-    InterruptPinRoutes, InterruptSourcePinRoute, InterruptStatus, Measurement, ModeChangeError,
-    OutputDataRate, PortraitLandscapeOrientation, PortraitLandscapeStatus, PowerMode, ReadMode,
-    SlaveAddr, SystemMode, UnscaledMeasurement, WakeInterrupts,
+    ic, mode, AutoSleepDataRate, Axis, BackFrontTripAngle, CalibrationOrientation, DataStatus,
+    DebounceCounterMode, DecodedEvent, DetectedIc, DetectionError, DeviceConfig, DriverMode,
+    EnabledInterrupts, Error, Event, EventAxes, EventPolarity, FfMtSource, FifoMode, FifoStatus,
+    FifoTriggerSources, FreefallMotionConfig, FreefallMotionMode, FrontBackOrientation, GScale,
+    HighPassConfig, HighPassFilterCutoff, InterruptPinConfiguration, InterruptPinPolarity,
+    InterruptPinRoutes, InterruptSourcePinRoute, InterruptStatus, Measurement, MilliGMeasurement,
+    ModeChangeError, OffsetCalibration, OutputDataRate, PortraitLandscapeAngles,
+    PortraitLandscapeHysteresis, PortraitLandscapeOrientation, PortraitLandscapeStatus,
+    PortraitLandscapeThreshold, PowerMode, PulseConfig, PulseSource, ReadMode, SelfTestAxis,
+    SelfTestReport, SlaveAddr, SystemMode, SystemStatus, TransientConfig, TransientSource,
+    UnscaledMeasurement, WakeInterrupts, ZLockoutAngle,
 };
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "async")]
+pub use crate::asynch::Mma8x5xAsync;
+mod any;
+pub use crate::any::AnyMma8x5x;
 mod common;
 mod conversion;
+mod detect;
+pub use crate::detect::DetectedDevice;
+mod events;
+pub use crate::events::{Events, EventsError};
+mod interrupt_handler;
+pub use crate::interrupt_handler::{InputPins, Int1, Int2, InterruptHandler};
 mod mma845x;
 mod mma865x;
 mod register_access;
+mod register_dump;
+pub use crate::register_dump::{RegisterChange, RegisterDiff, RegisterDump};
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(feature = "trace")]
+pub mod trace;
 
 /// MMA8x5x device driver
 #[derive(Debug)]
@@ -265,8 +787,12 @@ pub struct Mma8x5x<I2C, IC, MODE> {
     ctrl_reg1: Config,
     ctrl_reg2: Config,
     ctrl_reg3: Config,
+    ctrl_reg4: Config,
+    ctrl_reg5: Config,
     pl_cfg: Config,
     xyz_data_cfg: Config,
+    f_setup: Config,
+    hp_filter_cutoff: Config,
     _ic: PhantomData<IC>,
     _mode: PhantomData<MODE>,
 }
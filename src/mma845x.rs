@@ -1,59 +1,90 @@
-use crate::{ic, mode, register_access::BitFlags, Config, Mma8x5x, SlaveAddr, MMA845X_BASE_ADDR};
-use core::marker::PhantomData;
+use crate::{ic, mode, Mma8x5x, ModeChangeError, SlaveAddr, MMA845X_BASE_ADDR};
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+impl<I2C, IC: ic::HasSelectableAddress> Mma8x5x<I2C, IC, mode::Standby> {
+    pub(crate) fn new_with_address(i2c: I2C, address: SlaveAddr) -> Self {
+        Mma8x5x::create(i2c, address.addr(MMA845X_BASE_ADDR))
+    }
+}
 
 impl<I2C> Mma8x5x<I2C, ic::Mma8451, mode::Standby> {
     /// Create new instance of the MMA8451 device.
     pub fn new_mma8451(i2c: I2C, address: SlaveAddr) -> Self {
-        Mma8x5x {
-            i2c,
-            xyz_data_cfg: Config::default(),
-            ctrl_reg1: Config::default(),
-            ctrl_reg2: Config::default(),
-            ctrl_reg3: Config::default(),
-            pl_cfg: Config {
-                bits: BitFlags::DBCNTM,
-            },
-            address: address.addr(MMA845X_BASE_ADDR),
-            _ic: PhantomData,
-            _mode: PhantomData,
-        }
+        Mma8x5x::new_with_address(i2c, address)
     }
 }
 
 impl<I2C> Mma8x5x<I2C, ic::Mma8452, mode::Standby> {
     /// Create new instance of the MMA8452 device.
     pub fn new_mma8452(i2c: I2C, address: SlaveAddr) -> Self {
-        Mma8x5x {
-            i2c,
-            address: address.addr(MMA845X_BASE_ADDR),
-            xyz_data_cfg: Config::default(),
-            ctrl_reg1: Config::default(),
-            ctrl_reg2: Config::default(),
-            ctrl_reg3: Config::default(),
-            pl_cfg: Config {
-                bits: BitFlags::DBCNTM,
-            },
-            _ic: PhantomData,
-            _mode: PhantomData,
-        }
+        Mma8x5x::new_with_address(i2c, address)
     }
 }
 
 impl<I2C> Mma8x5x<I2C, ic::Mma8453, mode::Standby> {
     /// Create new instance of the MMA8453 device.
     pub fn new_mma8453(i2c: I2C, address: SlaveAddr) -> Self {
-        Mma8x5x {
-            i2c,
-            xyz_data_cfg: Config::default(),
-            ctrl_reg1: Config::default(),
-            ctrl_reg2: Config::default(),
-            ctrl_reg3: Config::default(),
-            pl_cfg: Config {
-                bits: BitFlags::DBCNTM,
-            },
-            address: address.addr(MMA845X_BASE_ADDR),
-            _ic: PhantomData,
-            _mode: PhantomData,
-        }
+        Mma8x5x::new_with_address(i2c, address)
+    }
+}
+
+impl<E, I2C, IC> Mma8x5x<I2C, IC, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: ic::HasSelectableAddress,
+{
+    fn attach_with_address(
+        i2c: I2C,
+        address: SlaveAddr,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5x<I2C, IC, mode::Standby>>> {
+        Mma8x5x::new_with_address(i2c, address).attach()
+    }
+}
+
+impl<E, I2C> Mma8x5x<I2C, ic::Mma8451, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8451 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub fn attach_mma8451(
+        i2c: I2C,
+        address: SlaveAddr,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5x<I2C, ic::Mma8451, mode::Standby>>> {
+        Mma8x5x::attach_with_address(i2c, address)
+    }
+}
+
+impl<E, I2C> Mma8x5x<I2C, ic::Mma8452, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8452 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub fn attach_mma8452(
+        i2c: I2C,
+        address: SlaveAddr,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5x<I2C, ic::Mma8452, mode::Standby>>> {
+        Mma8x5x::attach_with_address(i2c, address)
+    }
+}
+
+impl<E, I2C> Mma8x5x<I2C, ic::Mma8453, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8453 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub fn attach_mma8453(
+        i2c: I2C,
+        address: SlaveAddr,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5x<I2C, ic::Mma8453, mode::Standby>>> {
+        Mma8x5x::attach_with_address(i2c, address)
     }
 }
@@ -1,42 +1,48 @@
 //! MLX90614-specific functions
 
-use crate::{ic, mode, register_access::BitFlags, Config, Mma8x5x};
-use core::marker::PhantomData;
+use crate::{ic, mode, Mma8x5x, ModeChangeError};
+use embedded_hal::i2c::{I2c, SevenBitAddress};
 
 impl<I2C> Mma8x5x<I2C, ic::Mma8652, mode::Standby> {
     /// Create new instance of the MMA8652 device.
     pub fn new_mma8652(i2c: I2C) -> Self {
-        Mma8x5x {
-            i2c,
-            address: 0x1D,
-            xyz_data_cfg: Config::default(),
-            ctrl_reg1: Config::default(),
-            ctrl_reg2: Config::default(),
-            ctrl_reg3: Config::default(),
-            pl_cfg: Config {
-                bits: BitFlags::DBCNTM,
-            },
-            _ic: PhantomData,
-            _mode: PhantomData,
-        }
+        Mma8x5x::create(i2c, 0x1D)
     }
 }
 
 impl<I2C> Mma8x5x<I2C, ic::Mma8653, mode::Standby> {
     /// Create new instance of the MMA8653 device.
     pub fn new_mma8653(i2c: I2C) -> Self {
-        Mma8x5x {
-            i2c,
-            address: 0x1D,
-            xyz_data_cfg: Config::default(),
-            ctrl_reg1: Config::default(),
-            ctrl_reg2: Config::default(),
-            ctrl_reg3: Config::default(),
-            pl_cfg: Config {
-                bits: BitFlags::DBCNTM,
-            },
-            _ic: PhantomData,
-            _mode: PhantomData,
-        }
+        Mma8x5x::create(i2c, 0x1D)
+    }
+}
+
+impl<E, I2C> Mma8x5x<I2C, ic::Mma8652, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8652 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub fn attach_mma8652(
+        i2c: I2C,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5x<I2C, ic::Mma8652, mode::Standby>>> {
+        Mma8x5x::new_mma8652(i2c).attach()
+    }
+}
+
+impl<E, I2C> Mma8x5x<I2C, ic::Mma8653, mode::Active>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+{
+    /// Create new instance of an MMA8653 device that may already be in active mode.
+    ///
+    /// Reads the configuration from the device. See [`sync_from_device()`](#method.sync_from_device).
+    /// If the device is in standby mode, it is changed to active mode.
+    pub fn attach_mma8653(
+        i2c: I2C,
+    ) -> Result<Self, ModeChangeError<E, Mma8x5x<I2C, ic::Mma8653, mode::Standby>>> {
+        Mma8x5x::new_mma8653(i2c).attach()
     }
 }
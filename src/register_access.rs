@@ -5,13 +5,39 @@ pub struct Register {}
 impl Register {
     pub const STATUS: u8 = 0x00;
     pub const OUT_X_H: u8 = 0x01;
+    pub const OUT_X_L: u8 = 0x02;
+    pub const OUT_Y_H: u8 = 0x03;
+    pub const OUT_Y_L: u8 = 0x04;
+    pub const OUT_Z_H: u8 = 0x05;
+    pub const OUT_Z_L: u8 = 0x06;
+    pub const F_SETUP: u8 = 0x09;
+    pub const TRIG_CFG: u8 = 0x0A;
     pub const SYSMOD: u8 = 0x0B;
     pub const INT_SOURCE: u8 = 0x0C;
     pub const WHO_AM_I: u8 = 0x0D;
     pub const XYZ_DATA_CFG: u8 = 0x0E;
+    pub const HP_FILTER_CUTOFF: u8 = 0x0F;
     pub const PL_CFG: u8 = 0x11;
     pub const PL_COUNT: u8 = 0x12;
     pub const PL_STATUS: u8 = 0x10;
+    pub const PL_BF_ZCOMP: u8 = 0x13;
+    pub const P_L_THS_REG: u8 = 0x14;
+    pub const FF_MT_CFG: u8 = 0x15;
+    pub const FF_MT_SRC: u8 = 0x16;
+    pub const FF_MT_THS: u8 = 0x17;
+    pub const FF_MT_COUNT: u8 = 0x18;
+    pub const TRANSIENT_CFG: u8 = 0x1D;
+    pub const TRANSIENT_SRC: u8 = 0x1E;
+    pub const TRANSIENT_THS: u8 = 0x1F;
+    pub const TRANSIENT_COUNT: u8 = 0x20;
+    pub const PULSE_CFG: u8 = 0x21;
+    pub const PULSE_SRC: u8 = 0x22;
+    pub const PULSE_THSX: u8 = 0x23;
+    pub const PULSE_THSY: u8 = 0x24;
+    pub const PULSE_THSZ: u8 = 0x25;
+    pub const PULSE_TMLT: u8 = 0x26;
+    pub const PULSE_LTCY: u8 = 0x27;
+    pub const PULSE_WIND: u8 = 0x28;
     pub const ASLP_COUNT: u8 = 0x29;
     pub const CTRL_REG1: u8 = 0x2A;
     pub const CTRL_REG2: u8 = 0x2B;
@@ -19,15 +45,24 @@ impl Register {
     pub const CTRL_REG4: u8 = 0x2D;
     pub const CTRL_REG5: u8 = 0x2E;
     pub const OFF_X: u8 = 0x2F;
+    pub const OFF_Y: u8 = 0x30;
+    pub const OFF_Z: u8 = 0x31;
 }
 
 pub struct BitFlags;
 impl BitFlags {
     pub const FS0: u8 = 1;
     pub const FS1: u8 = 1 << 1;
+    pub const HPF_OUT: u8 = 1 << 4;
+
+    pub const SEL0: u8 = 1;
+    pub const SEL1: u8 = 1 << 1;
+    pub const PULSE_LPF_EN: u8 = 1 << 4;
+    pub const PULSE_HPF_BYP: u8 = 1 << 5;
 
     pub const ACTIVE: u8 = 1;
     pub const F_READ: u8 = 1 << 1;
+    pub const LNOISE: u8 = 1 << 2;
     pub const ASLP_RATE0: u8 = 1 << 6;
     pub const ASLP_RATE1: u8 = 1 << 7;
 
@@ -52,6 +87,17 @@ impl BitFlags {
     pub const ZOW: u8 = 1 << 6;
     pub const XYZOW: u8 = 1 << 7;
 
+    pub const F_WMRK_FLAG: u8 = 1 << 6;
+    pub const F_OVF: u8 = 1 << 7;
+
+    pub const F_MODE0: u8 = 1 << 6;
+    pub const F_MODE1: u8 = 1 << 7;
+
+    pub const TRIG_FF_MT: u8 = 1 << 2;
+    pub const TRIG_PULSE: u8 = 1 << 3;
+    pub const TRIG_LNDPRT: u8 = 1 << 4;
+    pub const TRIG_TRANS: u8 = 1 << 5;
+
     pub const PL_EN: u8 = 1 << 6;
     pub const DBCNTM: u8 = 1 << 7;
 
@@ -69,6 +115,56 @@ impl BitFlags {
     pub const SRC_FIFO: u8 = 1 << 6;
     pub const SRC_ASLP: u8 = 1 << 7;
 
+    pub const XEFE: u8 = 1 << 3;
+    pub const YEFE: u8 = 1 << 4;
+    pub const ZEFE: u8 = 1 << 5;
+    pub const OAE: u8 = 1 << 6;
+    pub const FF_MT_ELE: u8 = 1 << 7;
+
+    pub const XHP: u8 = 1;
+    pub const XHE: u8 = 1 << 1;
+    pub const YHP: u8 = 1 << 2;
+    pub const YHE: u8 = 1 << 3;
+    pub const ZHP: u8 = 1 << 4;
+    pub const ZHE: u8 = 1 << 5;
+    pub const FF_MT_EA: u8 = 1 << 7;
+
+    pub const FF_MT_DBCNTM: u8 = 1 << 7;
+
+    pub const HPF_BYP: u8 = 1;
+    pub const XTEFE: u8 = 1 << 1;
+    pub const YTEFE: u8 = 1 << 2;
+    pub const ZTEFE: u8 = 1 << 3;
+    pub const TRANS_ELE: u8 = 1 << 4;
+
+    pub const X_TRANS_POL: u8 = 1;
+    pub const XTRANSE: u8 = 1 << 1;
+    pub const Y_TRANS_POL: u8 = 1 << 2;
+    pub const YTRANSE: u8 = 1 << 3;
+    pub const Z_TRANS_POL: u8 = 1 << 4;
+    pub const ZTRANSE: u8 = 1 << 5;
+    pub const TRANS_EA: u8 = 1 << 6;
+
+    pub const TRANS_DBCNTM: u8 = 1 << 7;
+
+    pub const XSPEFE: u8 = 1;
+    pub const XDPEFE: u8 = 1 << 1;
+    pub const YSPEFE: u8 = 1 << 2;
+    pub const YDPEFE: u8 = 1 << 3;
+    pub const ZSPEFE: u8 = 1 << 4;
+    pub const ZDPEFE: u8 = 1 << 5;
+    pub const PULSE_ELE: u8 = 1 << 6;
+    pub const DPA: u8 = 1 << 7;
+
+    pub const POL_X: u8 = 1;
+    pub const POL_Y: u8 = 1 << 1;
+    pub const POL_Z: u8 = 1 << 2;
+    pub const DPE: u8 = 1 << 3;
+    pub const AX_X: u8 = 1 << 4;
+    pub const AX_Y: u8 = 1 << 5;
+    pub const AX_Z: u8 = 1 << 6;
+    pub const PULSE_EA: u8 = 1 << 7;
+
     pub const PP_OD: u8 = 1;
     pub const IPOL: u8 = 1 << 1;
 
@@ -92,6 +188,9 @@ impl BitFlags {
     pub const WAKE_PULSE: u8 = 1 << 4;
     pub const WAKE_LNDPRT: u8 = 1 << 5;
     pub const WAKE_TRANS: u8 = 1 << 6;
+    pub const FGATE: u8 = 1 << 7;
+
+    pub const FGERR: u8 = 1 << 7;
 }
 
 impl<E, I2C, IC, MODE> Mma8x5x<I2C, IC, MODE>
@@ -0,0 +1,865 @@
+//! Reading and decoding of the whole register map
+
+use core::fmt;
+
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+use crate::{
+    ic::private::Sealed,
+    register_access::{BitFlags as BF, Register},
+    DetectedIc, Error, Mma8x5x,
+};
+
+/// Number of registers read, from STATUS up to OFF_Z
+const REGISTER_COUNT: usize = Register::OFF_Z as usize + 1;
+
+/// Device models on which a register or field is available
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Models {
+    All,
+    /// MMA8451 and MMA8652
+    Fifo,
+    /// MMA8451, MMA8452 and MMA8453
+    Mma845x,
+    /// All but the MMA8653
+    NotMma8653,
+}
+
+impl Models {
+    fn contains(self, ic: DetectedIc) -> bool {
+        match self {
+            Models::All => true,
+            Models::Fifo => ic.has_fifo(),
+            Models::Mma845x => matches!(
+                ic,
+                DetectedIc::Mma8451 | DetectedIc::Mma8452 | DetectedIc::Mma8453
+            ),
+            Models::NotMma8653 => ic != DetectedIc::Mma8653,
+        }
+    }
+}
+
+/// Representation of a field value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Bit,
+    Number,
+    Signed,
+    Hex,
+    Names(&'static [&'static str]),
+}
+
+/// Register field
+///
+/// Fields without a name hold the value of the whole register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Field {
+    name: &'static str,
+    mask: u8,
+    format: Format,
+    models: Models,
+}
+
+impl Field {
+    const fn new(name: &'static str, mask: u8, format: Format) -> Self {
+        Field {
+            name,
+            mask,
+            format,
+            models: Models::All,
+        }
+    }
+
+    const fn bit(name: &'static str, mask: u8) -> Self {
+        Field::new(name, mask, Format::Bit)
+    }
+
+    const fn number(name: &'static str, mask: u8) -> Self {
+        Field::new(name, mask, Format::Number)
+    }
+
+    const fn names(name: &'static str, mask: u8, names: &'static [&'static str]) -> Self {
+        Field::new(name, mask, Format::Names(names))
+    }
+
+    const fn value(mask: u8, format: Format) -> Self {
+        Field::new("", mask, format)
+    }
+
+    const fn only(self, models: Models) -> Self {
+        Field { models, ..self }
+    }
+
+    fn get(&self, register: u8) -> u8 {
+        (register & self.mask) >> self.mask.trailing_zeros()
+    }
+
+    fn write_value(&self, f: &mut fmt::Formatter<'_>, register: u8) -> fmt::Result {
+        let value = self.get(register);
+        match self.format {
+            Format::Bit | Format::Number => write!(f, "{}", value),
+            Format::Signed => write!(f, "{}", value as i8),
+            Format::Hex => write!(f, "0x{:02X}", value),
+            Format::Names(names) => f.write_str(names[usize::from(value)]),
+        }
+    }
+}
+
+/// Name and fields of a register
+#[derive(Debug, PartialEq, Eq)]
+struct Layout {
+    address: u8,
+    name: &'static str,
+    models: Models,
+    fields: &'static [Field],
+}
+
+impl Layout {
+    const fn new(address: u8, name: &'static str, fields: &'static [Field]) -> Self {
+        Layout {
+            address,
+            name,
+            models: Models::All,
+            fields,
+        }
+    }
+
+    const fn only(self, models: Models) -> Self {
+        Layout { models, ..self }
+    }
+}
+
+const DATA: &[Field] = &[Field::value(0xFF, Format::Hex)];
+const NUMBER: &[Field] = &[Field::value(0xFF, Format::Number)];
+const OFFSET: &[Field] = &[Field::value(0xFF, Format::Signed)];
+const PULSE_THS: &[Field] = &[Field::value(0x7F, Format::Number)];
+const ODR: &[&str] = &[
+    "800Hz", "400Hz", "200Hz", "100Hz", "50Hz", "12.5Hz", "6.25Hz", "1.56Hz",
+];
+const ASLP_RATE: &[&str] = &["50Hz", "12.5Hz", "6.25Hz", "1.56Hz"];
+const POWER_MODE: &[&str] = &[
+    "normal",
+    "low-noise-low-power",
+    "high-resolution",
+    "low-power",
+];
+const DBCNTM: Field = Field::bit("DBCNTM", BF::FF_MT_DBCNTM);
+const THS: Field = Field::number("THS", 0x7F);
+
+/// STATUS register layout while the FIFO is enabled
+static F_STATUS: Layout = Layout::new(
+    Register::STATUS,
+    "F_STATUS",
+    &[
+        Field::bit("F_OVF", BF::F_OVF),
+        Field::bit("F_WMRK_FLAG", BF::F_WMRK_FLAG),
+        Field::number("F_CNT", 0x3F),
+    ],
+);
+
+static LAYOUTS: [Layout; 44] = [
+    Layout::new(
+        Register::STATUS,
+        "STATUS",
+        &[
+            Field::bit("XYZOW", BF::XYZOW),
+            Field::bit("ZOW", BF::ZOW),
+            Field::bit("YOW", BF::YOW),
+            Field::bit("XOW", BF::XOW),
+            Field::bit("XYZDR", BF::XYZDR),
+            Field::bit("ZDR", BF::ZDR),
+            Field::bit("YDR", BF::YDR),
+            Field::bit("XDR", BF::XDR),
+        ],
+    ),
+    Layout::new(Register::OUT_X_H, "OUT_X_MSB", DATA),
+    Layout::new(Register::OUT_X_L, "OUT_X_LSB", DATA),
+    Layout::new(Register::OUT_Y_H, "OUT_Y_MSB", DATA),
+    Layout::new(Register::OUT_Y_L, "OUT_Y_LSB", DATA),
+    Layout::new(Register::OUT_Z_H, "OUT_Z_MSB", DATA),
+    Layout::new(Register::OUT_Z_L, "OUT_Z_LSB", DATA),
+    Layout::new(
+        Register::F_SETUP,
+        "F_SETUP",
+        &[
+            Field::names(
+                "F_MODE",
+                BF::F_MODE1 | BF::F_MODE0,
+                &["disabled", "circular", "stop-on-overflow", "trigger"],
+            ),
+            Field::number("F_WMRK", 0x3F),
+        ],
+    )
+    .only(Models::Fifo),
+    Layout::new(
+        Register::TRIG_CFG,
+        "TRIG_CFG",
+        &[
+            Field::bit("TRIG_TRANS", BF::TRIG_TRANS),
+            Field::bit("TRIG_LNDPRT", BF::TRIG_LNDPRT),
+            Field::bit("TRIG_PULSE", BF::TRIG_PULSE),
+            Field::bit("TRIG_FF_MT", BF::TRIG_FF_MT),
+        ],
+    )
+    .only(Models::Fifo),
+    Layout::new(
+        Register::SYSMOD,
+        "SYSMOD",
+        &[
+            Field::bit("FGERR", BF::FGERR).only(Models::Fifo),
+            Field::number("FGT", 0x7C).only(Models::Fifo),
+            Field::names("SYSMOD", 0b11, &["standby", "wake", "sleep", "reserved"]),
+        ],
+    ),
+    Layout::new(
+        Register::INT_SOURCE,
+        "INT_SOURCE",
+        &[
+            Field::bit("SRC_ASLP", BF::SRC_ASLP),
+            Field::bit("SRC_FIFO", BF::SRC_FIFO).only(Models::Fifo),
+            Field::bit("SRC_TRANS", BF::SRC_TRANS).only(Models::NotMma8653),
+            Field::bit("SRC_LNDPRT", BF::SRC_LNDPRT),
+            Field::bit("SRC_PULSE", BF::SRC_PULSE).only(Models::NotMma8653),
+            Field::bit("SRC_FF_MT", BF::SRC_FF_MT),
+            Field::bit("SRC_DRDY", BF::SRC_DRDY),
+        ],
+    ),
+    Layout::new(Register::WHO_AM_I, "WHO_AM_I", DATA),
+    Layout::new(
+        Register::XYZ_DATA_CFG,
+        "XYZ_DATA_CFG",
+        &[
+            Field::bit("HPF_OUT", BF::HPF_OUT).only(Models::NotMma8653),
+            Field::names("FS", BF::FS1 | BF::FS0, &["2g", "4g", "8g", "reserved"]),
+        ],
+    ),
+    Layout::new(
+        Register::HP_FILTER_CUTOFF,
+        "HP_FILTER_CUTOFF",
+        &[
+            Field::bit("PULSE_HPF_BYP", BF::PULSE_HPF_BYP),
+            Field::bit("PULSE_LPF_EN", BF::PULSE_LPF_EN),
+            Field::number("SEL", BF::SEL1 | BF::SEL0),
+        ],
+    )
+    .only(Models::NotMma8653),
+    Layout::new(
+        Register::PL_STATUS,
+        "PL_STATUS",
+        &[
+            Field::bit("NEWLP", BF::NEWLP),
+            Field::bit("LO", BF::LO),
+            Field::names(
+                "LAPO",
+                BF::LAPO1 | BF::LAPO0,
+                &[
+                    "portrait-up",
+                    "portrait-down",
+                    "landscape-right",
+                    "landscape-left",
+                ],
+            ),
+            Field::names("BAFRO", BF::BAFRO, &["front", "back"]),
+        ],
+    ),
+    Layout::new(
+        Register::PL_CFG,
+        "PL_CFG",
+        &[
+            Field::bit("DBCNTM", BF::DBCNTM),
+            Field::bit("PL_EN", BF::PL_EN),
+        ],
+    ),
+    Layout::new(Register::PL_COUNT, "PL_COUNT", NUMBER),
+    Layout::new(
+        Register::PL_BF_ZCOMP,
+        "PL_BF_ZCOMP",
+        &[Field::number("BKFR", 0xC0), Field::number("ZLOCK", 0x07)],
+    ),
+    Layout::new(
+        Register::P_L_THS_REG,
+        "P_L_THS_REG",
+        &[Field::number("P_L_THS", 0xF8), Field::number("HYS", 0x07)],
+    ),
+    Layout::new(
+        Register::FF_MT_CFG,
+        "FF_MT_CFG",
+        &[
+            Field::bit("ELE", BF::FF_MT_ELE),
+            Field::bit("OAE", BF::OAE),
+            Field::bit("ZEFE", BF::ZEFE),
+            Field::bit("YEFE", BF::YEFE),
+            Field::bit("XEFE", BF::XEFE),
+        ],
+    ),
+    Layout::new(
+        Register::FF_MT_SRC,
+        "FF_MT_SRC",
+        &[
+            Field::bit("EA", BF::FF_MT_EA),
+            Field::bit("ZHE", BF::ZHE),
+            Field::bit("ZHP", BF::ZHP),
+            Field::bit("YHE", BF::YHE),
+            Field::bit("YHP", BF::YHP),
+            Field::bit("XHE", BF::XHE),
+            Field::bit("XHP", BF::XHP),
+        ],
+    ),
+    Layout::new(Register::FF_MT_THS, "FF_MT_THS", &[DBCNTM, THS]),
+    Layout::new(Register::FF_MT_COUNT, "FF_MT_COUNT", NUMBER),
+    Layout::new(
+        Register::TRANSIENT_CFG,
+        "TRANSIENT_CFG",
+        &[
+            Field::bit("ELE", BF::TRANS_ELE),
+            Field::bit("ZTEFE", BF::ZTEFE),
+            Field::bit("YTEFE", BF::YTEFE),
+            Field::bit("XTEFE", BF::XTEFE),
+            Field::bit("HPF_BYP", BF::HPF_BYP),
+        ],
+    )
+    .only(Models::NotMma8653),
+    Layout::new(
+        Register::TRANSIENT_SRC,
+        "TRANSIENT_SRC",
+        &[
+            Field::bit("EA", BF::TRANS_EA),
+            Field::bit("ZTRANSE", BF::ZTRANSE),
+            Field::bit("Z_TRANS_POL", BF::Z_TRANS_POL),
+            Field::bit("YTRANSE", BF::YTRANSE),
+            Field::bit("Y_TRANS_POL", BF::Y_TRANS_POL),
+            Field::bit("XTRANSE", BF::XTRANSE),
+            Field::bit("X_TRANS_POL", BF::X_TRANS_POL),
+        ],
+    )
+    .only(Models::NotMma8653),
+    Layout::new(Register::TRANSIENT_THS, "TRANSIENT_THS", &[DBCNTM, THS]).only(Models::NotMma8653),
+    Layout::new(Register::TRANSIENT_COUNT, "TRANSIENT_COUNT", NUMBER).only(Models::NotMma8653),
+    Layout::new(
+        Register::PULSE_CFG,
+        "PULSE_CFG",
+        &[
+            Field::bit("DPA", BF::DPA),
+            Field::bit("ELE", BF::PULSE_ELE),
+            Field::bit("ZDPEFE", BF::ZDPEFE),
+            Field::bit("ZSPEFE", BF::ZSPEFE),
+            Field::bit("YDPEFE", BF::YDPEFE),
+            Field::bit("YSPEFE", BF::YSPEFE),
+            Field::bit("XDPEFE", BF::XDPEFE),
+            Field::bit("XSPEFE", BF::XSPEFE),
+        ],
+    )
+    .only(Models::NotMma8653),
+    Layout::new(
+        Register::PULSE_SRC,
+        "PULSE_SRC",
+        &[
+            Field::bit("EA", BF::PULSE_EA),
+            Field::bit("AX_Z", BF::AX_Z),
+            Field::bit("AX_Y", BF::AX_Y),
+            Field::bit("AX_X", BF::AX_X),
+            Field::bit("DPE", BF::DPE),
+            Field::bit("POL_Z", BF::POL_Z),
+            Field::bit("POL_Y", BF::POL_Y),
+            Field::bit("POL_X", BF::POL_X),
+        ],
+    )
+    .only(Models::NotMma8653),
+    Layout::new(Register::PULSE_THSX, "PULSE_THSX", PULSE_THS).only(Models::NotMma8653),
+    Layout::new(Register::PULSE_THSY, "PULSE_THSY", PULSE_THS).only(Models::NotMma8653),
+    Layout::new(Register::PULSE_THSZ, "PULSE_THSZ", PULSE_THS).only(Models::NotMma8653),
+    Layout::new(Register::PULSE_TMLT, "PULSE_TMLT", NUMBER).only(Models::NotMma8653),
+    Layout::new(Register::PULSE_LTCY, "PULSE_LTCY", NUMBER).only(Models::NotMma8653),
+    Layout::new(Register::PULSE_WIND, "PULSE_WIND", NUMBER).only(Models::NotMma8653),
+    Layout::new(Register::ASLP_COUNT, "ASLP_COUNT", NUMBER),
+    Layout::new(
+        Register::CTRL_REG1,
+        "CTRL_REG1",
+        &[
+            Field::names("ODR", BF::ODR2 | BF::ODR1 | BF::ODR0, ODR),
+            Field::names("ASLP_RATE", BF::ASLP_RATE1 | BF::ASLP_RATE0, ASLP_RATE),
+            Field::bit("LNOISE", BF::LNOISE).only(Models::Mma845x),
+            Field::bit("F_READ", BF::F_READ),
+            Field::bit("ACTIVE", BF::ACTIVE),
+        ],
+    ),
+    Layout::new(
+        Register::CTRL_REG2,
+        "CTRL_REG2",
+        &[
+            Field::bit("ST", BF::ST),
+            Field::bit("RST", BF::RST),
+            Field::names("SMODS", BF::SMODS1 | BF::SMODS0, POWER_MODE),
+            Field::bit("SLPE", BF::SLPE),
+            Field::names("MODS", BF::MODS1 | BF::MODS0, POWER_MODE),
+        ],
+    ),
+    Layout::new(
+        Register::CTRL_REG3,
+        "CTRL_REG3",
+        &[
+            Field::bit("FGATE", BF::FGATE).only(Models::Fifo),
+            Field::bit("WAKE_TRANS", BF::WAKE_TRANS).only(Models::NotMma8653),
+            Field::bit("WAKE_LNDPRT", BF::WAKE_LNDPRT),
+            Field::bit("WAKE_PULSE", BF::WAKE_PULSE).only(Models::NotMma8653),
+            Field::bit("WAKE_FF_MT", BF::WAKE_FF_MT),
+            Field::bit("IPOL", BF::IPOL),
+            Field::bit("PP_OD", BF::PP_OD),
+        ],
+    ),
+    Layout::new(
+        Register::CTRL_REG4,
+        "CTRL_REG4",
+        &[
+            Field::bit("INT_EN_ASLP", BF::INT_EN_ASLP),
+            Field::bit("INT_EN_FIFO", BF::INT_EN_FIFO).only(Models::Fifo),
+            Field::bit("INT_EN_TRANS", BF::INT_EN_TRANS).only(Models::NotMma8653),
+            Field::bit("INT_EN_LNDPRT", BF::INT_EN_LNDPRT),
+            Field::bit("INT_EN_PULSE", BF::INT_EN_PULSE).only(Models::NotMma8653),
+            Field::bit("INT_EN_FF_MT", BF::INT_EN_FF_MT),
+            Field::bit("INT_EN_DRDY", BF::INT_EN_DRDY),
+        ],
+    ),
+    Layout::new(
+        Register::CTRL_REG5,
+        "CTRL_REG5",
+        &[
+            Field::bit("INT_CFG_ASLP", BF::INT_CFG_ASLP),
+            Field::bit("INT_CFG_FIFO", BF::INT_CFG_FIFO).only(Models::Fifo),
+            Field::bit("INT_CFG_TRANS", BF::INT_CFG_TRANS).only(Models::NotMma8653),
+            Field::bit("INT_CFG_LNDPRT", BF::INT_CFG_LNDPRT),
+            Field::bit("INT_CFG_PULSE", BF::INT_CFG_PULSE).only(Models::NotMma8653),
+            Field::bit("INT_CFG_FF_MT", BF::INT_CFG_FF_MT),
+            Field::bit("INT_CFG_DRDY", BF::INT_CFG_DRDY),
+        ],
+    ),
+    Layout::new(Register::OFF_X, "OFF_X", OFFSET),
+    Layout::new(Register::OFF_Y, "OFF_Y", OFFSET),
+    Layout::new(Register::OFF_Z, "OFF_Z", OFFSET),
+];
+
+/// Content of all the registers of a device
+///
+/// See [`dump_registers()`](struct.Mma8x5x.html#method.dump_registers).
+/// The `Display` implementation decodes the fields of each register available
+/// on the device model, one register per line. For example:
+/// `CTRL_REG1: ODR=100Hz ASLP_RATE=50Hz F_READ=0 ACTIVE=1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterDump {
+    ic: DetectedIc,
+    registers: [u8; REGISTER_COUNT],
+}
+
+impl RegisterDump {
+    pub(crate) fn new(ic: DetectedIc) -> Self {
+        RegisterDump {
+            ic,
+            registers: [0; REGISTER_COUNT],
+        }
+    }
+
+    /// Device model
+    pub fn ic(&self) -> DetectedIc {
+        self.ic
+    }
+
+    /// Value of a register
+    ///
+    /// Returns `None` if the register is not available on the device model.
+    pub fn register(&self, address: u8) -> Option<u8> {
+        self.get_layout(address)
+            .map(|_| self.registers[usize::from(address)])
+    }
+
+    /// Compare with another dump of the same device model.
+    ///
+    /// Returns the registers whose value is different in `other`.
+    pub fn diff<'a>(&'a self, other: &'a RegisterDump) -> RegisterDiff<'a> {
+        RegisterDiff {
+            old: self,
+            new: other,
+            index: 0,
+        }
+    }
+
+    /// Buffer for the burst read starting at F_SETUP
+    pub(crate) fn get_config_buffer(&mut self) -> &mut [u8] {
+        &mut self.registers[usize::from(Register::F_SETUP)..]
+    }
+
+    /// Buffer for the burst read starting at STATUS
+    ///
+    /// The read mode is taken from the configuration read before. In fast-read
+    /// mode, only the STATUS and MSB output data registers are read.
+    /// See [`expand_fast_read_data()`](Self::expand_fast_read_data).
+    pub(crate) fn get_data_buffer(&mut self) -> &mut [u8] {
+        let len = if self.is_fast_read() { 4 } else { 7 };
+        &mut self.registers[..len]
+    }
+
+    /// Whether fast-read mode is set in the configuration read
+    pub(crate) fn is_fast_read(&self) -> bool {
+        (self.registers[usize::from(Register::CTRL_REG1)] & BF::F_READ) != 0
+    }
+
+    /// Move the MSB output data read in fast-read mode to their registers.
+    pub(crate) fn expand_fast_read_data(&mut self) {
+        let [status, x, y, z] = [0, 1, 2, 3].map(|i| self.registers[i]);
+        self.registers[..7].copy_from_slice(&[status, x, 0, y, 0, z, 0]);
+    }
+
+    fn is_fifo_enabled(&self) -> bool {
+        let f_setup = self.registers[usize::from(Register::F_SETUP)];
+        Models::Fifo.contains(self.ic) && (f_setup & (BF::F_MODE1 | BF::F_MODE0)) != 0
+    }
+
+    fn get_layout(&self, address: u8) -> Option<&'static Layout> {
+        let layout = LAYOUTS
+            .iter()
+            .find(|layout| layout.address == address && layout.models.contains(self.ic))?;
+        if address == Register::STATUS && self.is_fifo_enabled() {
+            Some(&F_STATUS)
+        } else {
+            Some(layout)
+        }
+    }
+
+    fn get_fields(&self, layout: &'static Layout) -> impl Iterator<Item = &'static Field> {
+        let ic = self.ic;
+        layout
+            .fields
+            .iter()
+            .filter(move |field| field.models.contains(ic))
+    }
+}
+
+impl fmt::Display for RegisterDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let layouts = LAYOUTS
+            .iter()
+            .filter_map(|layout| self.get_layout(layout.address));
+        for (i, layout) in layouts.enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            let value = self.registers[usize::from(layout.address)];
+            write!(f, "{}:", layout.name)?;
+            for field in self.get_fields(layout) {
+                f.write_str(" ")?;
+                if !field.name.is_empty() {
+                    write!(f, "{}=", field.name)?;
+                }
+                field.write_value(f, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the registers which differ between two dumps
+///
+/// See [`RegisterDump::diff()`]. The `Display` implementation shows the
+/// changed fields of each register, one register per line. For example:
+/// `CTRL_REG1: ODR=100Hz->50Hz ACTIVE=0->1`.
+#[derive(Debug, Clone)]
+pub struct RegisterDiff<'a> {
+    old: &'a RegisterDump,
+    new: &'a RegisterDump,
+    index: usize,
+}
+
+impl Iterator for RegisterDiff<'_> {
+    type Item = RegisterChange;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(layout) = LAYOUTS.get(self.index) {
+            self.index += 1;
+            let address = layout.address;
+            let change = self
+                .old
+                .get_layout(address)
+                .zip(self.old.register(address))
+                .zip(self.new.register(address));
+            match change {
+                Some(((layout, old), new)) if old != new => {
+                    return Some(RegisterChange {
+                        address,
+                        name: layout.name,
+                        old,
+                        new,
+                        ic: self.old.ic,
+                        layout,
+                    })
+                }
+                _ => (),
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for RegisterDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, change) in self.clone().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Register whose value differs between two dumps
+///
+/// The `Display` implementation shows the changed fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterChange {
+    /// Register address
+    pub address: u8,
+    /// Register name
+    pub name: &'static str,
+    /// Value in the dump on which [`RegisterDump::diff()`] was called
+    pub old: u8,
+    /// Value in the other dump
+    pub new: u8,
+    ic: DetectedIc,
+    layout: &'static Layout,
+}
+
+impl fmt::Display for RegisterChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.name)?;
+        let fields = self.layout.fields.iter().filter(|field| {
+            field.models.contains(self.ic) && field.get(self.old) != field.get(self.new)
+        });
+        for field in fields {
+            f.write_str(" ")?;
+            if !field.name.is_empty() {
+                write!(f, "{}=", field.name)?;
+            }
+            field.write_value(f, self.old)?;
+            f.write_str("->")?;
+            field.write_value(f, self.new)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E, I2C, IC, MODE> Mma8x5x<I2C, IC, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    IC: Sealed,
+{
+    /// Read all the registers of the device.
+    ///
+    /// The configuration and the output data registers are read in
+    /// two bursts. The output data is read according to the read mode
+    /// set in the device. Reading the status and source registers clears
+    /// them as usual and, if the FIFO is enabled, the sample read
+    /// is removed from the FIFO. In fast-read mode, the LSB output
+    /// data registers are not read and contain 0.
+    pub fn dump_registers(&mut self) -> Result<RegisterDump, Error<E>> {
+        let mut dump = RegisterDump::new(IC::MODEL);
+        self.i2c
+            .write_read(self.address, &[Register::F_SETUP], dump.get_config_buffer())
+            .map_err(Error::I2C)?;
+        self.i2c
+            .write_read(self.address, &[Register::STATUS], dump.get_data_buffer())
+            .map_err(Error::I2C)?;
+        if dump.is_fast_read() {
+            dump.expand_fast_read_data();
+        }
+        Ok(dump)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use super::*;
+    use std::{format, string::String, string::ToString, vec, vec::Vec};
+
+    fn dump(ic: DetectedIc, values: &[(u8, u8)]) -> RegisterDump {
+        let mut dump = RegisterDump::new(ic);
+        for &(address, value) in values {
+            dump.registers[usize::from(address)] = value;
+        }
+        dump
+    }
+
+    fn find_line(dump: &RegisterDump, name: &str) -> Option<String> {
+        let text = dump.to_string();
+        let prefix = format!("{}:", name);
+        text.lines()
+            .find(|line| line.starts_with(&prefix))
+            .map(String::from)
+    }
+
+    #[test]
+    fn layouts_are_sorted_and_masks_fit_names() {
+        for pair in LAYOUTS.windows(2) {
+            assert!(pair[0].address < pair[1].address);
+        }
+        assert_eq!(Register::OFF_Z, LAYOUTS[LAYOUTS.len() - 1].address);
+        for field in LAYOUTS.iter().chain([&F_STATUS]).flat_map(|l| l.fields) {
+            if let Format::Names(names) = field.format {
+                assert_eq!(names.len(), usize::from(field.get(0xFF)) + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_ctrl_reg1() {
+        let dump = dump(DetectedIc::Mma8652, &[(Register::CTRL_REG1, 0x19)]);
+        assert_eq!(
+            Some("CTRL_REG1: ODR=100Hz ASLP_RATE=50Hz F_READ=0 ACTIVE=1".into()),
+            find_line(&dump, "CTRL_REG1")
+        );
+    }
+
+    #[test]
+    fn decodes_low_noise_only_on_mma845x() {
+        let dump = dump(DetectedIc::Mma8451, &[(Register::CTRL_REG1, 0xFE)]);
+        assert_eq!(
+            Some("CTRL_REG1: ODR=1.56Hz ASLP_RATE=1.56Hz LNOISE=1 F_READ=1 ACTIVE=0".into()),
+            find_line(&dump, "CTRL_REG1")
+        );
+    }
+
+    #[test]
+    fn decodes_fifo_gate_only_on_fifo_devices() {
+        let dump_8451 = dump(DetectedIc::Mma8451, &[(Register::CTRL_REG3, 0x80)]);
+        assert_eq!(
+            Some(
+                "CTRL_REG3: FGATE=1 WAKE_TRANS=0 WAKE_LNDPRT=0 WAKE_PULSE=0 WAKE_FF_MT=0 IPOL=0 PP_OD=0"
+                    .into()
+            ),
+            find_line(&dump_8451, "CTRL_REG3")
+        );
+        let dump_8452 = dump(DetectedIc::Mma8452, &[(Register::CTRL_REG3, 0x80)]);
+        assert_eq!(
+            Some(
+                "CTRL_REG3: WAKE_TRANS=0 WAKE_LNDPRT=0 WAKE_PULSE=0 WAKE_FF_MT=0 IPOL=0 PP_OD=0"
+                    .into()
+            ),
+            find_line(&dump_8452, "CTRL_REG3")
+        );
+    }
+
+    #[test]
+    fn decodes_whole_register_values() {
+        let dump = dump(
+            DetectedIc::Mma8451,
+            &[
+                (Register::WHO_AM_I, 0x1A),
+                (Register::ASLP_COUNT, 200),
+                (Register::OFF_Z, 0xFE),
+                (Register::PULSE_THSX, 0x85),
+            ],
+        );
+        assert_eq!(Some("WHO_AM_I: 0x1A".into()), find_line(&dump, "WHO_AM_I"));
+        assert_eq!(
+            Some("ASLP_COUNT: 200".into()),
+            find_line(&dump, "ASLP_COUNT")
+        );
+        assert_eq!(Some("OFF_Z: -2".into()), find_line(&dump, "OFF_Z"));
+        assert_eq!(Some("PULSE_THSX: 5".into()), find_line(&dump, "PULSE_THSX"));
+    }
+
+    #[test]
+    fn decodes_fifo_status_if_fifo_enabled() {
+        let values = [(Register::STATUS, 0x8A), (Register::F_SETUP, 0x40)];
+        let dump = dump(DetectedIc::Mma8451, &values);
+        assert_eq!(
+            Some("F_STATUS: F_OVF=1 F_WMRK_FLAG=0 F_CNT=10".into()),
+            find_line(&dump, "F_STATUS")
+        );
+        assert_eq!(None, find_line(&dump, "STATUS"));
+        assert_eq!(
+            Some("F_SETUP: F_MODE=circular F_WMRK=0".into()),
+            find_line(&dump, "F_SETUP")
+        );
+    }
+
+    #[test]
+    fn skips_registers_and_fields_not_available() {
+        let dump = dump(DetectedIc::Mma8653, &[(Register::F_SETUP, 0x40)]);
+        assert_eq!(None, dump.register(Register::F_SETUP));
+        assert_eq!(None, dump.register(Register::TRANSIENT_CFG));
+        assert_eq!(None, dump.register(0x07));
+        assert_eq!(None, dump.register(0x32));
+        assert_eq!(Some(0), dump.register(Register::STATUS));
+        assert_eq!(None, find_line(&dump, "F_SETUP"));
+        assert_eq!(None, find_line(&dump, "PULSE_CFG"));
+        assert_eq!(
+            Some("INT_SOURCE: SRC_ASLP=0 SRC_LNDPRT=0 SRC_FF_MT=0 SRC_DRDY=0".into()),
+            find_line(&dump, "INT_SOURCE")
+        );
+        assert_eq!(
+            Some("SYSMOD: SYSMOD=standby".into()),
+            find_line(&dump, "SYSMOD")
+        );
+        assert_eq!(29, dump.to_string().lines().count());
+        assert_eq!(
+            44,
+            RegisterDump::new(DetectedIc::Mma8451)
+                .to_string()
+                .lines()
+                .count()
+        );
+    }
+
+    #[test]
+    fn expands_fast_read_data() {
+        let mut dump = dump(DetectedIc::Mma8452, &[(0, 0x0F), (1, 1), (2, 2), (3, 3)]);
+        dump.expand_fast_read_data();
+        assert_eq!([0x0F, 1, 0, 2, 0, 3, 0], dump.registers[..7]);
+    }
+
+    #[test]
+    fn same_dumps_do_not_differ() {
+        let dump = dump(DetectedIc::Mma8451, &[(Register::CTRL_REG1, 0xFE)]);
+        assert_eq!(0, dump.diff(&dump.clone()).count());
+        assert_eq!("", dump.diff(&dump).to_string());
+    }
+
+    #[test]
+    fn diff_shows_changed_fields() {
+        let old = dump(
+            DetectedIc::Mma8652,
+            &[(Register::CTRL_REG1, 0x18), (Register::OFF_X, 1)],
+        );
+        let new = dump(
+            DetectedIc::Mma8652,
+            &[
+                (Register::CTRL_REG1, 0x21),
+                (Register::OFF_X, 0xFF),
+                (Register::PL_CFG, BF::PL_EN),
+            ],
+        );
+        let changes: Vec<_> = old
+            .diff(&new)
+            .map(|change| (change.address, change.name, change.old, change.new))
+            .collect();
+        assert_eq!(
+            vec![
+                (Register::PL_CFG, "PL_CFG", 0, BF::PL_EN),
+                (Register::CTRL_REG1, "CTRL_REG1", 0x18, 0x21),
+                (Register::OFF_X, "OFF_X", 1, 0xFF),
+            ],
+            changes
+        );
+        assert_eq!(
+            "PL_CFG: PL_EN=0->1\nCTRL_REG1: ODR=100Hz->50Hz ACTIVE=0->1\nOFF_X: 1->-1",
+            old.diff(&new).to_string()
+        );
+    }
+
+    #[test]
+    fn diff_ignores_registers_not_available() {
+        let old = dump(DetectedIc::Mma8653, &[]);
+        let new = dump(DetectedIc::Mma8653, &[(Register::PULSE_CFG, 1)]);
+        assert_eq!(0, old.diff(&new).count());
+    }
+}
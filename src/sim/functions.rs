@@ -0,0 +1,814 @@
+//! Embedded functions of the simulated device
+//!
+//! Each function processes one sample at a time, updates its source register
+//! and returns the interrupt source flag if an event occurred.
+
+use super::Registers;
+use crate::register_access::{BitFlags, Register};
+
+/// Threshold resolution of the freefall/motion, transient and pulse detection in G/LSB
+const THRESHOLD_RESOLUTION: f32 = 0.063;
+
+/// Tangent of the Z-lockout angles 14°, 18°, 21°, 25°, 29°, 33°, 37° and 42°
+const Z_LOCKOUT_TAN: [f32; 8] = [
+    0.2493, 0.3249, 0.3839, 0.4663, 0.5543, 0.6494, 0.7536, 0.9004,
+];
+/// Portrait/landscape hysteresis angles in degrees
+const PL_HYSTERESIS: [f32; 8] = [0.0, 4.0, 7.0, 11.0, 14.0, 17.0, 21.0, 24.0];
+
+fn reg(registers: &Registers, register: u8) -> u8 {
+    registers[usize::from(register)]
+}
+
+fn set_reg(registers: &mut Registers, register: u8, value: u8) {
+    registers[usize::from(register)] = value;
+}
+
+fn get_threshold(ths: u8) -> f32 {
+    f32::from(ths & 0x7F) * THRESHOLD_RESOLUTION
+}
+
+/// Debounce counter of an event condition
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Debounce {
+    count: u16,
+}
+
+impl Debounce {
+    /// Update the counter with the condition at the current sample and
+    /// return whether it has been met for `limit` counter steps.
+    ///
+    /// `steps` is the number of counter steps in a sample period.
+    /// In clear mode the counter is cleared as soon as the condition is not
+    /// met anymore, otherwise it is decremented.
+    pub(crate) fn update(&mut self, condition: bool, limit: u8, clear: bool, steps: u16) -> bool {
+        let limit = u16::from(limit);
+        if condition {
+            self.count = self.count.saturating_add(steps).min(limit.max(1));
+            self.count >= limit
+        } else {
+            self.count = if clear {
+                0
+            } else {
+                self.count.saturating_sub(steps)
+            };
+            false
+        }
+    }
+}
+
+/// First order high-pass filter
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct HighPassFilter {
+    baseline: Option<[f32; 3]>,
+}
+
+impl HighPassFilter {
+    /// Filter a sample. The filter starts settled on the first sample.
+    pub(crate) fn update(&mut self, acceleration: [f32; 3], alpha: f32) -> [f32; 3] {
+        let baseline = self.baseline.get_or_insert(acceleration);
+        let mut output = [0.0; 3];
+        for i in 0..3 {
+            baseline[i] += alpha * (acceleration[i] - baseline[i]);
+            output[i] = acceleration[i] - baseline[i];
+        }
+        output
+    }
+}
+
+/// Return the high-pass filter coefficient for the cutoff selection and data rate.
+pub(crate) fn get_high_pass_alpha(hp_filter_cutoff: u8, data_rate_hz: f32) -> f32 {
+    let base_cutoff = if data_rate_hz >= 400.0 {
+        16.0
+    } else if data_rate_hz >= 200.0 {
+        8.0
+    } else if data_rate_hz >= 100.0 {
+        4.0
+    } else {
+        2.0
+    };
+    let sel = hp_filter_cutoff & (BitFlags::SEL1 | BitFlags::SEL0);
+    let cutoff = base_cutoff / f32::from(1_u8 << sel);
+    (2.0 * core::f32::consts::PI * cutoff / data_rate_hz).min(1.0)
+}
+
+/// Update a source register as configured by the event latch enable.
+///
+/// Returns whether a new event was signalled.
+fn update_source(
+    registers: &mut Registers,
+    src: u8,
+    ea: u8,
+    latch: bool,
+    event: Option<u8>,
+) -> bool {
+    if latch {
+        if (reg(registers, src) & ea) != 0 {
+            return false;
+        }
+        if let Some(flags) = event {
+            set_reg(registers, src, ea | flags);
+            return true;
+        }
+        false
+    } else {
+        set_reg(registers, src, event.map_or(0, |flags| ea | flags));
+        event.is_some()
+    }
+}
+
+/// Freefall/motion detection
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct FreefallMotion {
+    debounce: Debounce,
+}
+
+impl FreefallMotion {
+    pub(crate) fn update(
+        &mut self,
+        registers: &mut Registers,
+        acceleration: [f32; 3],
+        counter_steps: u16,
+    ) -> u8 {
+        let cfg = reg(registers, Register::FF_MT_CFG);
+        let ths = reg(registers, Register::FF_MT_THS);
+        let threshold = get_threshold(ths);
+        let enabled = [
+            (cfg & BitFlags::XEFE) != 0,
+            (cfg & BitFlags::YEFE) != 0,
+            (cfg & BitFlags::ZEFE) != 0,
+        ];
+        if !enabled.contains(&true) {
+            return 0;
+        }
+        let mut flags = 0;
+        let condition = if (cfg & BitFlags::OAE) != 0 {
+            for i in (0..3).filter(|&i| enabled[i] && acceleration[i].abs() > threshold) {
+                // XHE/YHE/ZHE with the polarity flags XHP/YHP/ZHP below
+                flags |= BitFlags::XHE << (2 * i);
+                if acceleration[i] < 0.0 {
+                    flags |= BitFlags::XHP << (2 * i);
+                }
+            }
+            flags != 0
+        } else {
+            (0..3)
+                .filter(|&i| enabled[i])
+                .all(|i| acceleration[i].abs() < threshold)
+        };
+        let limit = reg(registers, Register::FF_MT_COUNT);
+        let clear = (ths & BitFlags::FF_MT_DBCNTM) != 0;
+        let event = self
+            .debounce
+            .update(condition, limit, clear, counter_steps)
+            .then_some(flags);
+        let latch = (cfg & BitFlags::FF_MT_ELE) != 0;
+        if update_source(
+            registers,
+            Register::FF_MT_SRC,
+            BitFlags::FF_MT_EA,
+            latch,
+            event,
+        ) {
+            BitFlags::SRC_FF_MT
+        } else {
+            0
+        }
+    }
+}
+
+/// Transient detection
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Transient {
+    debounce: Debounce,
+}
+
+impl Transient {
+    pub(crate) fn update(
+        &mut self,
+        registers: &mut Registers,
+        acceleration: [f32; 3],
+        filtered: [f32; 3],
+        counter_steps: u16,
+    ) -> u8 {
+        let cfg = reg(registers, Register::TRANSIENT_CFG);
+        let ths = reg(registers, Register::TRANSIENT_THS);
+        let threshold = get_threshold(ths);
+        let input = if (cfg & BitFlags::HPF_BYP) != 0 {
+            acceleration
+        } else {
+            filtered
+        };
+        let mut flags = 0;
+        for (i, value) in input.into_iter().enumerate() {
+            // XTEFE/YTEFE/ZTEFE
+            let enabled = (cfg & (BitFlags::XTEFE << i)) != 0;
+            if enabled && value.abs() > threshold {
+                flags |= BitFlags::XTRANSE << (2 * i);
+                if value < 0.0 {
+                    flags |= BitFlags::X_TRANS_POL << (2 * i);
+                }
+            }
+        }
+        let limit = reg(registers, Register::TRANSIENT_COUNT);
+        let clear = (ths & BitFlags::TRANS_DBCNTM) != 0;
+        let event = self
+            .debounce
+            .update(flags != 0, limit, clear, counter_steps)
+            .then_some(flags);
+        let latch = (cfg & BitFlags::TRANS_ELE) != 0;
+        if update_source(
+            registers,
+            Register::TRANSIENT_SRC,
+            BitFlags::TRANS_EA,
+            latch,
+            event,
+        ) {
+            BitFlags::SRC_TRANS
+        } else {
+            0
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum PulseState {
+    #[default]
+    Idle,
+    /// First pulse above the threshold with its duration and axis flags
+    First { duration: u16, flags: u8 },
+    /// Waiting for the latency time to finish after a single pulse
+    Latency { remaining: u16 },
+    /// Waiting for a second pulse within the time window
+    Window { remaining: u16 },
+    /// Second pulse above the threshold with its duration and axis flags
+    Second { duration: u16, flags: u8 },
+    /// Waiting until the acceleration is below the threshold again
+    WaitForRelease,
+}
+
+/// Single/double pulse (tap) detection
+///
+/// Times are counted in steps of half a sample period, or a sample period
+/// if the pulse low-pass filter is enabled. The pulse time limit is counted
+/// in single steps and the latency and window in double steps.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Pulse {
+    state: PulseState,
+}
+
+impl Pulse {
+    pub(crate) fn update(
+        &mut self,
+        registers: &mut Registers,
+        acceleration: [f32; 3],
+        filtered: [f32; 3],
+        step: u16,
+    ) -> u8 {
+        let cfg = reg(registers, Register::PULSE_CFG);
+        let hp_filter_cutoff = reg(registers, Register::HP_FILTER_CUTOFF);
+        let input = if (hp_filter_cutoff & BitFlags::PULSE_HPF_BYP) != 0 {
+            acceleration
+        } else {
+            filtered
+        };
+        let single_enabled = cfg & (BitFlags::XSPEFE | BitFlags::YSPEFE | BitFlags::ZSPEFE);
+        let double_enabled = cfg & (BitFlags::XDPEFE | BitFlags::YDPEFE | BitFlags::ZDPEFE);
+        let mut flags = 0;
+        for (i, value) in input.into_iter().enumerate() {
+            let enabled = ((single_enabled | double_enabled) & (0b11 << (2 * i))) != 0;
+            let threshold = get_threshold(reg(registers, Register::PULSE_THSX + i as u8));
+            if enabled && value.abs() > threshold {
+                flags |= BitFlags::AX_X << i;
+                if value < 0.0 {
+                    flags |= BitFlags::POL_X << i;
+                }
+            }
+        }
+        let above = flags != 0;
+        let time_limit = u16::from(reg(registers, Register::PULSE_TMLT));
+        let latency = 2 * u16::from(reg(registers, Register::PULSE_LTCY));
+        let window = 2 * u16::from(reg(registers, Register::PULSE_WIND));
+        let double_abort = (cfg & BitFlags::DPA) != 0;
+        let mut event = None;
+        self.state = match self.state {
+            PulseState::Idle if above => PulseState::First {
+                duration: step,
+                flags,
+            },
+            PulseState::Idle => PulseState::Idle,
+            PulseState::First { duration, flags } if above => {
+                if duration + step > time_limit {
+                    PulseState::WaitForRelease
+                } else {
+                    PulseState::First {
+                        duration: duration + step,
+                        flags,
+                    }
+                }
+            }
+            PulseState::First { flags, .. } => {
+                if (single_enabled & get_enable_mask(flags)) != 0 {
+                    event = Some(flags);
+                }
+                if (double_enabled & (get_enable_mask(flags) << 1)) != 0 {
+                    PulseState::Latency { remaining: latency }
+                } else {
+                    PulseState::Idle
+                }
+            }
+            PulseState::Latency { .. } if above && double_abort => PulseState::WaitForRelease,
+            PulseState::Latency { remaining } if remaining > step => PulseState::Latency {
+                remaining: remaining - step,
+            },
+            PulseState::Latency { .. } => PulseState::Window { remaining: window },
+            PulseState::Window { .. } if above => PulseState::Second {
+                duration: step,
+                flags,
+            },
+            PulseState::Window { remaining } if remaining > step => PulseState::Window {
+                remaining: remaining - step,
+            },
+            PulseState::Window { .. } => PulseState::Idle,
+            PulseState::Second { duration, flags } if above => {
+                if duration + step > time_limit {
+                    PulseState::WaitForRelease
+                } else {
+                    PulseState::Second {
+                        duration: duration + step,
+                        flags,
+                    }
+                }
+            }
+            PulseState::Second { flags, .. } => {
+                if (double_enabled & (get_enable_mask(flags) << 1)) != 0 {
+                    event = Some(BitFlags::DPE | flags);
+                }
+                PulseState::Idle
+            }
+            PulseState::WaitForRelease if above => PulseState::WaitForRelease,
+            PulseState::WaitForRelease => PulseState::Idle,
+        };
+        let latch = (cfg & BitFlags::PULSE_ELE) != 0;
+        let signalled = if latch {
+            update_source(
+                registers,
+                Register::PULSE_SRC,
+                BitFlags::PULSE_EA,
+                true,
+                event,
+            )
+        } else if let Some(flags) = event {
+            set_reg(registers, Register::PULSE_SRC, BitFlags::PULSE_EA | flags);
+            true
+        } else {
+            false
+        };
+        if signalled {
+            BitFlags::SRC_PULSE
+        } else {
+            0
+        }
+    }
+}
+
+/// Return the single pulse enable flags (XSPEFE, YSPEFE, ZSPEFE) for the axis flags of a pulse.
+fn get_enable_mask(flags: u8) -> u8 {
+    (0..3)
+        .filter(|i| (flags & (BitFlags::AX_X << i)) != 0)
+        .fold(0, |mask, i| mask | (BitFlags::XSPEFE << (2 * i)))
+}
+
+/// Portrait/landscape detection
+///
+/// The orientation is portrait up for negative Y, portrait down for positive Y,
+/// landscape right for positive X and landscape left for negative X acceleration.
+/// The back orientation corresponds to negative Z acceleration.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct PortraitLandscape {
+    debounce: Debounce,
+}
+
+impl PortraitLandscape {
+    pub(crate) fn update(&mut self, registers: &mut Registers, acceleration: [f32; 3]) -> u8 {
+        let cfg = reg(registers, Register::PL_CFG);
+        if (cfg & BitFlags::PL_EN) == 0 {
+            return 0;
+        }
+        let status = reg(registers, Register::PL_STATUS) & !BitFlags::NEWLP;
+        let orientation = get_orientation(
+            status,
+            acceleration,
+            reg(registers, Register::PL_BF_ZCOMP),
+            reg(registers, Register::P_L_THS_REG),
+        );
+        let changed = orientation != status;
+        let limit = reg(registers, Register::PL_COUNT);
+        let clear = (cfg & BitFlags::DBCNTM) != 0;
+        if self.debounce.update(changed, limit, clear, 1) {
+            self.debounce = Debounce::default();
+            set_reg(
+                registers,
+                Register::PL_STATUS,
+                BitFlags::NEWLP | orientation,
+            );
+            BitFlags::SRC_LNDPRT
+        } else {
+            0
+        }
+    }
+}
+
+/// Return the PL_STATUS orientation bits for the acceleration, starting from the current ones.
+fn get_orientation(status: u8, acceleration: [f32; 3], bf_zcomp: u8, pl_ths: u8) -> u8 {
+    let [x, y, z] = acceleration;
+    let mut orientation = status & (BitFlags::LAPO1 | BitFlags::LAPO0 | BitFlags::BAFRO);
+    if z != 0.0 {
+        orientation &= !BitFlags::BAFRO;
+        if z < 0.0 {
+            orientation |= BitFlags::BAFRO;
+        }
+    }
+    let tan_z_lockout = Z_LOCKOUT_TAN[usize::from(bf_zcomp & 0b111)];
+    if x * x + y * y < tan_z_lockout * tan_z_lockout * z * z {
+        return orientation | BitFlags::LO;
+    }
+    let threshold = get_pl_threshold_angle(pl_ths >> 3);
+    let hysteresis = PL_HYSTERESIS[usize::from(pl_ths & 0b111)];
+    // Angle from the Y axis in the XY plane
+    let angle = atan_deg(x.abs(), y.abs());
+    let landscape = (status & BitFlags::LAPO1) != 0;
+    let landscape = if landscape {
+        angle > threshold - hysteresis
+    } else {
+        angle > threshold + hysteresis
+    };
+    let lapo = match (landscape, x < 0.0, y > 0.0) {
+        (false, _, false) => 0,
+        (false, _, true) => BitFlags::LAPO0,
+        (true, false, _) => BitFlags::LAPO1,
+        (true, true, _) => BitFlags::LAPO1 | BitFlags::LAPO0,
+    };
+    (orientation & BitFlags::BAFRO) | lapo
+}
+
+fn get_pl_threshold_angle(ths: u8) -> f32 {
+    match ths {
+        0x07 => 15.0,
+        0x09 => 20.0,
+        0x0C => 30.0,
+        0x0D => 35.0,
+        0x0F => 40.0,
+        0x13 => 55.0,
+        0x14 => 60.0,
+        0x17 => 70.0,
+        0x19 => 75.0,
+        _ => 45.0,
+    }
+}
+
+/// Approximate `atan(opposite / adjacent)` in degrees for non-negative values.
+fn atan_deg(opposite: f32, adjacent: f32) -> f32 {
+    if opposite > adjacent {
+        return 90.0 - atan_deg(adjacent, opposite);
+    }
+    if opposite == 0.0 {
+        return 0.0;
+    }
+    let ratio = opposite / adjacent;
+    45.0 * ratio - ratio * (ratio - 1.0) * (14.02 + 3.80 * ratio)
+}
+
+/// Auto-sleep/wake
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct AutoSleep {
+    idle_us: u32,
+}
+
+/// Interrupt sources of the functions which can keep the device awake or wake it up
+const ACTIVITY_SOURCES: u8 =
+    BitFlags::SRC_FF_MT | BitFlags::SRC_PULSE | BitFlags::SRC_LNDPRT | BitFlags::SRC_TRANS;
+
+impl AutoSleep {
+    /// Update the system mode after a sample period with the events signalled
+    /// during it. Returns the auto-sleep interrupt source flag on a transition.
+    pub(crate) fn update(&mut self, registers: &mut Registers, period_us: u32, events: u8) -> u8 {
+        let ctrl_reg2 = reg(registers, Register::CTRL_REG2);
+        let sysmod = reg(registers, Register::SYSMOD);
+        if (ctrl_reg2 & BitFlags::SLPE) == 0 {
+            self.idle_us = 0;
+            set_reg(registers, Register::SYSMOD, 1);
+            return 0;
+        }
+        let events = events & reg(registers, Register::CTRL_REG4);
+        if sysmod == 2 {
+            // The WAKE_* bits in CTRL_REG3 are the interrupt source bits shifted by one.
+            let wake_sources = (reg(registers, Register::CTRL_REG3) >> 1) & ACTIVITY_SOURCES;
+            if (events & wake_sources) == 0 {
+                return 0;
+            }
+            self.idle_us = 0;
+            set_reg(registers, Register::SYSMOD, 1);
+            return BitFlags::SRC_ASLP;
+        }
+        if (events & ACTIVITY_SOURCES) != 0 {
+            self.idle_us = 0;
+            return 0;
+        }
+        self.idle_us = self.idle_us.saturating_add(period_us);
+        let count_step_us = if period_us >= 640_000 {
+            640_000
+        } else {
+            320_000
+        };
+        let timeout_us = u32::from(reg(registers, Register::ASLP_COUNT)) * count_step_us;
+        if self.idle_us < timeout_us {
+            return 0;
+        }
+        self.idle_us = 0;
+        set_reg(registers, Register::SYSMOD, 2);
+        BitFlags::SRC_ASLP
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::REGISTER_COUNT;
+
+    #[test]
+    fn debounce_clear() {
+        let mut debounce = Debounce::default();
+        assert!(!debounce.update(true, 2, true, 1));
+        assert!(!debounce.update(false, 2, true, 1));
+        assert!(!debounce.update(true, 2, true, 1));
+        assert!(debounce.update(true, 2, true, 1));
+    }
+
+    #[test]
+    fn debounce_decrement() {
+        let mut debounce = Debounce::default();
+        assert!(!debounce.update(true, 3, false, 1));
+        assert!(!debounce.update(true, 3, false, 1));
+        assert!(!debounce.update(false, 3, false, 1));
+        assert!(!debounce.update(true, 3, false, 1));
+        assert!(debounce.update(true, 3, false, 1));
+    }
+
+    #[test]
+    fn debounce_with_several_steps_per_sample() {
+        let mut debounce = Debounce::default();
+        assert!(!debounce.update(true, 8, false, 4));
+        assert!(!debounce.update(false, 8, false, 2));
+        assert!(!debounce.update(true, 8, false, 4));
+        assert!(debounce.update(true, 8, false, 4));
+    }
+
+    #[test]
+    fn debounce_without_count() {
+        assert!(Debounce::default().update(true, 0, false, 1));
+    }
+
+    #[test]
+    fn high_pass_filter_starts_settled() {
+        let mut filter = HighPassFilter::default();
+        assert_eq!([0.0; 3], filter.update([0.0, 0.0, 1.0], 0.5));
+        assert_eq!([0.5, 0.0, 0.0], filter.update([1.0, 0.0, 1.0], 0.5));
+        assert_eq!([0.25, 0.0, 0.0], filter.update([1.0, 0.0, 1.0], 0.5));
+    }
+
+    #[test]
+    fn high_pass_alpha() {
+        let alpha = get_high_pass_alpha(0, 800.0);
+        assert!((alpha - 2.0 * core::f32::consts::PI * 16.0 / 800.0).abs() < 1e-6);
+        let alpha = get_high_pass_alpha(BitFlags::SEL1 | BitFlags::SEL0, 50.0);
+        assert!((alpha - 2.0 * core::f32::consts::PI * 0.25 / 50.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn atan_approximation() {
+        assert_eq!(0.0, atan_deg(0.0, 1.0));
+        assert!((atan_deg(1.0, 1.0) - 45.0).abs() < 0.01);
+        assert!((atan_deg(1.0, 0.0) - 90.0).abs() < 0.01);
+        assert!((atan_deg(0.5774, 1.0) - 30.0).abs() < 0.2);
+        assert!((atan_deg(1.0, 0.5774) - 60.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn orientation() {
+        let ths = 0x84;
+        let zcomp = 0x44;
+        assert_eq!(0, get_orientation(0, [0.0, -1.0, 0.0], zcomp, ths));
+        let portrait_down = get_orientation(0, [0.0, 1.0, 0.0], zcomp, ths);
+        assert_eq!(BitFlags::LAPO0, portrait_down);
+        let landscape_right = get_orientation(0, [1.0, 0.0, 0.0], zcomp, ths);
+        assert_eq!(BitFlags::LAPO1, landscape_right);
+        let landscape_left = get_orientation(0, [-1.0, 0.0, -0.1], zcomp, ths);
+        assert_eq!(
+            BitFlags::LAPO1 | BitFlags::LAPO0 | BitFlags::BAFRO,
+            landscape_left
+        );
+    }
+
+    #[test]
+    fn orientation_hysteresis() {
+        // 50° from the Y axis is within 45° +/- 14°
+        let acceleration = [0.766, -0.643, 0.0];
+        assert_eq!(0, get_orientation(0, acceleration, 0x44, 0x84));
+        let landscape = BitFlags::LAPO1;
+        assert_eq!(
+            landscape,
+            get_orientation(landscape, acceleration, 0x44, 0x84)
+        );
+        assert_eq!(landscape, get_orientation(0, acceleration, 0x44, 0x80));
+    }
+
+    #[test]
+    fn orientation_lockout() {
+        let landscape = BitFlags::LAPO1;
+        assert_eq!(
+            landscape | BitFlags::LO,
+            get_orientation(landscape, [0.0, -0.1, 1.0], 0x44, 0x84)
+        );
+    }
+
+    #[test]
+    fn motion_detection_latched() {
+        let mut registers = [0; REGISTER_COUNT];
+        set_reg(
+            &mut registers,
+            Register::FF_MT_CFG,
+            BitFlags::FF_MT_ELE | BitFlags::OAE | BitFlags::XEFE,
+        );
+        set_reg(&mut registers, Register::FF_MT_THS, 16);
+        let mut motion = FreefallMotion::default();
+        assert_eq!(0, motion.update(&mut registers, [0.5, 0.0, 0.0], 1));
+        assert_eq!(
+            BitFlags::SRC_FF_MT,
+            motion.update(&mut registers, [-1.5, 0.0, 0.0], 1)
+        );
+        let src = BitFlags::FF_MT_EA | BitFlags::XHE | BitFlags::XHP;
+        assert_eq!(src, reg(&registers, Register::FF_MT_SRC));
+        assert_eq!(0, motion.update(&mut registers, [0.0, 0.0, 0.0], 1));
+        assert_eq!(src, reg(&registers, Register::FF_MT_SRC));
+    }
+
+    #[test]
+    fn freefall_detection_with_debounce() {
+        let mut registers = [0; REGISTER_COUNT];
+        let cfg = BitFlags::XEFE | BitFlags::YEFE | BitFlags::ZEFE;
+        set_reg(&mut registers, Register::FF_MT_CFG, cfg);
+        set_reg(&mut registers, Register::FF_MT_THS, 3);
+        set_reg(&mut registers, Register::FF_MT_COUNT, 2);
+        let mut freefall = FreefallMotion::default();
+        assert_eq!(0, freefall.update(&mut registers, [0.0, 0.0, 0.1], 1));
+        assert_eq!(
+            BitFlags::SRC_FF_MT,
+            freefall.update(&mut registers, [0.0, 0.0, 0.1], 1)
+        );
+        assert_eq!(BitFlags::FF_MT_EA, reg(&registers, Register::FF_MT_SRC));
+        assert_eq!(0, freefall.update(&mut registers, [0.0, 0.0, 1.0], 1));
+        assert_eq!(0, reg(&registers, Register::FF_MT_SRC));
+    }
+
+    #[test]
+    fn transient_detection() {
+        let mut registers = [0; REGISTER_COUNT];
+        let cfg = BitFlags::TRANS_ELE | BitFlags::ZTEFE;
+        set_reg(&mut registers, Register::TRANSIENT_CFG, cfg);
+        set_reg(&mut registers, Register::TRANSIENT_THS, 8);
+        let mut transient = Transient::default();
+        assert_eq!(
+            0,
+            transient.update(&mut registers, [0.0, 0.0, 1.0], [0.0, 0.0, 0.0], 1)
+        );
+        assert_eq!(
+            BitFlags::SRC_TRANS,
+            transient.update(&mut registers, [0.0, 0.0, 0.4], [0.0, 0.0, -0.6], 1)
+        );
+        assert_eq!(
+            BitFlags::TRANS_EA | BitFlags::ZTRANSE | BitFlags::Z_TRANS_POL,
+            reg(&registers, Register::TRANSIENT_SRC)
+        );
+    }
+
+    fn tap_registers(cfg: u8) -> Registers {
+        let mut registers = [0; REGISTER_COUNT];
+        set_reg(&mut registers, Register::PULSE_CFG, cfg);
+        set_reg(&mut registers, Register::PULSE_THSX + 2, 16);
+        set_reg(&mut registers, Register::PULSE_TMLT, 4);
+        set_reg(&mut registers, Register::PULSE_LTCY, 2);
+        set_reg(&mut registers, Register::PULSE_WIND, 4);
+        registers
+    }
+
+    // Two time limit steps per sample
+    fn run_pulse(pulse: &mut Pulse, registers: &mut Registers, samples: &[f32]) -> u8 {
+        samples.iter().fold(0, |sources, &z| {
+            sources | pulse.update(registers, [0.0, 0.0, z], [0.0, 0.0, z], 2)
+        })
+    }
+
+    #[test]
+    fn single_tap() {
+        let mut registers = tap_registers(BitFlags::ZSPEFE);
+        let mut pulse = Pulse::default();
+        let sources = run_pulse(&mut pulse, &mut registers, &[0.0, 2.0, 2.0, 0.0]);
+        assert_eq!(BitFlags::SRC_PULSE, sources);
+        assert_eq!(
+            BitFlags::PULSE_EA | BitFlags::AX_Z,
+            reg(&registers, Register::PULSE_SRC)
+        );
+    }
+
+    #[test]
+    fn too_long_pulse_is_no_tap() {
+        let mut registers = tap_registers(BitFlags::ZSPEFE);
+        let mut pulse = Pulse::default();
+        let sources = run_pulse(&mut pulse, &mut registers, &[2.0, 2.0, 2.0, 0.0, 0.0]);
+        assert_eq!(0, sources);
+    }
+
+    #[test]
+    fn double_tap() {
+        let mut registers = tap_registers(BitFlags::ZDPEFE);
+        let mut pulse = Pulse::default();
+        let samples = [-2.0, 0.0, 0.0, 0.0, -2.0, 0.0];
+        let sources = run_pulse(&mut pulse, &mut registers, &samples);
+        assert_eq!(BitFlags::SRC_PULSE, sources);
+        assert_eq!(
+            BitFlags::PULSE_EA | BitFlags::DPE | BitFlags::AX_Z | BitFlags::POL_Z,
+            reg(&registers, Register::PULSE_SRC)
+        );
+    }
+
+    #[test]
+    fn no_double_tap_after_window() {
+        let mut registers = tap_registers(BitFlags::ZDPEFE);
+        let mut pulse = Pulse::default();
+        let samples = [2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0];
+        assert_eq!(0, run_pulse(&mut pulse, &mut registers, &samples));
+    }
+
+    #[test]
+    fn enable_mask() {
+        assert_eq!(BitFlags::XSPEFE, get_enable_mask(BitFlags::AX_X));
+        assert_eq!(
+            BitFlags::YSPEFE | BitFlags::ZSPEFE,
+            get_enable_mask(BitFlags::AX_Y | BitFlags::AX_Z | BitFlags::POL_Z)
+        );
+    }
+
+    #[test]
+    fn auto_sleep_after_inactivity() {
+        let mut registers = [0; REGISTER_COUNT];
+        set_reg(&mut registers, Register::CTRL_REG2, BitFlags::SLPE);
+        set_reg(&mut registers, Register::ASLP_COUNT, 1);
+        set_reg(&mut registers, Register::SYSMOD, 1);
+        let mut auto_sleep = AutoSleep::default();
+        assert_eq!(0, auto_sleep.update(&mut registers, 160_000, 0));
+        assert_eq!(
+            BitFlags::SRC_ASLP,
+            auto_sleep.update(&mut registers, 160_000, 0)
+        );
+        assert_eq!(2, reg(&registers, Register::SYSMOD));
+    }
+
+    #[test]
+    fn activity_keeps_awake() {
+        let mut registers = [0; REGISTER_COUNT];
+        set_reg(&mut registers, Register::CTRL_REG2, BitFlags::SLPE);
+        set_reg(&mut registers, Register::CTRL_REG4, BitFlags::INT_EN_PULSE);
+        set_reg(&mut registers, Register::ASLP_COUNT, 1);
+        set_reg(&mut registers, Register::SYSMOD, 1);
+        let mut auto_sleep = AutoSleep::default();
+        assert_eq!(0, auto_sleep.update(&mut registers, 160_000, 0));
+        let events = BitFlags::SRC_PULSE;
+        assert_eq!(0, auto_sleep.update(&mut registers, 160_000, events));
+        assert_eq!(0, auto_sleep.update(&mut registers, 160_000, 0));
+        assert_eq!(1, reg(&registers, Register::SYSMOD));
+    }
+
+    #[test]
+    fn wake_on_enabled_wake_source() {
+        let mut registers = [0; REGISTER_COUNT];
+        set_reg(&mut registers, Register::CTRL_REG2, BitFlags::SLPE);
+        set_reg(
+            &mut registers,
+            Register::CTRL_REG4,
+            BitFlags::INT_EN_PULSE | BitFlags::INT_EN_TRANS,
+        );
+        set_reg(&mut registers, Register::CTRL_REG3, BitFlags::WAKE_TRANS);
+        set_reg(&mut registers, Register::SYSMOD, 2);
+        let mut auto_sleep = AutoSleep::default();
+        let pulse = BitFlags::SRC_PULSE;
+        assert_eq!(0, auto_sleep.update(&mut registers, 20_000, pulse));
+        assert_eq!(2, reg(&registers, Register::SYSMOD));
+        let transient = BitFlags::SRC_TRANS;
+        assert_eq!(
+            BitFlags::SRC_ASLP,
+            auto_sleep.update(&mut registers, 20_000, transient)
+        );
+        assert_eq!(1, reg(&registers, Register::SYSMOD));
+    }
+}
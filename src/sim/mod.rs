@@ -0,0 +1,710 @@
+//! Software simulation of the devices
+//!
+//! [`SimulatedMma8x5x`] implements the `embedded-hal` I²C traits and models the
+//! register file and the embedded functions of a device, so that it can be
+//! used in place of a real bus to test application code using the driver
+//! without hardware.
+//!
+//! The I²C traits are also implemented for a shared reference to the simulated
+//! device. This way the driver can use it while the test advances the time
+//! and reads the simulated interrupt pins:
+//!
+//! ```
+//! use core::time::Duration;
+//! use embedded_hal::digital::InputPin;
+//! use mma8x5x::{sim::SimulatedMma8x5x, EnabledInterrupts, Measurement, Mma8x5x};
+//!
+//! let sim = SimulatedMma8x5x::new_mma8652(|| Measurement {
+//!     x: 0.0,
+//!     y: 0.0,
+//!     z: 1.0,
+//! });
+//! let mut sensor = Mma8x5x::new_mma8652(&sim);
+//! sensor.set_enabled_interrupts(EnabledInterrupts {
+//!     data_ready: true,
+//!     ..EnabledInterrupts::default()
+//! }).unwrap();
+//! let mut sensor = sensor.into_active().ok().unwrap();
+//! sim.advance(Duration::from_millis(10));
+//! // The interrupt pins are active low by default
+//! assert!(sim.int2().is_low().unwrap());
+//! assert_eq!(1024, sensor.read_unscaled().unwrap().z);
+//! ```
+
+use core::{cell::RefCell, convert::Infallible, fmt, time::Duration};
+
+use embedded_hal::{
+    digital::{self, InputPin},
+    i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation},
+};
+
+use crate::{
+    common::{
+        calibration::OFFSET_RESOLUTION,
+        config::{get_data_rate, get_power_mode},
+    },
+    conversion::{counter_time_step_ms, pulse_time_step_ms},
+    ic,
+    register_access::{BitFlags, Register},
+    types::MMA845X_BASE_ADDR,
+    DetectedIc, Measurement, OutputDataRate, PowerMode, SlaveAddr,
+};
+
+mod functions;
+use self::functions::{
+    get_high_pass_alpha, AutoSleep, FreefallMotion, HighPassFilter, PortraitLandscape, Pulse,
+    Transient,
+};
+
+const REGISTER_COUNT: usize = Register::OFF_Z as usize + 1;
+
+/// Number of counter time steps in a sample period
+fn get_steps(period_us: u32, step_ms: f32) -> u16 {
+    (period_us as f32 / (step_ms * 1000.0) + 0.5) as u16
+}
+
+type Registers = [u8; REGISTER_COUNT];
+
+/// Simulated device
+///
+/// Behaves like a device connected to an I²C bus:
+/// - Only the device address is acknowledged.
+/// - The register address auto-increments, skipping the LSB registers in
+///   fast-read mode and wrapping around like the device does.
+/// - Read-only registers and registers not present in the device model
+///   ignore writes. Not present registers read as zero.
+/// - In active mode, only the mode change in CTRL_REG1, the reset in
+///   CTRL_REG2 and, on the MMA8451 and MMA8652, the portrait/landscape
+///   debounce counter can be written. Other writes are ignored.
+/// - Setting the reset bit restores all registers to their reset values.
+///
+/// In active mode, the device takes a sample when it becomes active and then
+/// one per sample period as the time is advanced with [`advance()`](Self::advance)
+/// or [`step()`](Self::step). The acceleration in G is taken from the provided
+/// closure and converted according to the resolution of the device, the
+/// configured G scale and the offset correction. Reading the data registers
+/// clears the data ready status.
+///
+/// Each sample is processed by the embedded functions available on the device:
+/// - Portrait/landscape detection with trip angles, Z-lockout, hysteresis and
+///   debounce counter. The orientation is portrait up for negative Y,
+///   portrait down for positive Y, landscape right for positive X and
+///   landscape left for negative X acceleration. Back corresponds to
+///   negative Z acceleration.
+/// - Freefall/motion detection with threshold and debounce counter.
+/// - Transient detection on the high-pass filtered acceleration.
+/// - Single/double pulse detection with time limit, latency and window.
+/// - Auto-sleep/wake with the sleep counter and the wake sources.
+///
+/// The events set the interrupt source flags of the enabled interrupts,
+/// which are signalled on the simulated interrupt pins as routed.
+/// Reading the source registers clears them.
+///
+/// The FIFO is not simulated: on all device models the FIFO registers
+/// (F_SETUP, TRIG_CFG) ignore writes and read as zero, so the FIFO functions
+/// of the driver are not meaningful with a simulated device.
+pub struct SimulatedMma8x5x<F> {
+    address: u8,
+    state: RefCell<State>,
+    acceleration: RefCell<F>,
+}
+
+impl<F> SimulatedMma8x5x<F>
+where
+    F: FnMut() -> Measurement,
+{
+    /// Create a simulated MMA8451 device
+    pub fn new_mma8451(address: SlaveAddr, acceleration: F) -> Self {
+        Self::new_with_address::<ic::Mma8451>(address, acceleration)
+    }
+
+    /// Create a simulated MMA8452 device
+    pub fn new_mma8452(address: SlaveAddr, acceleration: F) -> Self {
+        Self::new_with_address::<ic::Mma8452>(address, acceleration)
+    }
+
+    /// Create a simulated MMA8453 device
+    pub fn new_mma8453(address: SlaveAddr, acceleration: F) -> Self {
+        Self::new_with_address::<ic::Mma8453>(address, acceleration)
+    }
+
+    /// Create a simulated MMA8652 device
+    pub fn new_mma8652(acceleration: F) -> Self {
+        Self::new(DetectedIc::Mma8652, 0x1D, acceleration)
+    }
+
+    /// Create a simulated MMA8653 device
+    pub fn new_mma8653(acceleration: F) -> Self {
+        Self::new(DetectedIc::Mma8653, 0x1D, acceleration)
+    }
+
+    fn new_with_address<IC: ic::HasSelectableAddress>(address: SlaveAddr, acceleration: F) -> Self {
+        Self::new(IC::MODEL, address.addr(MMA845X_BASE_ADDR), acceleration)
+    }
+
+    fn new(ic: DetectedIc, address: u8, acceleration: F) -> Self {
+        let mut state = State {
+            ic,
+            registers: [0; REGISTER_COUNT],
+            pointer: 0,
+            elapsed: Duration::ZERO,
+            functions: Functions::default(),
+        };
+        state.reset();
+        SimulatedMma8x5x {
+            address,
+            state: RefCell::new(state),
+            acceleration: RefCell::new(acceleration),
+        }
+    }
+
+    /// Take and process a sample if the device is active.
+    pub fn step(&self) {
+        if !self.state.borrow().is_active() {
+            return;
+        }
+        let acceleration = (self.acceleration.borrow_mut())();
+        self.state.borrow_mut().process(acceleration);
+    }
+
+    /// Advance the time, taking a sample for each elapsed sample period.
+    ///
+    /// The sample period follows the configured data rate, or the auto-sleep
+    /// data rate while the device is in sleep mode.
+    pub fn advance(&self, duration: Duration) {
+        self.state.borrow_mut().elapsed += duration;
+        loop {
+            let mut state = self.state.borrow_mut();
+            if !state.is_active() {
+                state.elapsed = Duration::ZERO;
+                return;
+            }
+            let period = Duration::from_micros(state.sample_period_us().into());
+            if state.elapsed < period {
+                return;
+            }
+            state.elapsed -= period;
+            drop(state);
+            self.step();
+        }
+    }
+
+    fn transaction_internal(
+        &self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind> {
+        if address != self.address {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => {
+                    let activated = self.state.borrow_mut().write_data(data);
+                    if activated {
+                        self.step();
+                    }
+                }
+                Operation::Read(buffer) => self.state.borrow_mut().read_data(buffer),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F> SimulatedMma8x5x<F> {
+    /// Simulated device model
+    pub fn ic(&self) -> DetectedIc {
+        self.state.borrow().ic
+    }
+
+    /// I²C address of the simulated device
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Read a register without side effects
+    pub fn register(&self, register: u8) -> u8 {
+        self.state.borrow().register(register)
+    }
+
+    /// Set a register bypassing the access rules
+    ///
+    /// Useful to inject a state which is not simulated.
+    pub fn set_register(&self, register: u8, value: u8) {
+        self.state.borrow_mut().set_register(register, value);
+    }
+
+    /// Simulated INT1 pin
+    pub fn int1(&self) -> SimulatedInterruptPin<'_, F> {
+        SimulatedInterruptPin {
+            sim: self,
+            int1: true,
+        }
+    }
+
+    /// Simulated INT2 pin
+    pub fn int2(&self) -> SimulatedInterruptPin<'_, F> {
+        SimulatedInterruptPin {
+            sim: self,
+            int1: false,
+        }
+    }
+}
+
+/// Simulated interrupt pin
+///
+/// Its level follows the pending interrupts routed to the pin and the
+/// configured interrupt pin polarity.
+/// See [`SimulatedMma8x5x::int1()`] and [`SimulatedMma8x5x::int2()`].
+pub struct SimulatedInterruptPin<'a, F> {
+    sim: &'a SimulatedMma8x5x<F>,
+    int1: bool,
+}
+
+impl<F> fmt::Debug for SimulatedInterruptPin<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SimulatedInterruptPin")
+            .field("int1", &self.int1)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> digital::ErrorType for SimulatedInterruptPin<'_, F> {
+    type Error = Infallible;
+}
+
+impl<F> InputPin for SimulatedInterruptPin<'_, F> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.sim.state.borrow().is_int_high(self.int1))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Functions {
+    high_pass_filter: HighPassFilter,
+    portrait_landscape: PortraitLandscape,
+    freefall_motion: FreefallMotion,
+    transient: Transient,
+    pulse: Pulse,
+    auto_sleep: AutoSleep,
+}
+
+#[derive(Debug)]
+struct State {
+    ic: DetectedIc,
+    registers: Registers,
+    pointer: u8,
+    elapsed: Duration,
+    functions: Functions,
+}
+
+impl State {
+    fn register(&self, register: u8) -> u8 {
+        self.registers
+            .get(usize::from(register))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn set_register(&mut self, register: u8, value: u8) {
+        if let Some(reg) = self.registers.get_mut(usize::from(register)) {
+            *reg = value;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.registers = [0; REGISTER_COUNT];
+        self.registers[usize::from(Register::WHO_AM_I)] = self.ic.device_id();
+        self.registers[usize::from(Register::PL_CFG)] = BitFlags::DBCNTM;
+        self.registers[usize::from(Register::PL_BF_ZCOMP)] = 0x44;
+        self.registers[usize::from(Register::P_L_THS_REG)] = 0x84;
+        self.elapsed = Duration::ZERO;
+        self.functions = Functions::default();
+    }
+
+    fn is_active(&self) -> bool {
+        (self.register(Register::CTRL_REG1) & BitFlags::ACTIVE) != 0
+    }
+
+    fn is_writable(&self, register: u8) -> bool {
+        match register {
+            Register::STATUS..=0x08
+            | Register::SYSMOD
+            | Register::INT_SOURCE
+            | Register::WHO_AM_I
+            | Register::PL_STATUS
+            | Register::FF_MT_SRC
+            | Register::TRANSIENT_SRC
+            | Register::PULSE_SRC => false,
+            // The FIFO is not simulated
+            Register::F_SETUP | Register::TRIG_CFG => false,
+            Register::PL_BF_ZCOMP | Register::P_L_THS_REG => self.ic.has_programmable_orientation(),
+            Register::HP_FILTER_CUTOFF | Register::TRANSIENT_CFG..=Register::PULSE_WIND => {
+                self.ic.has_high_pass_filter()
+            }
+            // Reserved
+            0x19..=0x1C => false,
+            _ => register <= Register::OFF_Z,
+        }
+    }
+
+    /// Write a register and return whether the device has become active.
+    fn write_register(&mut self, register: u8, value: u8) -> bool {
+        if register == Register::CTRL_REG2 && (value & BitFlags::RST) != 0 {
+            self.reset();
+            return false;
+        }
+        if !self.is_writable(register) {
+            return false;
+        }
+        let was_active = self.is_active();
+        if was_active {
+            match register {
+                Register::CTRL_REG1 => {
+                    let ctrl_reg1 = self.register(register) & !BitFlags::ACTIVE;
+                    self.set_register(register, ctrl_reg1 | (value & BitFlags::ACTIVE));
+                }
+                Register::PL_COUNT if self.ic.has_programmable_orientation() => {
+                    self.set_register(register, value)
+                }
+                _ => (),
+            }
+        } else {
+            self.set_register(register, value);
+        }
+        let active = self.is_active();
+        if active != was_active {
+            self.elapsed = Duration::ZERO;
+            self.functions = Functions::default();
+            self.set_register(Register::SYSMOD, u8::from(active));
+            if !active {
+                self.set_register(Register::STATUS, 0);
+            }
+        }
+        active && !was_active
+    }
+
+    /// Write the data of a write operation and return whether the device has become active.
+    fn write_data(&mut self, data: &[u8]) -> bool {
+        let mut activated = false;
+        if let Some((&register, values)) = data.split_first() {
+            self.pointer = register;
+            for &value in values {
+                activated |= self.write_register(self.pointer, value);
+                self.pointer = get_next_write_address(self.pointer);
+            }
+        }
+        activated
+    }
+
+    fn read_data(&mut self, buffer: &mut [u8]) {
+        let fast_read = (self.register(Register::CTRL_REG1) & BitFlags::F_READ) != 0;
+        let mut read = 0_u64;
+        for byte in buffer.iter_mut() {
+            *byte = self.register(self.pointer);
+            if usize::from(self.pointer) < REGISTER_COUNT {
+                read |= 1 << self.pointer;
+            }
+            self.pointer = get_next_read_address(self.pointer, fast_read);
+        }
+        self.clear_on_read(read);
+    }
+
+    /// Clear the status and source registers read and their interrupt sources.
+    fn clear_on_read(&mut self, read: u64) {
+        let was_read = |register: u8| (read & (1 << register)) != 0;
+        let mut int_source = self.register(Register::INT_SOURCE);
+        if (Register::OUT_X_H..=Register::OUT_Z_L).any(was_read) {
+            self.set_register(Register::STATUS, 0);
+            int_source &= !BitFlags::SRC_DRDY;
+        }
+        if was_read(Register::SYSMOD) {
+            int_source &= !BitFlags::SRC_ASLP;
+        }
+        if was_read(Register::PL_STATUS) {
+            let pl_status = self.register(Register::PL_STATUS) & !BitFlags::NEWLP;
+            self.set_register(Register::PL_STATUS, pl_status);
+            int_source &= !BitFlags::SRC_LNDPRT;
+        }
+        if was_read(Register::FF_MT_SRC) {
+            if (self.register(Register::FF_MT_CFG) & BitFlags::FF_MT_ELE) != 0 {
+                self.set_register(Register::FF_MT_SRC, 0);
+            }
+            int_source &= !BitFlags::SRC_FF_MT;
+        }
+        if was_read(Register::TRANSIENT_SRC) {
+            if (self.register(Register::TRANSIENT_CFG) & BitFlags::TRANS_ELE) != 0 {
+                self.set_register(Register::TRANSIENT_SRC, 0);
+            }
+            int_source &= !BitFlags::SRC_TRANS;
+        }
+        if was_read(Register::PULSE_SRC) {
+            self.set_register(Register::PULSE_SRC, 0);
+            int_source &= !BitFlags::SRC_PULSE;
+        }
+        self.set_register(Register::INT_SOURCE, int_source);
+    }
+
+    fn is_sleeping(&self) -> bool {
+        self.register(Register::SYSMOD) == 2
+    }
+
+    /// Current data rate, the auto-sleep data rate in sleep mode
+    fn data_rate(&self) -> OutputDataRate {
+        let ctrl_reg1 = self.register(Register::CTRL_REG1);
+        if self.is_sleeping() {
+            match ctrl_reg1 >> 6 {
+                0 => OutputDataRate::Hz50,
+                1 => OutputDataRate::Hz12_5,
+                2 => OutputDataRate::Hz6_25,
+                _ => OutputDataRate::Hz1_56,
+            }
+        } else {
+            get_data_rate(ctrl_reg1)
+        }
+    }
+
+    /// Current power mode, the sleep power mode in sleep mode
+    fn power_mode(&self) -> PowerMode {
+        let ctrl_reg2 = self.register(Register::CTRL_REG2);
+        let mods = if self.is_sleeping() {
+            ctrl_reg2 >> 3
+        } else {
+            ctrl_reg2
+        };
+        get_power_mode(mods)
+    }
+
+    fn sample_period_us(&self) -> u32 {
+        match self.data_rate() {
+            OutputDataRate::Hz800 => 1_250,
+            OutputDataRate::Hz400 => 2_500,
+            OutputDataRate::Hz200 => 5_000,
+            OutputDataRate::Hz100 => 10_000,
+            OutputDataRate::Hz50 => 20_000,
+            OutputDataRate::Hz12_5 => 80_000,
+            OutputDataRate::Hz6_25 => 160_000,
+            OutputDataRate::Hz1_56 => 640_000,
+        }
+    }
+
+    /// Process a sample through the data path and the embedded functions.
+    fn process(&mut self, acceleration: Measurement) {
+        let mut axes = [acceleration.x, acceleration.y, acceleration.z];
+        for (i, value) in axes.iter_mut().enumerate() {
+            let offset = self.registers[usize::from(Register::OFF_X) + i] as i8;
+            *value += f32::from(offset) * OFFSET_RESOLUTION;
+        }
+        let period_us = self.sample_period_us();
+        let counter_steps = get_steps(
+            period_us,
+            counter_time_step_ms(self.data_rate(), self.power_mode()),
+        );
+        let lpf_enabled = (self.register(Register::HP_FILTER_CUTOFF) & BitFlags::PULSE_LPF_EN) != 0;
+        let pulse_steps = get_steps(
+            period_us,
+            pulse_time_step_ms(self.data_rate(), self.power_mode(), lpf_enabled),
+        );
+        let data_rate = 1_000_000.0 / period_us as f32;
+        let alpha = get_high_pass_alpha(self.register(Register::HP_FILTER_CUTOFF), data_rate);
+        let functions = &mut self.functions;
+        let filtered = functions.high_pass_filter.update(axes, alpha);
+        let hpf_out =
+            (self.registers[usize::from(Register::XYZ_DATA_CFG)] & BitFlags::HPF_OUT) != 0;
+        let output = if hpf_out && self.ic.has_high_pass_filter() {
+            filtered
+        } else {
+            axes
+        };
+        let registers = &mut self.registers;
+        let mut events = BitFlags::SRC_DRDY;
+        events |= functions.portrait_landscape.update(registers, axes);
+        events |= functions
+            .freefall_motion
+            .update(registers, axes, counter_steps);
+        if self.ic.has_high_pass_filter() {
+            events |= functions
+                .transient
+                .update(registers, axes, filtered, counter_steps);
+            events |= functions
+                .pulse
+                .update(registers, axes, filtered, pulse_steps);
+        }
+        events |= functions.auto_sleep.update(registers, period_us, events);
+        self.store_sample(output);
+        let enabled = self.register(Register::CTRL_REG4);
+        let int_source = self.register(Register::INT_SOURCE) | (events & enabled);
+        self.set_register(Register::INT_SOURCE, int_source);
+    }
+
+    fn store_sample(&mut self, acceleration: [f32; 3]) {
+        let shift = self.ic.sensitivity_shift();
+        // The samples have two bits more than the sensitivity at +/-2g.
+        let bits = shift + 2;
+        let scale_shift =
+            match self.register(Register::XYZ_DATA_CFG) & (BitFlags::FS1 | BitFlags::FS0) {
+                0 => shift,
+                BitFlags::FS0 => shift - 1,
+                _ => shift - 2,
+            };
+        let counts_per_g = f32::from(1_u16 << scale_shift);
+        for (i, value) in acceleration.into_iter().enumerate() {
+            let sample = get_sample(value, counts_per_g, bits);
+            let out = usize::from(Register::OUT_X_H) + 2 * i;
+            self.registers[out..out + 2].copy_from_slice(&sample.to_be_bytes());
+        }
+        let mut status = BitFlags::XDR | BitFlags::YDR | BitFlags::ZDR | BitFlags::XYZDR;
+        if (self.register(Register::STATUS) & BitFlags::XYZDR) != 0 {
+            status |= BitFlags::XOW | BitFlags::YOW | BitFlags::ZOW | BitFlags::XYZOW;
+        }
+        self.set_register(Register::STATUS, status);
+    }
+
+    fn is_int_high(&self, int1: bool) -> bool {
+        let pending = self.register(Register::INT_SOURCE) & self.register(Register::CTRL_REG4);
+        let ctrl_reg5 = self.register(Register::CTRL_REG5);
+        let routed = if int1 { ctrl_reg5 } else { !ctrl_reg5 };
+        let active = (pending & routed) != 0;
+        let active_high = (self.register(Register::CTRL_REG3) & BitFlags::IPOL) != 0;
+        active == active_high
+    }
+}
+
+impl<F> fmt::Debug for SimulatedMma8x5x<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SimulatedMma8x5x")
+            .field("address", &self.address)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> ErrorType for SimulatedMma8x5x<F> {
+    type Error = ErrorKind;
+}
+
+impl<F> I2c for SimulatedMma8x5x<F>
+where
+    F: FnMut() -> Measurement,
+{
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transaction_internal(address, operations)
+    }
+}
+
+impl<F> ErrorType for &SimulatedMma8x5x<F> {
+    type Error = ErrorKind;
+}
+
+impl<F> I2c for &SimulatedMma8x5x<F>
+where
+    F: FnMut() -> Measurement,
+{
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transaction_internal(address, operations)
+    }
+}
+
+/// Convert an acceleration in G into the left-justified content of the data registers.
+fn get_sample(acceleration: f32, counts_per_g: f32, bits: u8) -> u16 {
+    let max = (1_i32 << (bits - 1)) - 1;
+    let counts = round(acceleration * counts_per_g).clamp(-max - 1, max);
+    ((counts << (16 - bits)) as i16) as u16
+}
+
+fn round(value: f32) -> i32 {
+    if value < 0.0 {
+        (value - 0.5) as i32
+    } else {
+        (value + 0.5) as i32
+    }
+}
+
+fn get_next_write_address(register: u8) -> u8 {
+    if register >= Register::OFF_Z {
+        0
+    } else {
+        register + 1
+    }
+}
+
+/// Return the next register address when reading.
+///
+/// In fast-read mode the LSB registers are skipped. After the data registers
+/// the address wraps around to the status register.
+fn get_next_read_address(register: u8, fast_read: bool) -> u8 {
+    let last_data = if fast_read {
+        Register::OUT_Z_L - 1
+    } else {
+        Register::OUT_Z_L
+    };
+    match register {
+        r if r == last_data && fast_read => Register::STATUS,
+        Register::OUT_X_H..=Register::OUT_Z_L if fast_read => register + 2,
+        _ => get_next_write_address(register),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_address_increments_and_wraps() {
+        assert_eq!(0x01, get_next_write_address(0x00));
+        assert_eq!(0x31, get_next_write_address(0x30));
+        assert_eq!(0x00, get_next_write_address(0x31));
+    }
+
+    #[test]
+    fn read_address_normal() {
+        assert_eq!(0x01, get_next_read_address(0x00, false));
+        assert_eq!(0x02, get_next_read_address(0x01, false));
+        assert_eq!(0x07, get_next_read_address(0x06, false));
+        assert_eq!(0x00, get_next_read_address(0x31, false));
+    }
+
+    #[test]
+    fn read_address_fast() {
+        assert_eq!(0x01, get_next_read_address(0x00, true));
+        assert_eq!(0x03, get_next_read_address(0x01, true));
+        assert_eq!(0x05, get_next_read_address(0x03, true));
+        assert_eq!(0x00, get_next_read_address(0x05, true));
+        assert_eq!(0x0E, get_next_read_address(0x0D, true));
+    }
+
+    #[test]
+    fn sample_is_left_justified() {
+        assert_eq!(0x4000, get_sample(1.0, 4096.0, 14));
+        assert_eq!(0x4000, get_sample(1.0, 1024.0, 12));
+        assert_eq!(0x4000, get_sample(1.0, 256.0, 10));
+        assert_eq!(0xC000, get_sample(-1.0, 256.0, 10));
+        assert_eq!(0x0040, get_sample(0.5 / 256.0, 256.0, 10));
+    }
+
+    #[test]
+    fn sample_rounds() {
+        assert_eq!(0x0004, get_sample(0.6 / 4096.0, 4096.0, 14));
+        assert_eq!(0xFFFC, get_sample(-0.6 / 4096.0, 4096.0, 14));
+        assert_eq!(0x0000, get_sample(0.4 / 4096.0, 4096.0, 14));
+    }
+
+    #[test]
+    fn sample_saturates() {
+        assert_eq!(0x7FC0, get_sample(3.0, 256.0, 10));
+        assert_eq!(0x8000, get_sample(-3.0, 256.0, 10));
+    }
+}
@@ -0,0 +1,357 @@
+//! Recording and replay of I²C transactions
+//!
+//! [`RecordingI2c`] wraps the I²C bus used by the driver and writes each
+//! transaction as a line of text to a [`core::fmt::Write`] sink, for example
+//! a `String` or a serial port. [`ReplayI2c`] feeds such a trace back into
+//! the driver, so that the behavior of a device in the field can be turned
+//! into a deterministic test.
+//!
+//! Each line of a trace contains whitespace-separated fields:
+//! - The timestamp in microseconds at which the transaction started.
+//! - The device address in hexadecimal.
+//! - For each operation, `W` followed by the bytes written or `R` followed by
+//!   the bytes read, in hexadecimal.
+//! - If the transaction failed, `E` followed by the error kind: `bus`,
+//!   `arbitration-loss`, `nack-address`, `nack-data`, `nack`, `overrun` or `other`.
+//!
+//! Empty lines and lines starting with `#` are ignored. For example, a write
+//! to CTRL_REG1 followed by reading the device ID:
+//!
+//! ```text
+//! # Device ID
+//! 1200 1D W 2A 01
+//! 1450 1D W 0D R 4A
+//! ```
+
+use core::{fmt, str::Lines};
+
+use embedded_hal::i2c::{self, ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+/// I²C bus wrapper recording the transactions
+///
+/// The timestamps in microseconds are provided by a closure.
+/// See the [module documentation](self) for the trace format.
+pub struct RecordingI2c<I2C, W, T> {
+    i2c: I2C,
+    writer: W,
+    clock: T,
+    truncated: bool,
+}
+
+impl<I2C, W, T> RecordingI2c<I2C, W, T>
+where
+    W: fmt::Write,
+    T: FnMut() -> u64,
+{
+    /// Create a new instance recording the transactions on `i2c` into `writer`.
+    pub fn new(i2c: I2C, writer: W, clock: T) -> Self {
+        RecordingI2c {
+            i2c,
+            writer,
+            clock,
+            truncated: false,
+        }
+    }
+
+    fn log<E: i2c::Error>(
+        &mut self,
+        timestamp: u64,
+        address: u8,
+        operations: &[Operation<'_>],
+        result: &Result<(), E>,
+    ) {
+        if !self.truncated {
+            self.truncated = self.record(timestamp, address, operations, result).is_err();
+        }
+    }
+
+    fn record<E: i2c::Error>(
+        &mut self,
+        timestamp: u64,
+        address: u8,
+        operations: &[Operation<'_>],
+        result: &Result<(), E>,
+    ) -> fmt::Result {
+        write!(self.writer, "{} {:02X}", timestamp, address)?;
+        for operation in operations {
+            let (marker, data) = match operation {
+                Operation::Write(data) => ("W", &data[..]),
+                Operation::Read(buffer) => ("R", &buffer[..]),
+            };
+            write!(self.writer, " {}", marker)?;
+            for byte in data {
+                write!(self.writer, " {:02X}", byte)?;
+            }
+        }
+        if let Err(e) = result {
+            write!(self.writer, " E {}", get_error_name(e.kind()))?;
+        }
+        writeln!(self.writer)
+    }
+}
+
+impl<I2C, W, T> RecordingI2c<I2C, W, T> {
+    /// Return whether writing a transaction to the trace failed.
+    ///
+    /// This happens for example if the trace buffer is full. The transactions
+    /// on the bus are not affected but the trace is incomplete.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Destroy the instance, return the I²C bus and the trace writer.
+    pub fn destroy(self) -> (I2C, W) {
+        (self.i2c, self.writer)
+    }
+}
+
+impl<I2C, W, T> fmt::Debug for RecordingI2c<I2C, W, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordingI2c")
+            .field("truncated", &self.truncated)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I2C: ErrorType, W, T> ErrorType for RecordingI2c<I2C, W, T> {
+    type Error = I2C::Error;
+}
+
+impl<I2C, W, T> I2c for RecordingI2c<I2C, W, T>
+where
+    I2C: I2c,
+    W: fmt::Write,
+    T: FnMut() -> u64,
+{
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        let timestamp = (self.clock)();
+        let result = self.i2c.read(address, read);
+        self.log(timestamp, address, &[Operation::Read(read)], &result);
+        result
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        let timestamp = (self.clock)();
+        let result = self.i2c.write(address, write);
+        self.log(timestamp, address, &[Operation::Write(write)], &result);
+        result
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let timestamp = (self.clock)();
+        let result = self.i2c.write_read(address, write, read);
+        let operations = [Operation::Write(write), Operation::Read(read)];
+        self.log(timestamp, address, &operations, &result);
+        result
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let timestamp = (self.clock)();
+        let result = self.i2c.transaction(address, operations);
+        self.log(timestamp, address, operations, &result);
+        result
+    }
+}
+
+/// Errors returned when replaying a trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// There are no more transactions in the trace.
+    EndOfTrace,
+    /// The transaction does not match the one recorded at this line of the trace.
+    Mismatch(usize),
+    /// This line of the trace could not be parsed.
+    InvalidTrace(usize),
+    /// The recorded transaction failed with this error.
+    Recorded(ErrorKind),
+}
+
+impl i2c::Error for ReplayError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ReplayError::Recorded(kind) => *kind,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// I²C bus replaying a recorded trace
+///
+/// Each transaction must match the next transaction in the trace. The data
+/// read is taken from the trace and the recorded errors are returned.
+/// See the [module documentation](self) for the trace format.
+#[derive(Debug, Clone)]
+pub struct ReplayI2c<'a> {
+    lines: Lines<'a>,
+    line_number: usize,
+}
+
+impl<'a> ReplayI2c<'a> {
+    /// Create a new instance replaying `trace`.
+    pub fn new(trace: &'a str) -> Self {
+        ReplayI2c {
+            lines: trace.lines(),
+            line_number: 0,
+        }
+    }
+
+    /// Return whether all the transactions in the trace have been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.lines.clone().all(is_ignored)
+    }
+
+    fn next_transaction(&mut self) -> Option<(usize, &'a str)> {
+        for line in self.lines.by_ref() {
+            self.line_number += 1;
+            if !is_ignored(line) {
+                return Some((self.line_number, line));
+            }
+        }
+        None
+    }
+}
+
+impl ErrorType for ReplayI2c<'_> {
+    type Error = ReplayError;
+}
+
+impl I2c for ReplayI2c<'_> {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let (line_number, line) = self.next_transaction().ok_or(ReplayError::EndOfTrace)?;
+        let invalid = ReplayError::InvalidTrace(line_number);
+        let mismatch = ReplayError::Mismatch(line_number);
+        let mut fields = line.split_whitespace();
+        fields
+            .next()
+            .and_then(|timestamp| timestamp.parse::<u64>().ok())
+            .ok_or(invalid)?;
+        let recorded_address = fields.next().and_then(parse_byte).ok_or(invalid)?;
+        if recorded_address != address {
+            return Err(mismatch);
+        }
+        let mut field = fields.next();
+        for operation in operations.iter_mut() {
+            match (operation, field) {
+                (Operation::Write(data), Some("W")) => {
+                    for &byte in data.iter() {
+                        let recorded = fields.next().and_then(parse_byte).ok_or(mismatch)?;
+                        if recorded != byte {
+                            return Err(mismatch);
+                        }
+                    }
+                }
+                (Operation::Read(buffer), Some("R")) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = fields.next().and_then(parse_byte).ok_or(mismatch)?;
+                    }
+                }
+                (_, Some("W" | "R")) => return Err(mismatch),
+                _ => return Err(invalid),
+            }
+            field = fields.next();
+            if field.and_then(parse_byte).is_some() {
+                return Err(mismatch);
+            }
+        }
+        match field {
+            None => Ok(()),
+            Some("E") => {
+                let kind = fields.next().and_then(parse_error_name).ok_or(invalid)?;
+                Err(ReplayError::Recorded(kind))
+            }
+            Some("W" | "R") => Err(mismatch),
+            Some(_) => Err(invalid),
+        }
+    }
+}
+
+fn is_ignored(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#')
+}
+
+fn parse_byte(field: &str) -> Option<u8> {
+    if field.len() == 2 {
+        u8::from_str_radix(field, 16).ok()
+    } else {
+        None
+    }
+}
+
+fn get_error_name(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Bus => "bus",
+        ErrorKind::ArbitrationLoss => "arbitration-loss",
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => "nack-address",
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data) => "nack-data",
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown) => "nack",
+        ErrorKind::Overrun => "overrun",
+        _ => "other",
+    }
+}
+
+fn parse_error_name(name: &str) -> Option<ErrorKind> {
+    let kind = match name {
+        "bus" => ErrorKind::Bus,
+        "arbitration-loss" => ErrorKind::ArbitrationLoss,
+        "nack-address" => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        "nack-data" => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        "nack" => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+        "overrun" => ErrorKind::Overrun,
+        "other" => ErrorKind::Other,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bytes() {
+        assert_eq!(Some(0x1D), parse_byte("1D"));
+        assert_eq!(Some(0xAB), parse_byte("ab"));
+        assert_eq!(None, parse_byte("1"));
+        assert_eq!(None, parse_byte("100"));
+        assert_eq!(None, parse_byte("W"));
+    }
+
+    #[test]
+    fn error_names_round_trip() {
+        let kinds = [
+            ErrorKind::Bus,
+            ErrorKind::ArbitrationLoss,
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+            ErrorKind::Overrun,
+            ErrorKind::Other,
+        ];
+        for kind in kinds {
+            assert_eq!(Some(kind), parse_error_name(get_error_name(kind)));
+        }
+        assert_eq!(None, parse_error_name("unknown"));
+    }
+
+    #[test]
+    fn ignores_comments_and_empty_lines() {
+        assert!(is_ignored(""));
+        assert!(is_ignored("  "));
+        assert!(is_ignored("# comment"));
+        assert!(!is_ignored("0 1D W 0D"));
+    }
+}
@@ -2,9 +2,22 @@ pub const MMA845X_BASE_ADDR: u8 = 0x1C;
 
 /// All possible errors in this crate
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error<E> {
     /// I²C bus error
     I2C(E),
+    /// Invalid input data provided
+    InvalidInputData,
+    /// Unexpected device ID (Who am I) read. Contains the device ID read.
+    InvalidDevice(u8),
+    /// The device did not finish its reset in time
+    ResetTimeout,
+    /// The function is not available in the current mode
+    WrongMode,
+    /// The function is not supported by the device
+    Unsupported,
+    /// Interrupt pin error
+    Pin(embedded_hal::digital::ErrorKind),
 }
 
 /// All possible errors in this crate
@@ -16,30 +29,224 @@ pub struct ModeChangeError<E, DEV> {
     pub dev: DEV,
 }
 
-/// IC markers
+/// Error returned when no supported device could be detected
+#[derive(Debug)]
+pub struct DetectionError<I2C> {
+    /// I²C bus given for the detection
+    pub i2c: I2C,
+    /// Unknown device ID (Who am I) read during the detection, if any
+    pub device_id: Option<u8>,
+}
+
+/// IC markers and their capabilities
+///
+/// The functions specific to a feature are only available for the devices
+/// implementing the corresponding capability trait. These traits are sealed
+/// and can be used to write code generic over the devices supporting a feature.
 pub mod ic {
+    use crate::{
+        common::self_test::get_self_test_limits,
+        conversion::{convert_10bit, convert_12bit, convert_14bit},
+        DetectedIc, UnscaledMeasurement,
+    };
+
     /// MMA8451 IC marker
+    #[derive(Debug)]
     pub struct Mma8451;
     /// MMA8452 IC marker
+    #[derive(Debug)]
     pub struct Mma8452;
     /// MMA8453 IC marker
+    #[derive(Debug)]
     pub struct Mma8453;
     /// MMA8652 IC marker
+    #[derive(Debug)]
     pub struct Mma8652;
     /// MMA8653 IC marker
+    #[derive(Debug)]
     pub struct Mma8653;
+
+    pub(crate) mod private {
+        use crate::{DetectedIc, UnscaledMeasurement};
+
+        pub trait Sealed {
+            /// Device model
+            const MODEL: DetectedIc;
+            /// Sensitivity at +/-2g as the base-2 logarithm of counts/g
+            const SENSITIVITY_SHIFT: u8;
+            /// Self-test output change limits (minimum, maximum) of the X, Y and Z axes at +/-2g
+            const SELF_TEST_LIMITS: [(i16, i16); 3];
+
+            /// Convert the raw output data to the device resolution
+            fn convert(x: u16, y: u16, z: u16) -> UnscaledMeasurement;
+        }
+    }
+
+    /// Devices with a 32-sample FIFO buffer (MMA8451, MMA8652)
+    pub trait HasFifo: private::Sealed {}
+
+    /// Devices with transient detection (all but the MMA8653)
+    pub trait HasTransient: private::Sealed {}
+
+    /// Devices with single/double pulse (tap) detection (all but the MMA8653)
+    pub trait HasPulse: private::Sealed {}
+
+    /// Devices with programmable portrait/landscape detection angles (MMA8451, MMA8652)
+    ///
+    /// These devices also allow changing the portrait/landscape debounce
+    /// counter in active mode.
+    pub trait HasProgrammableOrientation: private::Sealed {}
+
+    /// Devices with fixed portrait/landscape detection angles (MMA8452, MMA8453, MMA8653)
+    pub trait HasFixedOrientation: private::Sealed {}
+
+    /// Devices with a selectable I²C address (MMA8451, MMA8452, MMA8453)
+    pub trait HasSelectableAddress: private::Sealed {}
+
+    /// Devices with a high-pass filter (all but the MMA8653)
+    pub trait HasHighPassFilter: private::Sealed {}
+
+    macro_rules! ic_impl {
+        ($ic:ident, $converter:ident $(, $capability:ident)*) => {
+            impl private::Sealed for $ic {
+                const MODEL: DetectedIc = DetectedIc::$ic;
+                const SENSITIVITY_SHIFT: u8 = DetectedIc::$ic.sensitivity_shift();
+                const SELF_TEST_LIMITS: [(i16, i16); 3] = get_self_test_limits(DetectedIc::$ic);
+
+                fn convert(x: u16, y: u16, z: u16) -> UnscaledMeasurement {
+                    $converter(x, y, z)
+                }
+            }
+            $(impl $capability for $ic {})*
+        };
+    }
+
+    ic_impl!(
+        Mma8451,
+        convert_14bit,
+        HasFifo,
+        HasTransient,
+        HasPulse,
+        HasProgrammableOrientation,
+        HasSelectableAddress,
+        HasHighPassFilter
+    );
+    ic_impl!(
+        Mma8452,
+        convert_12bit,
+        HasTransient,
+        HasPulse,
+        HasFixedOrientation,
+        HasSelectableAddress,
+        HasHighPassFilter
+    );
+    ic_impl!(
+        Mma8453,
+        convert_10bit,
+        HasTransient,
+        HasPulse,
+        HasFixedOrientation,
+        HasSelectableAddress,
+        HasHighPassFilter
+    );
+    ic_impl!(
+        Mma8652,
+        convert_12bit,
+        HasFifo,
+        HasTransient,
+        HasPulse,
+        HasProgrammableOrientation,
+        HasHighPassFilter
+    );
+    ic_impl!(Mma8653, convert_10bit, HasFixedOrientation);
+}
+
+/// Device model identified from its device ID (Who am I)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectedIc {
+    /// MMA8451
+    Mma8451,
+    /// MMA8452
+    Mma8452,
+    /// MMA8453
+    Mma8453,
+    /// MMA8652
+    Mma8652,
+    /// MMA8653
+    Mma8653,
+}
+
+impl DetectedIc {
+    /// Identify the device model from its device ID (Who am I)
+    pub fn from_device_id(id: u8) -> Option<Self> {
+        match id {
+            0x1A => Some(DetectedIc::Mma8451),
+            0x2A => Some(DetectedIc::Mma8452),
+            0x3A => Some(DetectedIc::Mma8453),
+            0x4A => Some(DetectedIc::Mma8652),
+            0x5A => Some(DetectedIc::Mma8653),
+            _ => None,
+        }
+    }
+
+    /// Device ID (Who am I) of this device model
+    pub fn device_id(self) -> u8 {
+        match self {
+            DetectedIc::Mma8451 => 0x1A,
+            DetectedIc::Mma8452 => 0x2A,
+            DetectedIc::Mma8453 => 0x3A,
+            DetectedIc::Mma8652 => 0x4A,
+            DetectedIc::Mma8653 => 0x5A,
+        }
+    }
+
+    /// Sensitivity at +/-2g as the base-2 logarithm of counts/g
+    pub(crate) const fn sensitivity_shift(self) -> u8 {
+        match self {
+            DetectedIc::Mma8451 => 12,
+            DetectedIc::Mma8452 | DetectedIc::Mma8652 => 10,
+            DetectedIc::Mma8453 | DetectedIc::Mma8653 => 8,
+        }
+    }
+
+    /// Whether this device model has a FIFO buffer
+    pub(crate) fn has_fifo(self) -> bool {
+        matches!(self, DetectedIc::Mma8451 | DetectedIc::Mma8652)
+    }
+
+    /// Whether this device model has a high-pass filter as well as
+    /// transient and pulse detection
+    pub(crate) fn has_high_pass_filter(self) -> bool {
+        self != DetectedIc::Mma8653
+    }
+
+    /// Whether this device model has programmable portrait/landscape detection angles
+    pub(crate) fn has_programmable_orientation(self) -> bool {
+        matches!(self, DetectedIc::Mma8451 | DetectedIc::Mma8652)
+    }
+}
+
+/// Current mode of an [`AnyMma8x5x`](crate::AnyMma8x5x) driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriverMode {
+    /// Standby mode
+    Standby,
+    /// Active mode
+    Active,
 }
 
 /// Mode markers
 pub mod mode {
     /// Standby mode
+    #[derive(Debug)]
     pub struct Standby;
     /// Active mode
+    #[derive(Debug)]
     pub struct Active;
 }
 
 /// Unscaled acceleration measurement
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct UnscaledMeasurement {
     /// X-axis acceleration.
     pub x: i16,
@@ -49,6 +256,29 @@ pub struct UnscaledMeasurement {
     pub z: i16,
 }
 
+/// Acceleration measurement in milli-g
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MilliGMeasurement {
+    /// X-axis acceleration.
+    pub x: i16,
+    /// Y-axis acceleration.
+    pub y: i16,
+    /// Z-axis acceleration.
+    pub z: i16,
+}
+
+/// Acceleration measurement scaled to G in fixed-point
+#[cfg(feature = "fixed")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FixedMeasurement {
+    /// X-axis acceleration.
+    pub x: fixed::types::I16F16,
+    /// Y-axis acceleration.
+    pub y: fixed::types::I16F16,
+    /// Z-axis acceleration.
+    pub z: fixed::types::I16F16,
+}
+
 /// Acceleration measurement scaled to configured G range
 #[derive(Debug, Default, Clone)]
 pub struct Measurement {
@@ -127,6 +357,17 @@ pub enum PowerMode {
     LowPower,
 }
 
+/// Current system status (only MMA8451 and MMA8652)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemStatus {
+    /// Current system mode
+    pub mode: SystemMode,
+    /// A wake/sleep transition occurred while the FIFO gate was blocking the FIFO input
+    pub fifo_gate_error: bool,
+    /// FIFO gate time: number of ODR time units since `fifo_gate_error` was set [0-31]
+    pub fifo_gate_time: u8,
+}
+
 /// Current system mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SystemMode {
@@ -168,6 +409,105 @@ pub enum DebounceCounterMode {
     Clear,
 }
 
+/// Back/front orientation trip angle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackFrontTripAngle {
+    /// Back to front transition at Z < 80° or Z > 280°. Front to back transition at 100° < Z < 260°.
+    Deg80,
+    /// Back to front transition at Z < 75° or Z > 285°. Front to back transition at 105° < Z < 255°. (default)
+    #[default]
+    Deg75,
+    /// Back to front transition at Z < 70° or Z > 290°. Front to back transition at 110° < Z < 250°.
+    Deg70,
+    /// Back to front transition at Z < 65° or Z > 295°. Front to back transition at 115° < Z < 245°.
+    Deg65,
+}
+
+/// Z-tilt lockout angle
+///
+/// Below this angle, portrait/landscape orientation changes are not detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ZLockoutAngle {
+    /// 14°
+    Deg14,
+    /// 18°
+    Deg18,
+    /// 21°
+    Deg21,
+    /// 25°
+    Deg25,
+    /// 29° (default)
+    #[default]
+    Deg29,
+    /// 33°
+    Deg33,
+    /// 37°
+    Deg37,
+    /// 42°
+    Deg42,
+}
+
+/// Portrait/landscape trip threshold angle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PortraitLandscapeThreshold {
+    /// 15°
+    Deg15,
+    /// 20°
+    Deg20,
+    /// 30°
+    Deg30,
+    /// 35°
+    Deg35,
+    /// 40°
+    Deg40,
+    /// 45° (default)
+    #[default]
+    Deg45,
+    /// 55°
+    Deg55,
+    /// 60°
+    Deg60,
+    /// 70°
+    Deg70,
+    /// 75°
+    Deg75,
+}
+
+/// Portrait/landscape trip hysteresis angle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PortraitLandscapeHysteresis {
+    /// +/-0°
+    Deg0,
+    /// +/-4°
+    Deg4,
+    /// +/-7°
+    Deg7,
+    /// +/-11°
+    Deg11,
+    /// +/-14° (default)
+    #[default]
+    Deg14,
+    /// +/-17°
+    Deg17,
+    /// +/-21°
+    Deg21,
+    /// +/-24°
+    Deg24,
+}
+
+/// Programmable portrait/landscape orientation detection angles
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PortraitLandscapeAngles {
+    /// Back/front trip angle
+    pub back_front_trip: BackFrontTripAngle,
+    /// Z-tilt lockout angle
+    pub z_lockout: ZLockoutAngle,
+    /// Portrait/landscape trip threshold angle
+    pub threshold: PortraitLandscapeThreshold,
+    /// Portrait/landscape trip hysteresis angle
+    pub hysteresis: PortraitLandscapeHysteresis,
+}
+
 /// Current portrait/landscape status
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct PortraitLandscapeStatus {
@@ -224,6 +564,87 @@ pub struct InterruptStatus {
     pub data_ready: bool,
 }
 
+/// Interrupt event
+///
+/// Contains the content of the source register read while servicing the interrupt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Auto-sleep/wake transition. Contains the new system mode.
+    AutoSleep(SystemMode),
+    /// FIFO overflow or watermark
+    Fifo(FifoStatus),
+    /// Transient detected
+    Transient(TransientSource),
+    /// Portrait/landscape orientation change
+    PortraitLandscape(PortraitLandscapeStatus),
+    /// Single and/or double pulse (tap) detected
+    Pulse(PulseSource),
+    /// Freefall/motion detected
+    FreefallMotion(FfMtSource),
+    /// New data ready. Contains the unscaled measurement.
+    DataReady(UnscaledMeasurement),
+}
+
+/// Acceleration axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// X axis
+    X,
+    /// Y axis
+    Y,
+    /// Z axis
+    Z,
+}
+
+/// Axes on which an event was detected
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EventAxes {
+    /// Event detected on the X axis
+    pub x: bool,
+    /// Event detected on the Y axis
+    pub y: bool,
+    /// Event detected on the Z axis
+    pub z: bool,
+}
+
+/// Event decoded from the interrupt source registers
+///
+/// See [`next_events()`](struct.Mma8x5x.html#method.next_events).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodedEvent {
+    /// Auto-sleep/wake transition. Contains the new system mode.
+    SleepWakeTransition(SystemMode),
+    /// FIFO sample count reached the watermark. Contains the sample count.
+    FifoWatermark(u8),
+    /// FIFO overflow. Contains the sample count.
+    FifoOverflow(u8),
+    /// Transient detected
+    Transient {
+        /// Axes on which the transient was detected
+        axes: EventAxes,
+    },
+    /// Portrait/landscape orientation change
+    OrientationChanged(PortraitLandscapeStatus),
+    /// Single or double pulse (tap) detected
+    Tap {
+        /// Axis on which the pulse was detected
+        axis: Axis,
+        /// The pulse was a double pulse
+        double: bool,
+        /// Pulse polarity
+        polarity: EventPolarity,
+    },
+    /// Freefall detected
+    Freefall,
+    /// Motion detected
+    Motion {
+        /// Axes on which the motion was detected
+        axes: EventAxes,
+    },
+    /// New data ready. Contains the unscaled measurement.
+    DataReady(UnscaledMeasurement),
+}
+
 /// Interrupt source enable/disable
 ///
 /// Unavailable interrupt sources on a device are ignored.
@@ -308,6 +729,480 @@ pub struct WakeInterrupts {
     pub freefall_motion: bool,
 }
 
+/// FIFO operating mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FifoMode {
+    /// FIFO is disabled (default)
+    #[default]
+    Disabled,
+    /// FIFO contains the most recent samples when overflowed (circular buffer)
+    Circular,
+    /// FIFO stops accepting new samples when overflowed
+    StopOnOverflow,
+    /// FIFO contains the samples before the trigger event and keeps accepting
+    /// new samples until it is full
+    Trigger,
+}
+
+/// Current FIFO status
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FifoStatus {
+    /// FIFO overflow event detected
+    pub overflow: bool,
+    /// FIFO sample count is greater than or equal to the watermark
+    pub watermark: bool,
+    /// Number of samples stored in the FIFO
+    pub count: u8,
+}
+
+/// Interrupt sources that trigger the FIFO in trigger mode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FifoTriggerSources {
+    /// Acceleration transient interrupt
+    pub transient: bool,
+    /// Portrait/landscape orientation interrupt
+    pub portrait_landscape: bool,
+    /// Single and/or double pulse detection interrupt
+    pub pulse: bool,
+    /// Freefall/motion interrupt
+    pub freefall_motion: bool,
+}
+
+/// Acceleration event polarity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventPolarity {
+    /// Positive acceleration event (default)
+    #[default]
+    Positive,
+    /// Negative acceleration event
+    Negative,
+}
+
+/// Freefall/motion detection mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FreefallMotionMode {
+    /// Freefall detection (default)
+    ///
+    /// An event is detected when the acceleration on all the enabled axes
+    /// is below the threshold (AND logic).
+    #[default]
+    Freefall,
+    /// Motion detection
+    ///
+    /// An event is detected when the acceleration on any of the enabled axes
+    /// is above the threshold (OR logic).
+    Motion,
+}
+
+/// Freefall/motion detection configuration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FreefallMotionConfig {
+    /// Freefall or motion detection
+    pub mode: FreefallMotionMode,
+    /// Enable event detection on the X axis
+    pub x_axis: bool,
+    /// Enable event detection on the Y axis
+    pub y_axis: bool,
+    /// Enable event detection on the Z axis
+    pub z_axis: bool,
+    /// Latch events into the freefall/motion source register until it is read
+    pub latch: bool,
+    /// Acceleration threshold in g [0.0-8.0]
+    ///
+    /// The resolution is 0.063g/LSB.
+    pub threshold: f32,
+    /// Debounce counter mode
+    pub debounce_counter_mode: DebounceCounterMode,
+    /// Minimum duration of the event in milliseconds.
+    ///
+    /// The resolution and range depend on the output data rate and power mode
+    /// configured at the time of setting this configuration. (e.g. 1.25ms/LSB at 800Hz)
+    pub debounce_time_ms: f32,
+}
+
+impl Default for FreefallMotionConfig {
+    fn default() -> Self {
+        FreefallMotionConfig {
+            mode: FreefallMotionMode::Freefall,
+            x_axis: false,
+            y_axis: false,
+            z_axis: false,
+            latch: false,
+            threshold: 0.0,
+            debounce_counter_mode: DebounceCounterMode::Decrement,
+            debounce_time_ms: 0.0,
+        }
+    }
+}
+
+/// Freefall/motion detection source
+///
+/// The event polarities are not available on the MMA8653.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FfMtSource {
+    /// One or more events have been detected
+    pub event_active: bool,
+    /// X-axis motion event detected
+    pub x_event: bool,
+    /// X-axis motion event polarity
+    pub x_polarity: EventPolarity,
+    /// Y-axis motion event detected
+    pub y_event: bool,
+    /// Y-axis motion event polarity
+    pub y_polarity: EventPolarity,
+    /// Z-axis motion event detected
+    pub z_event: bool,
+    /// Z-axis motion event polarity
+    pub z_polarity: EventPolarity,
+}
+
+/// Transient detection configuration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransientConfig {
+    /// Enable transient detection on the X axis
+    pub x_axis: bool,
+    /// Enable transient detection on the Y axis
+    pub y_axis: bool,
+    /// Enable transient detection on the Z axis
+    pub z_axis: bool,
+    /// Latch events into the transient source register until it is read
+    pub latch: bool,
+    /// Bypass the high-pass filter. Events are then detected on the low-pass filtered data.
+    pub bypass_high_pass_filter: bool,
+    /// Acceleration threshold in g [0.0-8.0]
+    ///
+    /// The resolution is 0.063g/LSB.
+    pub threshold: f32,
+    /// Debounce counter mode
+    pub debounce_counter_mode: DebounceCounterMode,
+    /// Minimum duration of the event in milliseconds.
+    ///
+    /// The resolution and range depend on the output data rate and power mode
+    /// configured at the time of setting this configuration. (e.g. 1.25ms/LSB at 800Hz)
+    pub debounce_time_ms: f32,
+}
+
+impl Default for TransientConfig {
+    fn default() -> Self {
+        TransientConfig {
+            x_axis: false,
+            y_axis: false,
+            z_axis: false,
+            latch: false,
+            bypass_high_pass_filter: false,
+            threshold: 0.0,
+            debounce_counter_mode: DebounceCounterMode::Decrement,
+            debounce_time_ms: 0.0,
+        }
+    }
+}
+
+/// Transient detection source
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TransientSource {
+    /// One or more events have been detected
+    pub event_active: bool,
+    /// X-axis transient event detected
+    pub x_event: bool,
+    /// X-axis transient event polarity
+    pub x_polarity: EventPolarity,
+    /// Y-axis transient event detected
+    pub y_event: bool,
+    /// Y-axis transient event polarity
+    pub y_polarity: EventPolarity,
+    /// Z-axis transient event detected
+    pub z_event: bool,
+    /// Z-axis transient event polarity
+    pub z_polarity: EventPolarity,
+}
+
+/// Pulse (tap) detection configuration
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PulseConfig {
+    /// Enable single pulse detection on the X axis
+    pub x_single: bool,
+    /// Enable double pulse detection on the X axis
+    pub x_double: bool,
+    /// Enable single pulse detection on the Y axis
+    pub y_single: bool,
+    /// Enable double pulse detection on the Y axis
+    pub y_double: bool,
+    /// Enable single pulse detection on the Z axis
+    pub z_single: bool,
+    /// Enable double pulse detection on the Z axis
+    pub z_double: bool,
+    /// Latch events into the pulse source register until it is read
+    pub latch: bool,
+    /// Suspend double pulse detection if the start of a pulse is detected
+    /// during the latency time and the pulse ends before the end of it
+    pub double_pulse_abort: bool,
+    /// X-axis pulse threshold in g [0.0-8.0]
+    ///
+    /// The resolution is 0.063g/LSB.
+    pub x_threshold: f32,
+    /// Y-axis pulse threshold in g [0.0-8.0]
+    ///
+    /// The resolution is 0.063g/LSB.
+    pub y_threshold: f32,
+    /// Z-axis pulse threshold in g [0.0-8.0]
+    ///
+    /// The resolution is 0.063g/LSB.
+    pub z_threshold: f32,
+    /// Maximum time in milliseconds that the acceleration may stay above
+    /// the threshold for a pulse to be detected.
+    ///
+    /// The resolution and range depend on the output data rate, power mode and
+    /// pulse low-pass filter setting configured at the time of setting this
+    /// configuration. (e.g. 0.625ms/LSB at 800Hz)
+    pub time_limit_ms: f32,
+    /// Time in milliseconds after a detected pulse during which no other pulse
+    /// is detected.
+    ///
+    /// The resolution is twice the one of the time limit.
+    pub latency_ms: f32,
+    /// Maximum time in milliseconds after the latency time during which the second
+    /// pulse of a double pulse must be detected.
+    ///
+    /// The resolution is twice the one of the time limit.
+    pub window_ms: f32,
+}
+
+/// Pulse (tap) detection source
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PulseSource {
+    /// One or more events have been detected
+    pub event_active: bool,
+    /// The detected event was a double pulse
+    pub double_pulse: bool,
+    /// X-axis pulse event detected
+    pub x_event: bool,
+    /// X-axis pulse event polarity
+    pub x_polarity: EventPolarity,
+    /// Y-axis pulse event detected
+    pub y_event: bool,
+    /// Y-axis pulse event polarity
+    pub y_polarity: EventPolarity,
+    /// Z-axis pulse event detected
+    pub z_event: bool,
+    /// Z-axis pulse event polarity
+    pub z_polarity: EventPolarity,
+}
+
+/// High-pass filter cutoff frequency selection
+///
+/// The actual cutoff frequency depends on the output data rate and power mode.
+/// The frequencies given here correspond to 800Hz in normal mode.
+/// See [`HighPassFilterCutoff::frequency_hz()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HighPassFilterCutoff {
+    /// Highest cutoff frequency: 16Hz at 800Hz (default)
+    #[default]
+    Highest,
+    /// High cutoff frequency: 8Hz at 800Hz
+    High,
+    /// Low cutoff frequency: 4Hz at 800Hz
+    Low,
+    /// Lowest cutoff frequency: 2Hz at 800Hz
+    Lowest,
+}
+
+impl HighPassFilterCutoff {
+    /// Cutoff frequency in Hz for the given output data rate and power mode
+    pub fn frequency_hz(self, rate: OutputDataRate, power_mode: PowerMode) -> f32 {
+        let highest = match (power_mode, rate) {
+            (PowerMode::HighResolution, _) => 16.0,
+            (_, OutputDataRate::Hz800) => 16.0,
+            (PowerMode::LowPower, OutputDataRate::Hz400) => 8.0,
+            (PowerMode::LowPower, OutputDataRate::Hz200) => 4.0,
+            (PowerMode::LowPower, OutputDataRate::Hz100) => 2.0,
+            (PowerMode::LowPower, OutputDataRate::Hz50) => 1.0,
+            (PowerMode::LowPower, _) => 0.25,
+            (_, OutputDataRate::Hz400) => 16.0,
+            (_, OutputDataRate::Hz200) => 8.0,
+            (_, OutputDataRate::Hz100) => 4.0,
+            (PowerMode::LowNoiseLowPower, OutputDataRate::Hz50) => 2.0,
+            (PowerMode::LowNoiseLowPower, _) => 0.5,
+            (_, _) => 2.0,
+        };
+        match self {
+            HighPassFilterCutoff::Highest => highest,
+            HighPassFilterCutoff::High => highest / 2.0,
+            HighPassFilterCutoff::Low => highest / 4.0,
+            HighPassFilterCutoff::Lowest => highest / 8.0,
+        }
+    }
+}
+
+/// High-pass filter configuration
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HighPassConfig {
+    /// Output high-pass filtered acceleration data
+    pub output_enabled: bool,
+    /// Cutoff frequency selection
+    pub cutoff: HighPassFilterCutoff,
+    /// Bypass the high-pass filter for pulse processing
+    pub pulse_hpf_bypass: bool,
+    /// Enable the low-pass filter for pulse processing
+    pub pulse_lpf_enabled: bool,
+}
+
+/// Orientation of the device during the offset calibration
+///
+/// Indicates the axis pointing up, away from the earth.
+/// This axis measures +1g when the device is at rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationOrientation {
+    /// X axis pointing up
+    XUp,
+    /// X axis pointing down
+    XDown,
+    /// Y axis pointing up
+    YUp,
+    /// Y axis pointing down
+    YDown,
+    /// Z axis pointing up (device lying flat, face up)
+    ZUp,
+    /// Z axis pointing down (device lying flat, face down)
+    ZDown,
+}
+
+/// Offset calibration result
+#[derive(Debug, Default, Clone)]
+pub struct OffsetCalibration {
+    /// X-axis offset correction written
+    pub x: i8,
+    /// Y-axis offset correction written
+    pub y: i8,
+    /// Z-axis offset correction written
+    pub z: i8,
+    /// Remaining error in G after applying the offset correction
+    ///
+    /// This is the rounding error or, if the error exceeds the offset
+    /// compensation range, the part that could not be compensated.
+    pub residual: Measurement,
+}
+
+/// Device configuration
+///
+/// Snapshot of the configuration registers covering FIFO, G scale, high-pass
+/// filter, output data rate, power modes, auto-sleep, interrupts, interrupt pin
+/// routes, portrait/landscape, freefall/motion, transient and pulse detection
+/// and offset correction.
+///
+/// The registers a device does not have are neither read nor written and
+/// keep their reset value here.
+/// The general settings can be inspected and changed with typed accessors
+/// like [`data_rate()`](#method.data_rate) and
+/// [`set_data_rate()`](#method.set_data_rate). The event detection settings
+/// depend on the data rate and are configured through the driver.
+///
+/// Read it with [`read_config()`](struct.Mma8x5x.html#method.read_config) and
+/// apply it with [`apply_config()`](struct.Mma8x5x.html#method.apply_config).
+///
+/// It can be stored in a fixed-size binary form with
+/// [`to_bytes()`](#method.to_bytes). With the `serde` feature it also
+/// implements `Serialize` and `Deserialize`.
+///
+/// The default value corresponds to the device configuration after reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceConfig {
+    /// F_SETUP register: FIFO mode and watermark
+    pub(crate) f_setup: u8,
+    /// TRIG_CFG register: FIFO trigger sources
+    pub(crate) trig_cfg: u8,
+    /// XYZ_DATA_CFG register: G scale and high-pass filtered output
+    pub(crate) xyz_data_cfg: u8,
+    /// HP_FILTER_CUTOFF register: high-pass filter cutoff and pulse filters
+    pub(crate) hp_filter_cutoff: u8,
+    /// PL_CFG register: portrait/landscape detection and debounce counter mode
+    pub(crate) pl_cfg: u8,
+    /// PL_COUNT register: portrait/landscape debounce counter
+    pub(crate) pl_count: u8,
+    /// PL_BF_ZCOMP register: back/front trip and Z-lockout angles
+    pub(crate) pl_bf_zcomp: u8,
+    /// P_L_THS_REG register: portrait/landscape threshold and hysteresis angles
+    pub(crate) pl_ths: u8,
+    /// FF_MT_CFG register: freefall/motion detection configuration
+    pub(crate) ff_mt_cfg: u8,
+    /// FF_MT_THS register: freefall/motion threshold and debounce counter mode
+    pub(crate) ff_mt_ths: u8,
+    /// FF_MT_COUNT register: freefall/motion debounce count
+    pub(crate) ff_mt_count: u8,
+    /// TRANSIENT_CFG register: transient detection configuration
+    pub(crate) transient_cfg: u8,
+    /// TRANSIENT_THS register: transient threshold and debounce counter mode
+    pub(crate) transient_ths: u8,
+    /// TRANSIENT_COUNT register: transient debounce count
+    pub(crate) transient_count: u8,
+    /// PULSE_CFG register: pulse detection configuration
+    pub(crate) pulse_cfg: u8,
+    /// PULSE_THSX register: X-axis pulse threshold
+    pub(crate) pulse_thsx: u8,
+    /// PULSE_THSY register: Y-axis pulse threshold
+    pub(crate) pulse_thsy: u8,
+    /// PULSE_THSZ register: Z-axis pulse threshold
+    pub(crate) pulse_thsz: u8,
+    /// PULSE_TMLT register: pulse time limit
+    pub(crate) pulse_tmlt: u8,
+    /// PULSE_LTCY register: pulse latency
+    pub(crate) pulse_ltcy: u8,
+    /// PULSE_WIND register: second pulse time window
+    pub(crate) pulse_wind: u8,
+    /// ASLP_COUNT register: auto-sleep count
+    pub(crate) aslp_count: u8,
+    /// CTRL_REG1 register: data rates and read mode (active bit excluded)
+    pub(crate) ctrl_reg1: u8,
+    /// CTRL_REG2 register: power modes and auto-sleep (self-test and reset bits excluded)
+    pub(crate) ctrl_reg2: u8,
+    /// CTRL_REG3 register: FIFO gate, wake interrupts and interrupt pin configuration
+    pub(crate) ctrl_reg3: u8,
+    /// CTRL_REG4 register: enabled interrupts
+    pub(crate) ctrl_reg4: u8,
+    /// CTRL_REG5 register: interrupt pin routes
+    pub(crate) ctrl_reg5: u8,
+    /// X-axis offset correction
+    pub(crate) offset_x: i8,
+    /// Y-axis offset correction
+    pub(crate) offset_y: i8,
+    /// Z-axis offset correction
+    pub(crate) offset_z: i8,
+}
+
+/// Self-test result of one axis
+///
+/// Values in counts at +/-2g and the full resolution of the device.
+/// The datasheets only give a typical output change, so the limits are
+/// heuristic bounds of half and twice the typical value.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SelfTestAxis {
+    /// Averaged output change between self-test enabled and disabled
+    pub delta: i16,
+    /// Minimum accepted output change
+    pub min: i16,
+    /// Maximum accepted output change
+    pub max: i16,
+    /// The output change is within the accepted limits
+    pub passed: bool,
+}
+
+/// Self-test report
+///
+/// The axes are checked against heuristic limits derived from the typical
+/// datasheet values, see [`SelfTestAxis`]. A failed axis indicates a likely
+/// defect but is no guarantee.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SelfTestReport {
+    /// X-axis result
+    pub x: SelfTestAxis,
+    /// Y-axis result
+    pub y: SelfTestAxis,
+    /// Z-axis result
+    pub z: SelfTestAxis,
+    /// All axes passed
+    pub passed: bool,
+}
+
 /// Possible slave addresses
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SlaveAddr {
@@ -332,12 +1227,88 @@ mod tests {
     use super::MMA845X_BASE_ADDR as BASE_ADDR;
     use super::*;
 
+    #[test]
+    fn can_get_hpf_cutoff_frequency() {
+        let f =
+            |cutoff: HighPassFilterCutoff, rate, power_mode| cutoff.frequency_hz(rate, power_mode);
+        assert_eq!(
+            16.0,
+            f(
+                HighPassFilterCutoff::Highest,
+                OutputDataRate::Hz800,
+                PowerMode::Normal
+            )
+        );
+        assert_eq!(
+            2.0,
+            f(
+                HighPassFilterCutoff::Lowest,
+                OutputDataRate::Hz800,
+                PowerMode::Normal
+            )
+        );
+        assert_eq!(
+            0.5,
+            f(
+                HighPassFilterCutoff::Lowest,
+                OutputDataRate::Hz100,
+                PowerMode::Normal
+            )
+        );
+        assert_eq!(
+            16.0,
+            f(
+                HighPassFilterCutoff::Highest,
+                OutputDataRate::Hz1_56,
+                PowerMode::HighResolution
+            )
+        );
+        assert_eq!(
+            0.0625,
+            f(
+                HighPassFilterCutoff::Lowest,
+                OutputDataRate::Hz12_5,
+                PowerMode::LowNoiseLowPower
+            )
+        );
+        assert_eq!(
+            0.03125,
+            f(
+                HighPassFilterCutoff::Lowest,
+                OutputDataRate::Hz6_25,
+                PowerMode::LowPower
+            )
+        );
+        assert_eq!(
+            0.25,
+            f(
+                HighPassFilterCutoff::Lowest,
+                OutputDataRate::Hz50,
+                PowerMode::Normal
+            )
+        );
+    }
+
     #[test]
     fn can_get_default_address() {
         let addr = SlaveAddr::default();
         assert_eq!(BASE_ADDR, addr.addr(BASE_ADDR));
     }
 
+    #[test]
+    fn can_identify_ic_from_device_id() {
+        for ic in [
+            DetectedIc::Mma8451,
+            DetectedIc::Mma8452,
+            DetectedIc::Mma8453,
+            DetectedIc::Mma8652,
+            DetectedIc::Mma8653,
+        ] {
+            assert_eq!(Some(ic), DetectedIc::from_device_id(ic.device_id()));
+        }
+        assert_eq!(None, DetectedIc::from_device_id(0));
+    }
+
     #[test]
     fn can_generate_alternative_addresses() {
         assert_eq!(BASE_ADDR, SlaveAddr::Alternative(false).addr(BASE_ADDR));
@@ -0,0 +1,244 @@
+mod base;
+use crate::base::{
+    new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF, Register,
+    ADDRESS,
+};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use mma8x5x::{
+    ic, mode, AnyMma8x5x, DetectedIc, DriverMode, Error, FifoMode, GScale, Mma8x5x, OutputDataRate,
+    TransientConfig, UnscaledMeasurement,
+};
+
+fn destroy(sensor: AnyMma8x5x<I2cMock>) {
+    sensor.destroy().done();
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $ic:ident, $bit_shift:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_create_from_typed_driver() {
+                let sensor = AnyMma8x5x::from($create(&[]));
+                assert_eq!(DetectedIc::$ic, sensor.ic());
+                assert_eq!(DriverMode::Standby, sensor.mode());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_change_mode() {
+                let sensor = AnyMma8x5x::from($create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0]),
+                ]));
+                let sensor = sensor.into_active().ok().unwrap();
+                assert_eq!(DriverMode::Active, sensor.mode());
+                let sensor = sensor.into_standby().ok().unwrap();
+                assert_eq!(DriverMode::Standby, sensor.mode());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn keeps_data_rate_across_mode_changes() {
+                let mut sensor = AnyMma8x5x::from($create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ODR2]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ODR2 | 1]),
+                ]));
+                assert_eq!(OutputDataRate::Hz800, sensor.data_rate());
+                sensor.set_data_rate(OutputDataRate::Hz50).unwrap();
+                let sensor = sensor.into_active().ok().unwrap();
+                assert_eq!(OutputDataRate::Hz50, sensor.data_rate());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_activate_then_reset() {
+                let sensor = AnyMma8x5x::from($create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::RST]),
+                ]));
+                let sensor = sensor.into_active().ok().unwrap();
+                let sensor = sensor.reset().ok().unwrap();
+                assert_eq!(DriverMode::Standby, sensor.mode());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_unscaled_in_active_mode() {
+                let sensor = AnyMma8x5x::from($create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]));
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let expected = UnscaledMeasurement {
+                    x: 0x4140 >> $bit_shift,
+                    y: 0x4280 >> $bit_shift,
+                    z: 0x43C0 >> $bit_shift,
+                };
+                assert_eq!(expected, sensor.read_unscaled().unwrap());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_next_events_in_active_mode() {
+                let sensor = AnyMma8x5x::from($create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![0]),
+                ]));
+                let mut sensor = sensor.into_active().ok().unwrap();
+                assert_eq!(None, sensor.next_events().unwrap().next());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn cannot_read_in_standby_mode() {
+                let mut sensor = AnyMma8x5x::from($create(&[]));
+                assert!(matches!(sensor.read(), Err(Error::WrongMode)));
+                destroy(sensor);
+            }
+
+            #[test]
+            fn cannot_configure_in_active_mode() {
+                let sensor = AnyMma8x5x::from($create(&[I2cTrans::write(
+                    ADDRESS,
+                    vec![Register::CTRL_REG1, 1],
+                )]));
+                let mut sensor = sensor.into_active().ok().unwrap();
+                assert!(matches!(
+                    sensor.set_scale(GScale::G4),
+                    Err(Error::WrongMode)
+                ));
+                destroy(sensor);
+            }
+
+            #[test]
+            fn cannot_change_into_current_mode() {
+                let sensor = AnyMma8x5x::from($create(&[]));
+                let err = sensor.into_standby().err().unwrap();
+                assert!(matches!(err.error, Error::WrongMode));
+                assert_eq!(DriverMode::Standby, err.dev.mode());
+                destroy(err.dev);
+            }
+
+            #[test]
+            fn can_read_device_id_in_any_mode() {
+                let id = DetectedIc::$ic.device_id();
+                let mut sensor = AnyMma8x5x::from($create(&[
+                    I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![id]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![id]),
+                ]));
+                assert_eq!(id, sensor.device_id().unwrap());
+                let mut sensor = sensor.into_active().ok().unwrap();
+                assert_eq!(id, sensor.device_id().unwrap());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_convert_into_typed_driver() {
+                let sensor = AnyMma8x5x::from($create(&[]));
+                let sensor: Mma8x5x<_, ic::$ic, mode::Standby> = sensor.try_into().unwrap();
+                sensor.destroy().done();
+            }
+
+            #[test]
+            fn conversion_into_wrong_mode_returns_driver() {
+                let sensor = AnyMma8x5x::from($create(&[]));
+                let result: Result<Mma8x5x<_, ic::$ic, mode::Active>, _> = sensor.try_into();
+                destroy(result.err().unwrap());
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, Mma8451, 2);
+tests!(mma8452, new_mma8452, Mma8452, 4);
+tests!(mma8453, new_mma8453, Mma8453, 6);
+tests!(mma8652, new_mma8652, Mma8652, 4);
+tests!(mma8653, new_mma8653, Mma8653, 6);
+
+#[test]
+fn can_detect() {
+    let i2c = I2cMock::new(&[I2cTrans::write_read(
+        0x1C,
+        vec![Register::WHO_AM_I],
+        vec![0x2A],
+    )]);
+    let sensor = AnyMma8x5x::detect(i2c).unwrap();
+    assert_eq!(DetectedIc::Mma8452, sensor.ic());
+    assert_eq!(DriverMode::Standby, sensor.mode());
+    assert_eq!(0x1C, sensor.address());
+    destroy(sensor);
+}
+
+#[test]
+fn detect_returns_address_of_supported_device() {
+    let i2c = I2cMock::new(&[
+        I2cTrans::write_read(0x1C, vec![Register::WHO_AM_I], vec![0x33]),
+        I2cTrans::write_read(0x1D, vec![Register::WHO_AM_I], vec![0x4A]),
+    ]);
+    let sensor = AnyMma8x5x::detect(i2c).unwrap();
+    assert_eq!(DetectedIc::Mma8652, sensor.ic());
+    assert_eq!(0x1D, sensor.address());
+    destroy(sensor);
+}
+
+#[test]
+fn conversion_into_wrong_device_returns_driver() {
+    let sensor = AnyMma8x5x::from(new_mma8652(&[]));
+    let result: Result<Mma8x5x<_, ic::Mma8653, mode::Standby>, _> = sensor.try_into();
+    destroy(result.err().unwrap());
+}
+
+#[test]
+fn unsupported_features_return_error() {
+    let mut sensor = AnyMma8x5x::from(new_mma8653(&[]));
+    assert!(matches!(
+        sensor.set_transient_config(TransientConfig::default()),
+        Err(Error::Unsupported)
+    ));
+    assert!(matches!(
+        sensor.set_fifo_mode(FifoMode::Circular),
+        Err(Error::Unsupported)
+    ));
+    assert!(matches!(sensor.pulse_source(), Err(Error::Unsupported)));
+    assert!(matches!(sensor.system_status(), Err(Error::Unsupported)));
+    destroy(sensor);
+}
+
+#[test]
+fn can_use_fifo_on_supported_device() {
+    let mut sensor = AnyMma8x5x::from(new_mma8652(&[I2cTrans::write(
+        ADDRESS,
+        vec![Register::F_SETUP, BF::F_MODE0],
+    )]));
+    sensor.set_fifo_mode(FifoMode::Circular).unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn can_set_debounce_counter_in_active_mode_only_where_allowed() {
+    let sensor = AnyMma8x5x::from(new_mma8652(&[
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+        I2cTrans::write(ADDRESS, vec![Register::PL_COUNT, 5]),
+    ]));
+    let mut sensor = sensor.into_active().ok().unwrap();
+    sensor.set_debounce_counter(5).unwrap();
+    destroy(sensor);
+
+    let sensor = AnyMma8x5x::from(new_mma8452(&[I2cTrans::write(
+        ADDRESS,
+        vec![Register::CTRL_REG1, 1],
+    )]));
+    let mut sensor = sensor.into_active().ok().unwrap();
+    assert!(matches!(
+        sensor.set_debounce_counter(5),
+        Err(Error::WrongMode)
+    ));
+    destroy(sensor);
+}
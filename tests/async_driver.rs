@@ -0,0 +1,548 @@
+#![cfg(feature = "async")]
+
+mod base;
+use crate::base::{BitFlags as BF, Register, ADDRESS};
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTrans},
+    digital::{Mock as PinMock, State as PinState, Transaction as PinTrans},
+    i2c::{Mock as I2cMock, Transaction as I2cTrans},
+};
+use mma8x5x::{
+    ic, mode, CalibrationOrientation, DataStatus, DecodedEvent, DeviceConfig, EnabledInterrupts,
+    Error, Event, FifoMode, GScale, Int2, InterruptPinPolarity, InterruptStatus, MilliGMeasurement,
+    Mma8x5xAsync, OutputDataRate, SlaveAddr, SystemMode, SystemStatus, TransientConfig,
+    UnscaledMeasurement,
+};
+
+fn new_mma8451(transactions: &[I2cTrans]) -> Mma8x5xAsync<I2cMock, ic::Mma8451, mode::Standby> {
+    Mma8x5xAsync::new_mma8451(I2cMock::new(transactions), SlaveAddr::Alternative(true))
+}
+
+fn new_mma8452(transactions: &[I2cTrans]) -> Mma8x5xAsync<I2cMock, ic::Mma8452, mode::Standby> {
+    Mma8x5xAsync::new_mma8452(I2cMock::new(transactions), SlaveAddr::Alternative(true))
+}
+
+fn new_mma8453(transactions: &[I2cTrans]) -> Mma8x5xAsync<I2cMock, ic::Mma8453, mode::Standby> {
+    Mma8x5xAsync::new_mma8453(I2cMock::new(transactions), SlaveAddr::Alternative(true))
+}
+
+fn new_mma8652(transactions: &[I2cTrans]) -> Mma8x5xAsync<I2cMock, ic::Mma8652, mode::Standby> {
+    Mma8x5xAsync::new_mma8652(I2cMock::new(transactions))
+}
+
+fn new_mma8653(transactions: &[I2cTrans]) -> Mma8x5xAsync<I2cMock, ic::Mma8653, mode::Standby> {
+    Mma8x5xAsync::new_mma8653(I2cMock::new(transactions))
+}
+
+fn destroy<IC, MODE>(sensor: Mma8x5xAsync<I2cMock, IC, MODE>) {
+    sensor.destroy().done();
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $bit_shift:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_create_and_destroy() {
+                let sensor = $create(&[]);
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_change_mode() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0]),
+                ]);
+                let sensor = block_on(sensor.into_active()).ok().unwrap();
+                let sensor = block_on(sensor.into_standby()).ok().unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_reset_from_active() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::RST]),
+                ]);
+                let sensor = block_on(sensor.into_active()).ok().unwrap();
+                let sensor = block_on(sensor.reset()).ok().unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_set_data_rate() {
+                let mut sensor = $create(&[I2cTrans::write(
+                    ADDRESS,
+                    vec![Register::CTRL_REG1, BF::ODR1 | BF::ODR0],
+                )]);
+                block_on(sensor.set_data_rate(OutputDataRate::Hz100)).unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_device_id() {
+                let mut sensor = $create(&[I2cTrans::write_read(
+                    ADDRESS,
+                    vec![Register::WHO_AM_I],
+                    vec![0x2A],
+                )]);
+                assert_eq!(0x2A, block_on(sensor.device_id()).unwrap());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_data_status() {
+                let mut sensor = $create(&[I2cTrans::write_read(
+                    ADDRESS,
+                    vec![Register::STATUS],
+                    vec![BF::XYZDR],
+                )]);
+                let status = block_on(sensor.data_status()).unwrap();
+                assert_eq!(
+                    DataStatus {
+                        xyz_new_data: true,
+                        ..DataStatus::default()
+                    },
+                    status
+                );
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_unscaled() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                let mut sensor = block_on(sensor.into_active()).ok().unwrap();
+                assert_eq!(
+                    UnscaledMeasurement {
+                        x: 0x4140 >> $bit_shift,
+                        y: 0x4280 >> $bit_shift,
+                        z: 0x43C0 >> $bit_shift,
+                    },
+                    block_on(sensor.read_unscaled()).unwrap()
+                );
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_mg() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 2]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![0xF8, 0, 0, 0, 0x08, 0],
+                    ),
+                ]);
+                block_on(sensor.set_scale(GScale::G8)).unwrap();
+                let mut sensor = block_on(sensor.into_active()).ok().unwrap();
+                assert_eq!(
+                    MilliGMeasurement {
+                        x: -500,
+                        y: 0,
+                        z: 500,
+                    },
+                    block_on(sensor.read_mg()).unwrap()
+                );
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_with_status() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::STATUS],
+                        vec![BF::XYZDR, 0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                let mut sensor = block_on(sensor.into_active()).ok().unwrap();
+                let (status, m) = block_on(sensor.read_with_status()).unwrap();
+                assert!(status.xyz_new_data);
+                assert_eq!(
+                    UnscaledMeasurement {
+                        x: 0x4140 >> $bit_shift,
+                        y: 0x4280 >> $bit_shift,
+                        z: 0x43C0 >> $bit_shift,
+                    },
+                    m
+                );
+                destroy(sensor);
+            }
+
+            #[test]
+            fn wait_for_interrupt_active_low() {
+                let sensor = $create(&[]);
+                let mut pin = PinMock::new(&[PinTrans::wait_for_state(PinState::Low)]);
+                block_on(sensor.wait_for_interrupt(&mut pin)).unwrap();
+                pin.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn wait_for_interrupt_active_high() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG3, BF::IPOL]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![BF::SRC_DRDY]),
+                ]);
+                let mut pin = PinMock::new(&[PinTrans::wait_for_state(PinState::High)]);
+                block_on(sensor.set_interrupt_pin_polarity(InterruptPinPolarity::ActiveHigh))
+                    .unwrap();
+                block_on(sensor.wait_for_interrupt(&mut pin)).unwrap();
+                assert_eq!(
+                    InterruptStatus {
+                        data_ready: true,
+                        ..InterruptStatus::default()
+                    },
+                    block_on(sensor.interrupt_status()).unwrap()
+                );
+                pin.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_service_interrupt() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG4, BF::INT_EN_DRDY]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![BF::SRC_DRDY]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                block_on(sensor.set_enabled_interrupts(EnabledInterrupts {
+                    data_ready: true,
+                    ..EnabledInterrupts::default()
+                }))
+                .unwrap();
+                let pin = PinMock::new(&[PinTrans::get(PinState::Low)]);
+                let mut handler = sensor.interrupt_handler(Int2(pin));
+                let mut sensor = block_on(sensor.into_active()).ok().unwrap();
+                assert_eq!(
+                    Some(Event::DataReady(UnscaledMeasurement {
+                        x: 0x4140 >> $bit_shift,
+                        y: 0x4280 >> $bit_shift,
+                        z: 0x43C0 >> $bit_shift,
+                    })),
+                    block_on(handler.service_async(&mut sensor)).unwrap()
+                );
+                handler.destroy().0.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_next_events() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![BF::SRC_ASLP]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::SYSMOD], vec![2]),
+                ]);
+                let mut sensor = block_on(sensor.into_active()).ok().unwrap();
+                let mut events = block_on(sensor.next_events()).unwrap();
+                assert_eq!(
+                    Some(DecodedEvent::SleepWakeTransition(SystemMode::Sleep)),
+                    events.next()
+                );
+                assert_eq!(None, events.next());
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, 2);
+tests!(mma8452, new_mma8452, 4);
+tests!(mma8453, new_mma8453, 6);
+tests!(mma8652, new_mma8652, 4);
+tests!(mma8653, new_mma8653, 6);
+
+#[test]
+fn can_set_transient_config() {
+    let mut sensor = new_mma8452(&[
+        I2cTrans::write(
+            ADDRESS,
+            vec![Register::TRANSIENT_CFG, BF::TRANS_ELE | BF::ZTEFE],
+        ),
+        I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_THS, 8]),
+        I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_COUNT, 8]),
+    ]);
+    block_on(sensor.set_transient_config(TransientConfig {
+        z_axis: true,
+        latch: true,
+        threshold: 0.5,
+        debounce_time_ms: 10.0,
+        ..TransientConfig::default()
+    }))
+    .unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn cannot_set_invalid_transient_config() {
+    let mut sensor = new_mma8452(&[]);
+    assert!(matches!(
+        block_on(sensor.set_transient_config(TransientConfig {
+            threshold: 8.5,
+            ..TransientConfig::default()
+        })),
+        Err(Error::InvalidInputData)
+    ));
+    destroy(sensor);
+}
+
+#[test]
+fn can_use_fifo_gate() {
+    let mut sensor = new_mma8652(&[
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG3, BF::FGATE]),
+        I2cTrans::write_read(ADDRESS, vec![Register::SYSMOD], vec![BF::FGERR | 1]),
+    ]);
+    block_on(sensor.enable_fifo_gate()).unwrap();
+    assert_eq!(
+        SystemStatus {
+            mode: SystemMode::Wake,
+            fifo_gate_error: true,
+            fifo_gate_time: 0
+        },
+        block_on(sensor.system_status()).unwrap()
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_fifo() {
+    let mut sensor = new_mma8451(&[
+        I2cTrans::write(ADDRESS, vec![Register::F_SETUP, BF::F_MODE0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+        I2cTrans::write_read(ADDRESS, vec![Register::STATUS], vec![2]),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::OUT_X_H],
+            vec![
+                0x41, 0x40, 0x42, 0x80, 0x43, 0xC0, 0x44, 0x00, 0x45, 0x40, 0x46, 0x80,
+            ],
+        ),
+    ]);
+    block_on(sensor.set_fifo_mode(FifoMode::Circular)).unwrap();
+    let mut sensor = block_on(sensor.into_active()).ok().unwrap();
+    let mut buffer = [UnscaledMeasurement::default(); 4];
+    assert_eq!(2, block_on(sensor.read_fifo(&mut buffer)).unwrap());
+    assert_eq!(
+        UnscaledMeasurement {
+            x: 0x4140 >> 2,
+            y: 0x4280 >> 2,
+            z: 0x43C0 >> 2,
+        },
+        buffer[0]
+    );
+    assert_eq!(
+        UnscaledMeasurement {
+            x: 0x4400 >> 2,
+            y: 0x4540 >> 2,
+            z: 0x4680 >> 2,
+        },
+        buffer[1]
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn can_init() {
+    let mut sensor = new_mma8652(&[
+        I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![0x4A]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::RST]),
+        I2cTrans::write_read(ADDRESS, vec![Register::CTRL_REG2], vec![BF::RST]),
+        I2cTrans::write_read(ADDRESS, vec![Register::CTRL_REG2], vec![0]),
+    ]);
+    let mut delay =
+        CheckedDelay::new(&[DelayTrans::async_delay_ms(1), DelayTrans::async_delay_ms(1)]);
+    block_on(sensor.init(&mut delay)).unwrap();
+    delay.done();
+    destroy(sensor);
+}
+
+#[test]
+fn init_rejects_wrong_device() {
+    let mut sensor = new_mma8652(&[I2cTrans::write_read(
+        ADDRESS,
+        vec![Register::WHO_AM_I],
+        vec![0x2A],
+    )]);
+    let mut delay = CheckedDelay::new(&[]);
+    assert!(matches!(
+        block_on(sensor.init(&mut delay)),
+        Err(Error::InvalidDevice(0x2A))
+    ));
+    delay.done();
+    destroy(sensor);
+}
+
+#[test]
+fn can_run_self_test() {
+    let sampling = |z: u8| {
+        let mut transactions = vec![I2cTrans::write(
+            ADDRESS,
+            vec![Register::CTRL_REG1, BF::ODR1 | BF::ODR0 | 1],
+        )];
+        for _ in 0..8 {
+            transactions.push(I2cTrans::write_read(
+                ADDRESS,
+                vec![Register::OUT_X_H],
+                vec![0x01, 0x80, 0x02, 0x00, z, 0x40],
+            ));
+        }
+        transactions.push(I2cTrans::write(
+            ADDRESS,
+            vec![Register::CTRL_REG1, BF::ODR1 | BF::ODR0],
+        ));
+        transactions
+    };
+    let mut transactions = vec![
+        I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+    ];
+    transactions.extend(sampling(0x10));
+    transactions.push(I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::ST]));
+    transactions.extend(sampling(0x1C));
+    transactions.extend([
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+    ]);
+    let mut sensor = new_mma8653(&transactions);
+    let mut delays = vec![DelayTrans::async_delay_ms(20)];
+    delays.extend((0..8).map(|_| DelayTrans::async_delay_ms(10)));
+    delays.push(DelayTrans::async_delay_ms(20));
+    delays.extend((0..8).map(|_| DelayTrans::async_delay_ms(10)));
+    let mut delay = CheckedDelay::new(&delays);
+    let report = block_on(sensor.run_self_test(&mut delay)).unwrap();
+    assert_eq!(0, report.x.delta);
+    assert_eq!(0, report.y.delta);
+    assert_eq!(48, report.z.delta);
+    assert!(!report.passed);
+    delay.done();
+    destroy(sensor);
+}
+
+#[test]
+fn can_calibrate_offsets() {
+    let mut transactions = vec![
+        I2cTrans::write_read(ADDRESS, vec![Register::OFF_X], vec![0, 0, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::OFF_X, 0, 0, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ODR1 | BF::ODR0 | 1]),
+    ];
+    for _ in 0..32 {
+        // X: 10 counts, Z: 256 counts (1g)
+        transactions.push(I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::OUT_X_H],
+            vec![0x02, 0x80, 0x00, 0x00, 0x40, 0x00],
+        ));
+    }
+    transactions.extend([
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ODR1 | BF::ODR0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::OFF_X, -20i8 as u8, 0, 0]),
+    ]);
+    let mut sensor = new_mma8653(&transactions);
+    let mut delays = vec![DelayTrans::async_delay_ms(20)];
+    delays.extend((0..32).map(|_| DelayTrans::async_delay_ms(10)));
+    let mut delay = CheckedDelay::new(&delays);
+    let cal = block_on(sensor.calibrate_offsets(CalibrationOrientation::ZUp, &mut delay)).unwrap();
+    assert_eq!((-20, 0, 0), (cal.x, cal.y, cal.z));
+    delay.done();
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_and_apply_config() {
+    let mut sensor = new_mma8652(&[
+        I2cTrans::write_read(ADDRESS, vec![Register::F_SETUP], vec![0, 0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::XYZ_DATA_CFG], vec![0x01, 0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::PL_CFG], vec![0x80, 0, 0x44, 0x84]),
+        I2cTrans::write_read(ADDRESS, vec![Register::FF_MT_CFG], vec![0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::FF_MT_THS], vec![0, 0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::TRANSIENT_CFG], vec![0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::TRANSIENT_THS], vec![0, 0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::PULSE_CFG], vec![0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::PULSE_THSX], vec![0; 6]),
+        I2cTrans::write_read(ADDRESS, vec![Register::ASLP_COUNT], vec![0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::CTRL_REG2], vec![0; 7]),
+        I2cTrans::write_read(ADDRESS, vec![Register::CTRL_REG1], vec![0x19]),
+        I2cTrans::write(ADDRESS, vec![Register::F_SETUP, 0, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0x01, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::PL_CFG, 0x80, 0, 0x44, 0x84]),
+        I2cTrans::write(ADDRESS, vec![Register::FF_MT_CFG, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::FF_MT_THS, 0, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_CFG, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_THS, 0, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::PULSE_CFG, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::PULSE_THSX, 0, 0, 0, 0, 0, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::ASLP_COUNT, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0, 0, 0, 0, 0, 0, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x18]),
+    ]);
+    let config = block_on(sensor.read_config()).unwrap();
+    let mut expected = DeviceConfig::default();
+    expected.set_scale(GScale::G4);
+    expected.set_data_rate(OutputDataRate::Hz100);
+    assert_eq!(expected, config);
+    block_on(sensor.apply_config(&config)).unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn can_sync_from_device_and_attach() {
+    let transactions = [
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::CTRL_REG1],
+            vec![0x19, 0x40, 0, 0, 0],
+        ),
+        I2cTrans::write_read(ADDRESS, vec![Register::XYZ_DATA_CFG], vec![0x01, 0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::PL_CFG], vec![0x80]),
+        I2cTrans::write_read(ADDRESS, vec![Register::F_SETUP], vec![0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x18]),
+    ];
+    let sensor = block_on(Mma8x5xAsync::attach_mma8652(I2cMock::new(&transactions)))
+        .ok()
+        .unwrap();
+    let sensor = block_on(sensor.into_standby()).ok().unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn sync_puts_active_device_in_standby() {
+    let transactions = [
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::CTRL_REG1],
+            vec![0x19, 0x40, 0, 0, 0],
+        ),
+        I2cTrans::write_read(ADDRESS, vec![Register::XYZ_DATA_CFG], vec![0x01, 0]),
+        I2cTrans::write_read(ADDRESS, vec![Register::PL_CFG], vec![0x80]),
+        I2cTrans::write_read(ADDRESS, vec![Register::F_SETUP], vec![0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x18]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x19]),
+    ];
+    let mut sensor = new_mma8652(&transactions);
+    block_on(sensor.sync_from_device()).unwrap();
+    let sensor = block_on(sensor.into_active()).ok().unwrap();
+    destroy(sensor);
+}
@@ -0,0 +1,142 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF,
+    Register, ADDRESS,
+};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTrans},
+    i2c::Transaction as I2cTrans,
+};
+use mma8x5x::{CalibrationOrientation, Error, ReadMode};
+
+const ODR_100HZ: u8 = BF::ODR1 | BF::ODR0;
+
+fn sample(x: i16, y: i16, z: i16, bit_shift: u8) -> I2cTrans {
+    let mut data = Vec::new();
+    for value in [x, y, z] {
+        data.extend_from_slice(&((value << bit_shift) as u16).to_be_bytes());
+    }
+    I2cTrans::write_read(ADDRESS, vec![Register::OUT_X_H], data)
+}
+
+fn read_offsets(x: i8, y: i8, z: i8) -> I2cTrans {
+    I2cTrans::write_read(
+        ADDRESS,
+        vec![Register::OFF_X],
+        vec![x as u8, y as u8, z as u8],
+    )
+}
+
+fn write_offsets(x: i8, y: i8, z: i8) -> I2cTrans {
+    I2cTrans::write(ADDRESS, vec![Register::OFF_X, x as u8, y as u8, z as u8])
+}
+
+fn write_f_setup(fifo: bool, f_setup: u8) -> Vec<I2cTrans> {
+    if fifo {
+        vec![I2cTrans::write(ADDRESS, vec![Register::F_SETUP, f_setup])]
+    } else {
+        Vec::new()
+    }
+}
+
+fn calibration_delays() -> CheckedDelay {
+    let mut delays = vec![DelayTrans::delay_ms(20)];
+    for _ in 0..32 {
+        delays.push(DelayTrans::delay_ms(10));
+    }
+    CheckedDelay::new(&delays)
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $bit_shift:expr, $one_g:expr, $fifo:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_calibrate_offsets() {
+                // 40mg, -20mg and 1g+80mg
+                let one_g: i16 = $one_g;
+                let x = (i32::from(one_g) * 40 / 1000) as i16;
+                let y = (i32::from(one_g) * -20 / 1000) as i16;
+                let z = one_g + (i32::from(one_g) * 80 / 1000) as i16;
+                let mut transactions = vec![
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 2]),
+                    read_offsets(5, -3, 0),
+                    write_offsets(0, 0, 0),
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+                ];
+                transactions.extend(write_f_setup($fifo, 0));
+                transactions.push(I2cTrans::write(
+                    ADDRESS,
+                    vec![Register::CTRL_REG1, ODR_100HZ | 1],
+                ));
+                for _ in 0..32 {
+                    transactions.push(sample(x, y, z, $bit_shift));
+                }
+                transactions.extend([
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, ODR_100HZ]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 2]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+                ]);
+                transactions.extend(write_f_setup($fifo, 0));
+                let expected = (
+                    -(f32::from(x) / f32::from(one_g) / 0.00196).round() as i8,
+                    -(f32::from(y) / f32::from(one_g) / 0.00196).round() as i8,
+                    -(f32::from(z - one_g) / f32::from(one_g) / 0.00196).round() as i8,
+                );
+                transactions.push(write_offsets(expected.0, expected.1, expected.2));
+                let mut sensor = $create(&transactions);
+                let mut delay = calibration_delays();
+                sensor.set_read_mode(ReadMode::Fast).unwrap();
+                let cal = sensor
+                    .calibrate_offsets(CalibrationOrientation::ZUp, &mut delay)
+                    .unwrap();
+                assert_eq!(expected, (cal.x, cal.y, cal.z));
+                assert!(cal.residual.x.abs() <= 0.5 / f32::from(one_g) + 0.001);
+                assert!(cal.residual.y.abs() <= 0.5 / f32::from(one_g) + 0.001);
+                assert!(cal.residual.z.abs() <= 0.5 / f32::from(one_g) + 0.001);
+                delay.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn restores_offsets_after_error() {
+                let mut transactions = vec![
+                    read_offsets(5, -3, 0),
+                    write_offsets(0, 0, 0),
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+                ];
+                transactions.extend(write_f_setup($fifo, 0));
+                transactions.extend([
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, ODR_100HZ | 1]),
+                    sample(0, 0, 0, $bit_shift)
+                        .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+                ]);
+                transactions.extend(write_f_setup($fifo, 0));
+                transactions.push(write_offsets(5, -3, 0));
+                let mut sensor = $create(&transactions);
+                let mut delay =
+                    CheckedDelay::new(&[DelayTrans::delay_ms(20), DelayTrans::delay_ms(10)]);
+                assert!(matches!(
+                    sensor.calibrate_offsets(CalibrationOrientation::ZDown, &mut delay),
+                    Err(Error::I2C(_))
+                ));
+                delay.done();
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, 2, 4096, true);
+tests!(mma8452, new_mma8452, 4, 1024, false);
+tests!(mma8453, new_mma8453, 6, 256, false);
+tests!(mma8652, new_mma8652, 4, 1024, true);
+tests!(mma8653, new_mma8653, 6, 256, false);
@@ -1,9 +1,10 @@
 mod base;
 use crate::base::{
-    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, Register, ADDRESS,
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF,
+    Register, ADDRESS,
 };
 use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
-use mma8x5x::{GScale, Measurement, ReadMode, UnscaledMeasurement};
+use mma8x5x::{DataStatus, GScale, Measurement, MilliGMeasurement, ReadMode, UnscaledMeasurement};
 
 macro_rules! set_scale_read_test {
     ($name:ident, $create:ident, $gscale:ident, $gscale_bits:expr, $bit_shift:expr, $max:expr, $g:expr) => {
@@ -100,6 +101,106 @@ macro_rules! read_tests {
                 destroy(sensor);
             }
 
+            #[test]
+            fn can_read_with_status() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::STATUS],
+                        vec![BF::XYZOW | BF::XYZDR, 0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                let expected = UnscaledMeasurement {
+                    x: 0x4140 >> $bit_shift,
+                    y: 0x4280 >> $bit_shift,
+                    z: 0x43C0 >> $bit_shift,
+                };
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let (status, m) = sensor.read_with_status().unwrap();
+                assert_eq!(
+                    DataStatus {
+                        xyz_overwrite: true,
+                        xyz_new_data: true,
+                        ..DataStatus::default()
+                    },
+                    status
+                );
+                assert_eq!(expected, m);
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_fast_with_status() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 2]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 3]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::STATUS],
+                        vec![BF::XYZDR, 0x41, 0x42, 0x43],
+                    ),
+                ]);
+                let expected = UnscaledMeasurement {
+                    x: 0x4100 >> $bit_shift,
+                    y: 0x4200 >> $bit_shift,
+                    z: 0x4300 >> $bit_shift,
+                };
+                sensor.set_read_mode(ReadMode::Fast).unwrap();
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let (status, m) = sensor.read_with_status().unwrap();
+                assert!(status.xyz_new_data);
+                assert!(!status.xyz_overwrite);
+                assert_eq!(expected, m);
+                destroy(sensor);
+            }
+
+            fn one_g_transactions() -> Vec<I2cTrans> {
+                let one_g = ($max / 2.0) as i16;
+                let mut data = Vec::new();
+                for value in [one_g, -one_g, 0] {
+                    data.extend_from_slice(&((value << $bit_shift) as u16).to_be_bytes());
+                }
+                vec![
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::OUT_X_H], data),
+                ]
+            }
+
+            #[test]
+            fn can_read_mg() {
+                let sensor = $create(&one_g_transactions());
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let expected = MilliGMeasurement {
+                    x: 1000,
+                    y: -1000,
+                    z: 0,
+                };
+                assert_eq!(expected, sensor.read_mg().unwrap());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_ms2() {
+                let sensor = $create(&one_g_transactions());
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let m = sensor.read_ms2().unwrap();
+                assert_near!(m.x, 9.80665, 0.0001);
+                assert_near!(m.y, -9.80665, 0.0001);
+                assert_near!(m.z, 0.0, 0.0001);
+                destroy(sensor);
+            }
+
+            #[cfg(feature = "fixed")]
+            #[test]
+            fn can_read_fixed() {
+                let sensor = $create(&one_g_transactions());
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let m = sensor.read_fixed().unwrap();
+                assert_eq!((1, -1, 0), (m.x.to_num(), m.y.to_num(), m.z.to_num()));
+                destroy(sensor);
+            }
+
             set_scale_read_test!(set_2g_read, $create, G2, 0, $bit_shift, $max, 2.0);
             set_scale_read_test!(set_4g_read, $create, G4, 1, $bit_shift, $max, 4.0);
             set_scale_read_test!(set_8g_read, $create, G8, 2, $bit_shift, $max, 8.0);
@@ -107,8 +208,8 @@ macro_rules! read_tests {
     };
 }
 
-read_tests!(mma8451, new_mma8451, 2, 4096.0);
-read_tests!(mma8452, new_mma8452, 4, 1024.0);
-read_tests!(mma8453, new_mma8453, 6, 256.0);
-read_tests!(mma8652, new_mma8652, 4, 1024.0);
-read_tests!(mma8653, new_mma8653, 6, 256.0);
+read_tests!(mma8451, new_mma8451, 2, 8192.0);
+read_tests!(mma8452, new_mma8452, 4, 2048.0);
+read_tests!(mma8453, new_mma8453, 6, 512.0);
+read_tests!(mma8652, new_mma8652, 4, 2048.0);
+read_tests!(mma8653, new_mma8653, 6, 512.0);
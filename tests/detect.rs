@@ -0,0 +1,91 @@
+mod base;
+use crate::base::Register;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use mma8x5x::{DetectedDevice, DetectedIc};
+
+const NACK: ErrorKind = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+
+fn who_am_i(address: u8, id: u8) -> I2cTrans {
+    I2cTrans::write_read(address, vec![Register::WHO_AM_I], vec![id])
+}
+
+fn no_device(address: u8) -> I2cTrans {
+    I2cTrans::write_read(address, vec![Register::WHO_AM_I], vec![0]).with_error(NACK)
+}
+
+macro_rules! detect_test {
+    ($name:ident, $ic:ident, [$($trans:expr),*], $address:expr) => {
+        #[test]
+        fn $name() {
+            let id = DetectedIc::$ic.device_id();
+            let i2c = I2cMock::new(&[$($trans,)* who_am_i($address, id)]);
+            let dev = DetectedDevice::detect(i2c).unwrap();
+            assert_eq!(DetectedIc::$ic, dev.ic());
+            let DetectedDevice::$ic(mut sensor) = dev else {
+                panic!("wrong device variant");
+            };
+            // The driver communicates at the address where the device was found
+            assert_eq!(id, sensor.device_id().unwrap());
+            sensor.destroy().done();
+        }
+    };
+}
+
+detect_test!(mma8451_default, Mma8451, [who_am_i(0x1C, 0x1A)], 0x1C);
+detect_test!(
+    mma8451_alternative,
+    Mma8451,
+    [no_device(0x1C), who_am_i(0x1D, 0x1A)],
+    0x1D
+);
+detect_test!(mma8452_default, Mma8452, [who_am_i(0x1C, 0x2A)], 0x1C);
+detect_test!(
+    mma8452_alternative,
+    Mma8452,
+    [no_device(0x1C), who_am_i(0x1D, 0x2A)],
+    0x1D
+);
+detect_test!(mma8453_default, Mma8453, [who_am_i(0x1C, 0x3A)], 0x1C);
+detect_test!(
+    mma8652,
+    Mma8652,
+    [no_device(0x1C), who_am_i(0x1D, 0x4A)],
+    0x1D
+);
+detect_test!(
+    mma8653,
+    Mma8653,
+    [no_device(0x1C), who_am_i(0x1D, 0x5A)],
+    0x1D
+);
+
+#[test]
+fn returns_bus_when_nothing_found() {
+    let err = DetectedDevice::detect(I2cMock::new(&[no_device(0x1C), no_device(0x1D)]))
+        .err()
+        .unwrap();
+    assert_eq!(None, err.device_id);
+    let mut i2c = err.i2c;
+    i2c.done();
+}
+
+#[test]
+fn reports_unknown_device_id() {
+    let err = DetectedDevice::detect(I2cMock::new(&[no_device(0x1C), who_am_i(0x1D, 0x12)]))
+        .err()
+        .unwrap();
+    assert_eq!(Some(0x12), err.device_id);
+    let mut i2c = err.i2c;
+    i2c.done();
+}
+
+#[test]
+fn mma865x_not_detected_at_default_address() {
+    let err = DetectedDevice::detect(I2cMock::new(&[who_am_i(0x1C, 0x4A), no_device(0x1D)]))
+        .err()
+        .unwrap();
+    assert_eq!(Some(0x4A), err.device_id);
+    let mut i2c = err.i2c;
+    i2c.done();
+}
@@ -0,0 +1,212 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, Register, ADDRESS,
+};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mma8x5x::{DeviceConfig, PulseConfig};
+use std::ops::Range;
+
+/// Register values in the order of the binary encoding
+const REGISTERS: [u8; 30] = [
+    0x54, 0x08, 0x12, 0x13, 0xC0, 0x05, 0x43, 0x4B, 0xD8, 0x8A, 0x0C, 0x1E, 0x84, 0x07, 0x55, 0x20,
+    0x21, 0x2A, 0x18, 0x28, 0x3C, 0x10, 0x5A, 0x1C, 0x0A, 0x11, 0x01, 0xFE, 0x05, 0x80,
+];
+
+fn from_registers(registers: [u8; 30]) -> DeviceConfig {
+    let mut data = [0; DeviceConfig::ENCODED_SIZE];
+    data[0] = 1;
+    data[1..31].copy_from_slice(&registers);
+    data[31] = data[..31]
+        .iter()
+        .fold(0u8, |sum, b| sum.wrapping_add(*b))
+        .wrapping_neg();
+    DeviceConfig::from_bytes(&data).unwrap()
+}
+
+fn config() -> DeviceConfig {
+    from_registers(REGISTERS)
+}
+
+/// Registers present in each device
+#[derive(Clone, Copy)]
+struct Features {
+    fifo: bool,
+    high_pass_filter: bool,
+    programmable_orientation: bool,
+}
+
+const MMA8451: Features = Features {
+    fifo: true,
+    high_pass_filter: true,
+    programmable_orientation: true,
+};
+const MMA845X: Features = Features {
+    fifo: false,
+    high_pass_filter: true,
+    programmable_orientation: false,
+};
+const MMA8652: Features = MMA8451;
+const MMA8653: Features = Features {
+    fifo: false,
+    high_pass_filter: false,
+    programmable_orientation: false,
+};
+
+/// Configuration as read from a device, where the missing registers keep their reset value
+fn device_config(features: Features) -> DeviceConfig {
+    let default = DeviceConfig::default().to_bytes();
+    let mut registers = REGISTERS;
+    let mut keep_reset_value = |range: Range<usize>| {
+        registers[range.clone()].copy_from_slice(&default[range.start + 1..range.end + 1]);
+    };
+    if !features.fifo {
+        // F_SETUP, TRIG_CFG
+        keep_reset_value(0..2);
+    }
+    if !features.high_pass_filter {
+        // HP_FILTER_CUTOFF and the transient and pulse registers
+        keep_reset_value(3..4);
+        keep_reset_value(11..21);
+    }
+    if !features.programmable_orientation {
+        // PL_BF_ZCOMP, P_L_THS_REG
+        keep_reset_value(6..8);
+    }
+    from_registers(registers)
+}
+
+/// Register blocks as (first register, contents) in the order they are accessed
+///
+/// The ACTIVE and RST bits are set in the values read.
+fn blocks(features: Features, read: bool) -> Vec<(u8, Vec<u8>)> {
+    let (active, reset) = if read { (1, 0x40) } else { (0, 0) };
+    let mut blocks = Vec::new();
+    if features.fifo {
+        blocks.push((Register::F_SETUP, vec![0x54, 0x08]));
+    }
+    if features.high_pass_filter {
+        blocks.push((Register::XYZ_DATA_CFG, vec![0x12, 0x13]));
+    } else {
+        blocks.push((Register::XYZ_DATA_CFG, vec![0x12]));
+    }
+    if features.programmable_orientation {
+        blocks.push((Register::PL_CFG, vec![0xC0, 0x05, 0x43, 0x4B]));
+    } else {
+        blocks.push((Register::PL_CFG, vec![0xC0, 0x05]));
+    }
+    blocks.push((Register::FF_MT_CFG, vec![0xD8]));
+    blocks.push((Register::FF_MT_THS, vec![0x8A, 0x0C]));
+    if features.high_pass_filter {
+        blocks.push((Register::TRANSIENT_CFG, vec![0x1E]));
+        blocks.push((Register::TRANSIENT_THS, vec![0x84, 0x07]));
+        blocks.push((Register::PULSE_CFG, vec![0x55]));
+        blocks.push((
+            Register::PULSE_THSX,
+            vec![0x20, 0x21, 0x2A, 0x18, 0x28, 0x3C],
+        ));
+    }
+    blocks.push((Register::ASLP_COUNT, vec![0x10]));
+    blocks.push((
+        Register::CTRL_REG2,
+        vec![0x1C | reset, 0x0A, 0x11, 0x01, 0xFE, 0x05, 0x80],
+    ));
+    blocks.push((Register::CTRL_REG1, vec![0x5A | active]));
+    blocks
+}
+
+fn read_transactions(features: Features) -> Vec<I2cTrans> {
+    blocks(features, true)
+        .into_iter()
+        .map(|(register, data)| I2cTrans::write_read(ADDRESS, vec![register], data))
+        .collect()
+}
+
+fn apply_transactions(features: Features) -> Vec<I2cTrans> {
+    blocks(features, false)
+        .into_iter()
+        .map(|(register, data)| {
+            let mut payload = vec![register];
+            payload.extend(data);
+            I2cTrans::write(ADDRESS, payload)
+        })
+        .collect()
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $features:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_read_config() {
+                // ACTIVE and RST bits are not part of the configuration
+                let mut sensor = $create(&read_transactions($features));
+                assert_eq!(device_config($features), sensor.read_config().unwrap());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_apply_config() {
+                let mut sensor = $create(&apply_transactions($features));
+                sensor.apply_config(&config()).unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn apply_config_updates_driver_state() {
+                let mut transactions = apply_transactions($features);
+                transactions.push(I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x5B]));
+                let mut sensor = $create(&transactions);
+                sensor.apply_config(&config()).unwrap();
+                let sensor = sensor.into_active().ok().unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_restore_config_read() {
+                let mut transactions = read_transactions($features);
+                transactions.extend(apply_transactions($features));
+                let mut sensor = $create(&transactions);
+                let data = sensor.read_config().unwrap().to_bytes();
+                let config = DeviceConfig::from_bytes(&data).unwrap();
+                assert_eq!(device_config($features), config);
+                sensor.apply_config(&config).unwrap();
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, MMA8451);
+tests!(mma8452, new_mma8452, MMA845X);
+tests!(mma8453, new_mma8453, MMA845X);
+tests!(mma8652, new_mma8652, MMA8652);
+tests!(mma8653, new_mma8653, MMA8653);
+
+#[test]
+fn apply_config_updates_pulse_filter_state() {
+    // 100Hz in normal mode with the pulse low-pass filter enabled: 10ms/LSB
+    let mut transactions = apply_transactions(MMA845X);
+    transactions.push(I2cTrans::write(ADDRESS, vec![Register::PULSE_CFG, 0]));
+    transactions.push(I2cTrans::write(
+        ADDRESS,
+        vec![Register::PULSE_THSX, 0, 0, 0, 5, 0, 0],
+    ));
+    let mut sensor = new_mma8452(&transactions);
+    sensor.apply_config(&config()).unwrap();
+    sensor
+        .set_pulse_config(PulseConfig {
+            time_limit_ms: 50.0,
+            ..Default::default()
+        })
+        .unwrap();
+    destroy(sensor);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn can_serialize_and_deserialize() {
+    let json = serde_json::to_string(&config()).unwrap();
+    assert!(json.contains("\"offset_z\":-128"));
+    assert_eq!(config(), serde_json::from_str(&json).unwrap());
+}
@@ -0,0 +1,226 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF,
+    Register, ADDRESS,
+};
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mma8x5x::{
+    Axis, DecodedEvent, Error, Event, EventAxes, EventPolarity, Events, FfMtSource,
+    PortraitLandscapeStatus, SystemMode, UnscaledMeasurement,
+};
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $bit_shift:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn no_events() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![0]),
+                ]);
+                let mut sensor = sensor.into_active().ok().unwrap();
+                assert_eq!(None, sensor.next_events().unwrap().next());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_data_ready_event() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![BF::SRC_DRDY]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let mut events = sensor.next_events().unwrap();
+                let expected = UnscaledMeasurement {
+                    x: 0x4140 >> $bit_shift,
+                    y: 0x4280 >> $bit_shift,
+                    z: 0x43C0 >> $bit_shift,
+                };
+                assert_eq!(Some(DecodedEvent::DataReady(expected)), events.next());
+                assert_eq!(None, events.next());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_orientation_event() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![BF::SRC_LNDPRT]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::PL_STATUS], vec![BF::NEWLP]),
+                ]);
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let mut events = sensor.next_events().unwrap();
+                assert_eq!(
+                    Some(DecodedEvent::OrientationChanged(PortraitLandscapeStatus {
+                        something_changed: true,
+                        ..PortraitLandscapeStatus::default()
+                    })),
+                    events.next()
+                );
+                assert_eq!(None, events.next());
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, 2);
+tests!(mma8452, new_mma8452, 4);
+tests!(mma8453, new_mma8453, 6);
+tests!(mma8652, new_mma8652, 4);
+tests!(mma8653, new_mma8653, 6);
+
+#[test]
+fn reads_each_source_once_in_order() {
+    let sensor = new_mma8451(&[
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::INT_SOURCE],
+            vec![BF::SRC_ASLP | BF::SRC_FIFO | BF::SRC_PULSE | BF::SRC_FF_MT],
+        ),
+        I2cTrans::write_read(ADDRESS, vec![Register::SYSMOD], vec![1]),
+        I2cTrans::write_read(ADDRESS, vec![Register::STATUS], vec![BF::F_WMRK_FLAG | 20]),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::PULSE_SRC],
+            vec![BF::PULSE_EA | BF::DPE | BF::AX_Y | BF::POL_Y],
+        ),
+        I2cTrans::write_read(ADDRESS, vec![Register::FF_MT_CFG], vec![BF::OAE]),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::FF_MT_SRC],
+            vec![BF::FF_MT_EA | BF::XHE | BF::ZHE],
+        ),
+    ]);
+    let mut sensor = sensor.into_active().ok().unwrap();
+    let mut events = sensor.next_events().unwrap();
+    assert_eq!(
+        Some(DecodedEvent::SleepWakeTransition(SystemMode::Wake)),
+        events.next()
+    );
+    assert_eq!(Some(DecodedEvent::FifoWatermark(20)), events.next());
+    assert_eq!(
+        Some(DecodedEvent::Tap {
+            axis: Axis::Y,
+            double: true,
+            polarity: EventPolarity::Negative
+        }),
+        events.next()
+    );
+    assert_eq!(
+        Some(DecodedEvent::Motion {
+            axes: EventAxes {
+                x: true,
+                y: false,
+                z: true
+            }
+        }),
+        events.next()
+    );
+    assert_eq!(None, events.next());
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_freefall_event() {
+    let sensor = new_mma8652(&[
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+        I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![BF::SRC_FF_MT]),
+        I2cTrans::write_read(ADDRESS, vec![Register::FF_MT_CFG], vec![BF::FF_MT_ELE]),
+        I2cTrans::write_read(ADDRESS, vec![Register::FF_MT_SRC], vec![BF::FF_MT_EA]),
+    ]);
+    let mut sensor = sensor.into_active().ok().unwrap();
+    let mut events = sensor.next_events().unwrap();
+    assert_eq!(Some(DecodedEvent::Freefall), events.next());
+    assert_eq!(None, events.next());
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_transient_event() {
+    let sensor = new_mma8452(&[
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+        I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![BF::SRC_TRANS]),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::TRANSIENT_SRC],
+            vec![BF::TRANS_EA | BF::ZTRANSE],
+        ),
+    ]);
+    let mut sensor = sensor.into_active().ok().unwrap();
+    let events: Vec<_> = sensor.next_events().unwrap().collect();
+    assert_eq!(
+        vec![DecodedEvent::Transient {
+            axes: EventAxes {
+                z: true,
+                ..EventAxes::default()
+            }
+        }],
+        events
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn keeps_events_read_before_an_error() {
+    let sensor = new_mma8452(&[
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::INT_SOURCE],
+            vec![BF::SRC_TRANS | BF::SRC_LNDPRT | BF::SRC_DRDY],
+        ),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::TRANSIENT_SRC],
+            vec![BF::TRANS_EA | BF::XTRANSE],
+        ),
+        I2cTrans::write_read(ADDRESS, vec![Register::PL_STATUS], vec![0])
+            .with_error(ErrorKind::Other),
+    ]);
+    let mut sensor = sensor.into_active().ok().unwrap();
+    let err = sensor.next_events().unwrap_err();
+    assert!(matches!(err.error, Error::I2C(ErrorKind::Other)));
+    let events: Vec<_> = err.events.collect();
+    assert_eq!(
+        vec![DecodedEvent::Transient {
+            axes: EventAxes {
+                x: true,
+                ..EventAxes::default()
+            }
+        }],
+        events
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn can_decode_interrupt_event() {
+    let mut events = Events::from_event(
+        Event::FreefallMotion(FfMtSource {
+            event_active: true,
+            y_event: true,
+            ..FfMtSource::default()
+        }),
+        true,
+    );
+    assert_eq!(
+        Some(DecodedEvent::Motion {
+            axes: EventAxes {
+                y: true,
+                ..EventAxes::default()
+            }
+        }),
+        events.next()
+    );
+    assert_eq!(None, events.next());
+}
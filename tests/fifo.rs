@@ -0,0 +1,271 @@
+mod base;
+use crate::base::{destroy, new_mma8451, new_mma8652, BitFlags as BF, Register, ADDRESS};
+use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mma8x5x::{
+    ic::HasFifo, mode, FifoMode, FifoStatus, FifoTriggerSources, Mma8x5x, ReadMode, SystemMode,
+    SystemStatus, UnscaledMeasurement, WakeInterrupts,
+};
+
+fn enable_fifo<IC: HasFifo>(sensor: &mut Mma8x5x<I2cMock, IC, mode::Standby>) {
+    sensor.set_fifo_mode(FifoMode::Circular).unwrap();
+    sensor.set_fifo_watermark(16).unwrap();
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $bit_shift:expr) => {
+        mod $name {
+            use super::*;
+
+            set_test!(
+                set_fifo_disabled,
+                $create,
+                F_SETUP,
+                0,
+                set_fifo_mode,
+                FifoMode::Disabled
+            );
+            set_test!(
+                set_fifo_circular,
+                $create,
+                F_SETUP,
+                BF::F_MODE0,
+                set_fifo_mode,
+                FifoMode::Circular
+            );
+            set_test!(
+                set_fifo_stop_on_overflow,
+                $create,
+                F_SETUP,
+                BF::F_MODE1,
+                set_fifo_mode,
+                FifoMode::StopOnOverflow
+            );
+            set_test!(
+                set_fifo_trigger,
+                $create,
+                F_SETUP,
+                BF::F_MODE1 | BF::F_MODE0,
+                set_fifo_mode,
+                FifoMode::Trigger
+            );
+            set_test!(
+                set_fifo_watermark,
+                $create,
+                F_SETUP,
+                20,
+                set_fifo_watermark,
+                20
+            );
+            set_test!(
+                set_fifo_trigger_sources,
+                $create,
+                TRIG_CFG,
+                BF::TRIG_TRANS | BF::TRIG_FF_MT,
+                set_fifo_trigger_sources,
+                FifoTriggerSources {
+                    transient: true,
+                    freefall_motion: true,
+                    ..FifoTriggerSources::default()
+                }
+            );
+
+            #[test]
+            fn set_fifo_mode_keeps_watermark() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::F_SETUP, 20]),
+                    I2cTrans::write(ADDRESS, vec![Register::F_SETUP, BF::F_MODE0 | 20]),
+                    I2cTrans::write(ADDRESS, vec![Register::F_SETUP, BF::F_MODE0 | 32]),
+                ]);
+                sensor.set_fifo_watermark(20).unwrap();
+                sensor.set_fifo_mode(FifoMode::Circular).unwrap();
+                sensor.set_fifo_watermark(32).unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn cannot_set_invalid_fifo_watermark() {
+                let mut sensor = $create(&[]);
+                assert!(matches!(
+                    sensor.set_fifo_watermark(33),
+                    Err(mma8x5x::Error::InvalidInputData)
+                ));
+                destroy(sensor);
+            }
+
+            get_test!(
+                can_get_fifo_status,
+                $create,
+                STATUS,
+                BF::F_OVF | BF::F_WMRK_FLAG | 32,
+                fifo_status,
+                FifoStatus {
+                    overflow: true,
+                    watermark: true,
+                    count: 32
+                }
+            );
+
+            set_test!(
+                enable_fifo_gate,
+                $create,
+                CTRL_REG3,
+                BF::FGATE,
+                enable_fifo_gate
+            );
+            set_test!(disable_fifo_gate, $create, CTRL_REG3, 0, disable_fifo_gate);
+
+            #[test]
+            fn set_wake_interrupts_keeps_fifo_gate() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG3, BF::FGATE]),
+                    I2cTrans::write(
+                        ADDRESS,
+                        vec![Register::CTRL_REG3, BF::FGATE | BF::WAKE_TRANS],
+                    ),
+                ]);
+                sensor.enable_fifo_gate().unwrap();
+                sensor
+                    .set_wake_interrupts(WakeInterrupts {
+                        transient: true,
+                        ..WakeInterrupts::default()
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            get_test!(
+                can_get_system_status,
+                $create,
+                SYSMOD,
+                BF::FGERR | (3 << 2) | 2,
+                system_status,
+                SystemStatus {
+                    mode: SystemMode::Sleep,
+                    fifo_gate_error: true,
+                    fifo_gate_time: 3
+                }
+            );
+
+            #[test]
+            fn can_read_empty_fifo() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::STATUS], vec![0]),
+                ]);
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let mut buffer = [UnscaledMeasurement::default(); 4];
+                assert_eq!(0, sensor.read_fifo(&mut buffer).unwrap());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_fifo() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::STATUS], vec![2]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![
+                            0x41, 0x40, 0x42, 0x80, 0x43, 0xC0, 0x10, 0x40, 0x20, 0x80, 0x30, 0xC0,
+                        ],
+                    ),
+                ]);
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let mut buffer = [UnscaledMeasurement::default(); 4];
+                assert_eq!(2, sensor.read_fifo(&mut buffer).unwrap());
+                assert_eq!(
+                    [
+                        UnscaledMeasurement {
+                            x: 0x4140 >> $bit_shift,
+                            y: 0x4280 >> $bit_shift,
+                            z: 0x43C0 >> $bit_shift,
+                        },
+                        UnscaledMeasurement {
+                            x: 0x1040 >> $bit_shift,
+                            y: 0x2080 >> $bit_shift,
+                            z: 0x30C0 >> $bit_shift,
+                        },
+                        UnscaledMeasurement::default(),
+                        UnscaledMeasurement::default(),
+                    ],
+                    buffer
+                );
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_fifo_into_smaller_buffer() {
+                let sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::STATUS], vec![BF::F_OVF | 32]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let mut buffer = [UnscaledMeasurement::default(); 1];
+                assert_eq!(1, sensor.read_fifo(&mut buffer).unwrap());
+                assert_eq!(
+                    UnscaledMeasurement {
+                        x: 0x4140 >> $bit_shift,
+                        y: 0x4280 >> $bit_shift,
+                        z: 0x43C0 >> $bit_shift,
+                    },
+                    buffer[0]
+                );
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_fifo_fast() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 2]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 3]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::STATUS], vec![2]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![0x41, 0x42, 0x43, 0x10, 0x20, 0x30],
+                    ),
+                ]);
+                sensor.set_read_mode(ReadMode::Fast).unwrap();
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let mut buffer = [UnscaledMeasurement::default(); 2];
+                assert_eq!(2, sensor.read_fifo(&mut buffer).unwrap());
+                assert_eq!(
+                    [
+                        UnscaledMeasurement {
+                            x: 0x4100 >> $bit_shift,
+                            y: 0x4200 >> $bit_shift,
+                            z: 0x4300 >> $bit_shift,
+                        },
+                        UnscaledMeasurement {
+                            x: 0x1000 >> $bit_shift,
+                            y: 0x2000 >> $bit_shift,
+                            z: 0x3000 >> $bit_shift,
+                        },
+                    ],
+                    buffer
+                );
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_configure_generically() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::F_SETUP, BF::F_MODE0]),
+                    I2cTrans::write(ADDRESS, vec![Register::F_SETUP, BF::F_MODE0 | 16]),
+                ]);
+                enable_fifo(&mut sensor);
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, 2);
+tests!(mma8652, new_mma8652, 4);
@@ -0,0 +1,111 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF,
+    Register, ADDRESS,
+};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mma8x5x::{
+    DebounceCounterMode, Error, EventPolarity, FfMtSource, FreefallMotionConfig,
+    FreefallMotionMode, OutputDataRate,
+};
+
+macro_rules! tests {
+    ($name:ident, $create:ident) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_set_default_config() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::FF_MT_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::FF_MT_THS, 0, 0]),
+                ]);
+                sensor
+                    .set_freefall_motion_config(FreefallMotionConfig::default())
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_set_freefall_config() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ODR1 | BF::ODR0]),
+                    I2cTrans::write(
+                        ADDRESS,
+                        vec![
+                            Register::FF_MT_CFG,
+                            BF::FF_MT_ELE | BF::XEFE | BF::YEFE | BF::ZEFE,
+                        ],
+                    ),
+                    I2cTrans::write(ADDRESS, vec![Register::FF_MT_THS, 3, 12]),
+                ]);
+                sensor.set_data_rate(OutputDataRate::Hz100).unwrap();
+                sensor
+                    .set_freefall_motion_config(FreefallMotionConfig {
+                        mode: FreefallMotionMode::Freefall,
+                        x_axis: true,
+                        y_axis: true,
+                        z_axis: true,
+                        latch: true,
+                        threshold: 0.2,
+                        debounce_time_ms: 120.0,
+                        ..FreefallMotionConfig::default()
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_set_motion_config() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::FF_MT_CFG, BF::OAE | BF::ZEFE]),
+                    I2cTrans::write(ADDRESS, vec![Register::FF_MT_THS, BF::FF_MT_DBCNTM | 48, 4]),
+                ]);
+                sensor
+                    .set_freefall_motion_config(FreefallMotionConfig {
+                        mode: FreefallMotionMode::Motion,
+                        z_axis: true,
+                        threshold: 3.0,
+                        debounce_counter_mode: DebounceCounterMode::Clear,
+                        debounce_time_ms: 5.0,
+                        ..FreefallMotionConfig::default()
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn cannot_set_invalid_threshold() {
+                let mut sensor = $create(&[]);
+                assert!(matches!(
+                    sensor.set_freefall_motion_config(FreefallMotionConfig {
+                        threshold: 9.0,
+                        ..FreefallMotionConfig::default()
+                    }),
+                    Err(Error::InvalidInputData)
+                ));
+                destroy(sensor);
+            }
+
+            get_test!(
+                can_get_freefall_motion_source,
+                $create,
+                FF_MT_SRC,
+                BF::FF_MT_EA | BF::ZHE | BF::ZHP,
+                freefall_motion_source,
+                FfMtSource {
+                    event_active: true,
+                    z_event: true,
+                    z_polarity: EventPolarity::Negative,
+                    ..FfMtSource::default()
+                }
+            );
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451);
+tests!(mma8452, new_mma8452);
+tests!(mma8453, new_mma8453);
+tests!(mma8652, new_mma8652);
+tests!(mma8653, new_mma8653);
@@ -0,0 +1,131 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, BitFlags as BF, Register, ADDRESS,
+};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mma8x5x::{GScale, HighPassConfig, HighPassFilterCutoff, PulseConfig, UnscaledMeasurement};
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $bit_shift:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_set_default_config() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::HP_FILTER_CUTOFF, 0]),
+                ]);
+                sensor
+                    .set_high_pass_filter(HighPassConfig::default())
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_set_config() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, BF::HPF_OUT]),
+                    I2cTrans::write(
+                        ADDRESS,
+                        vec![
+                            Register::HP_FILTER_CUTOFF,
+                            BF::PULSE_HPF_BYP | BF::PULSE_LPF_EN | BF::SEL1,
+                        ],
+                    ),
+                ]);
+                sensor
+                    .set_high_pass_filter(HighPassConfig {
+                        output_enabled: true,
+                        cutoff: HighPassFilterCutoff::Low,
+                        pulse_hpf_bypass: true,
+                        pulse_lpf_enabled: true,
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn scale_and_hpf_output_are_kept() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, BF::FS1]),
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, BF::HPF_OUT | BF::FS1]),
+                    I2cTrans::write(ADDRESS, vec![Register::HP_FILTER_CUTOFF, BF::SEL0]),
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, BF::HPF_OUT | BF::FS0]),
+                ]);
+                sensor.set_scale(GScale::G8).unwrap();
+                sensor
+                    .set_high_pass_filter(HighPassConfig {
+                        output_enabled: true,
+                        cutoff: HighPassFilterCutoff::High,
+                        ..HighPassConfig::default()
+                    })
+                    .unwrap();
+                sensor.set_scale(GScale::G4).unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_read_filtered_data() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, BF::HPF_OUT]),
+                    I2cTrans::write(ADDRESS, vec![Register::HP_FILTER_CUTOFF, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                sensor
+                    .set_high_pass_filter(HighPassConfig {
+                        output_enabled: true,
+                        ..HighPassConfig::default()
+                    })
+                    .unwrap();
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let expected = UnscaledMeasurement {
+                    x: 0x4140 >> $bit_shift,
+                    y: 0x4280 >> $bit_shift,
+                    z: 0x43C0 >> $bit_shift,
+                };
+                assert_eq!(expected, sensor.read_unscaled().unwrap());
+                destroy(sensor);
+            }
+
+            #[test]
+            fn pulse_time_depends_on_pulse_lpf() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::HP_FILTER_CUTOFF, BF::PULSE_LPF_EN]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ODR0]),
+                    I2cTrans::write(ADDRESS, vec![Register::PULSE_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::PULSE_THSX, 0, 0, 0, 8, 4, 2]),
+                ]);
+                sensor
+                    .set_high_pass_filter(HighPassConfig {
+                        pulse_lpf_enabled: true,
+                        ..HighPassConfig::default()
+                    })
+                    .unwrap();
+                sensor
+                    .set_data_rate(mma8x5x::OutputDataRate::Hz400)
+                    .unwrap();
+                sensor
+                    .set_pulse_config(PulseConfig {
+                        time_limit_ms: 20.0,
+                        latency_ms: 20.0,
+                        window_ms: 10.0,
+                        ..PulseConfig::default()
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, 2);
+tests!(mma8452, new_mma8452, 4);
+tests!(mma8453, new_mma8453, 6);
+tests!(mma8652, new_mma8652, 4);
@@ -0,0 +1,123 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF,
+    Register, ADDRESS,
+};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTrans},
+    i2c::Transaction as I2cTrans,
+};
+use mma8x5x::Error;
+
+const NACK: ErrorKind = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+
+fn read_ctrl_reg2(value: u8) -> I2cTrans {
+    I2cTrans::write_read(ADDRESS, vec![Register::CTRL_REG2], vec![value])
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $id:expr, $other_id:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_init() {
+                let mut sensor = $create(&[
+                    I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![$id]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::RST]),
+                    read_ctrl_reg2(0).with_error(NACK),
+                    read_ctrl_reg2(BF::RST),
+                    read_ctrl_reg2(0),
+                ]);
+                let mut delay = CheckedDelay::new(&[
+                    DelayTrans::delay_ms(1),
+                    DelayTrans::delay_ms(1),
+                    DelayTrans::delay_ms(1),
+                ]);
+                sensor.init(&mut delay).unwrap();
+                delay.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn init_resets_shadow_registers() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 2]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![$id]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::RST]),
+                    read_ctrl_reg2(0),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                ]);
+                let mut delay = CheckedDelay::new(&[DelayTrans::delay_ms(1)]);
+                sensor.set_read_mode(mma8x5x::ReadMode::Fast).unwrap();
+                sensor.init(&mut delay).unwrap();
+                let sensor = sensor.into_active().ok().unwrap();
+                delay.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn rejects_wrong_device() {
+                let mut sensor = $create(&[I2cTrans::write_read(
+                    ADDRESS,
+                    vec![Register::WHO_AM_I],
+                    vec![$other_id],
+                )]);
+                let mut delay = CheckedDelay::new(&[]);
+                assert!(matches!(
+                    sensor.init(&mut delay),
+                    Err(Error::InvalidDevice(id)) if id == $other_id
+                ));
+                delay.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn times_out_if_reset_does_not_finish() {
+                let mut transactions = vec![
+                    I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![$id]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::RST]),
+                ];
+                transactions.extend((0..10).map(|_| read_ctrl_reg2(BF::RST)));
+                let mut sensor = $create(&transactions);
+                let mut delay = CheckedDelay::new(&vec![DelayTrans::delay_ms(1); 10]);
+                assert!(matches!(
+                    sensor.init(&mut delay),
+                    Err(Error::ResetTimeout)
+                ));
+                delay.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn returns_bus_error_if_device_does_not_come_back() {
+                let mut transactions = vec![
+                    I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![$id]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::RST]),
+                ];
+                transactions.extend((0..10).map(|_| read_ctrl_reg2(0).with_error(NACK)));
+                let mut sensor = $create(&transactions);
+                let mut delay = CheckedDelay::new(&vec![DelayTrans::delay_ms(1); 10]);
+                assert!(matches!(sensor.init(&mut delay), Err(Error::I2C(_))));
+                delay.done();
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, 0x1A, 0x2A);
+tests!(mma8452, new_mma8452, 0x2A, 0x4A);
+tests!(mma8453, new_mma8453, 0x3A, 0x1A);
+tests!(mma8652, new_mma8652, 0x4A, 0x2A);
+tests!(mma8653, new_mma8653, 0x5A, 0x4A);
+
+#[test]
+fn mma845x_have_selectable_address() {
+    use mma8x5x::ic::{self, HasSelectableAddress};
+    fn selectable<IC: HasSelectableAddress>() {}
+    selectable::<ic::Mma8451>();
+    selectable::<ic::Mma8452>();
+    selectable::<ic::Mma8453>();
+}
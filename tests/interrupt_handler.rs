@@ -0,0 +1,296 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF,
+    Register, ADDRESS,
+};
+use embedded_hal_mock::eh1::{
+    digital::{Mock as PinMock, State as PinState, Transaction as PinTrans},
+    i2c::Transaction as I2cTrans,
+};
+use mma8x5x::{
+    EnabledInterrupts, Event, FfMtSource, FifoStatus, Int1, Int2, InterruptPinPolarity,
+    InterruptPinRoutes, InterruptSourcePinRoute, PortraitLandscapeStatus, PulseSource, SystemMode,
+    TransientSource, UnscaledMeasurement,
+};
+
+fn data_ready_int1() -> (EnabledInterrupts, InterruptPinRoutes) {
+    (
+        EnabledInterrupts {
+            data_ready: true,
+            ..EnabledInterrupts::default()
+        },
+        InterruptPinRoutes {
+            data_ready: InterruptSourcePinRoute::Int1,
+            ..InterruptPinRoutes::default()
+        },
+    )
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $bit_shift:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn no_event_if_pin_inactive() {
+                let (enabled, routes) = data_ready_int1();
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG4, BF::INT_EN_DRDY]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG5, BF::INT_CFG_DRDY]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                ]);
+                sensor.set_enabled_interrupts(enabled).unwrap();
+                sensor.set_interrupt_pin_routes(routes).unwrap();
+                let pin = PinMock::new(&[PinTrans::get(PinState::High)]);
+                let mut handler = sensor.interrupt_handler(Int1(pin));
+                let mut sensor = sensor.into_active().ok().unwrap();
+                assert_eq!(None, handler.service(&mut sensor).unwrap());
+                handler.destroy().0.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_service_data_ready() {
+                let (enabled, routes) = data_ready_int1();
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG4, BF::INT_EN_DRDY]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG5, BF::INT_CFG_DRDY]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                    I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![BF::SRC_DRDY]),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::OUT_X_H],
+                        vec![0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                sensor.set_enabled_interrupts(enabled).unwrap();
+                sensor.set_interrupt_pin_routes(routes).unwrap();
+                let pin = PinMock::new(&[PinTrans::get(PinState::Low)]);
+                let mut handler = sensor.interrupt_handler(Int1(pin));
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let expected = UnscaledMeasurement {
+                    x: 0x4140 >> $bit_shift,
+                    y: 0x4280 >> $bit_shift,
+                    z: 0x43C0 >> $bit_shift,
+                };
+                assert_eq!(
+                    Some(Event::DataReady(expected)),
+                    handler.service(&mut sensor).unwrap()
+                );
+                handler.destroy().0.done();
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, 2);
+tests!(mma8452, new_mma8452, 4);
+tests!(mma8453, new_mma8453, 6);
+tests!(mma8652, new_mma8652, 4);
+tests!(mma8653, new_mma8653, 6);
+
+const ALL_SOURCES: u8 = BF::SRC_ASLP
+    | BF::SRC_FIFO
+    | BF::SRC_TRANS
+    | BF::SRC_LNDPRT
+    | BF::SRC_PULSE
+    | BF::SRC_FF_MT
+    | BF::SRC_DRDY;
+
+macro_rules! event_test {
+    ($name:ident, $int_en:ident, $src_reg:ident, $src_value:expr, $event:expr) => {
+        #[test]
+        fn $name() {
+            let mut sensor = new_mma8451(&[
+                I2cTrans::write(ADDRESS, vec![Register::CTRL_REG4, BF::$int_en]),
+                I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+                I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![ALL_SOURCES]),
+                I2cTrans::write_read(ADDRESS, vec![Register::$src_reg], vec![$src_value]),
+            ]);
+            sensor
+                .set_enabled_interrupts(EnabledInterrupts {
+                    $name: true,
+                    ..EnabledInterrupts::default()
+                })
+                .unwrap();
+            let pin = PinMock::new(&[PinTrans::get(PinState::Low)]);
+            let mut handler = sensor.interrupt_handler(Int2(pin));
+            let mut sensor = sensor.into_active().ok().unwrap();
+            assert_eq!(Some($event), handler.service(&mut sensor).unwrap());
+            handler.destroy().0.done();
+            destroy(sensor);
+        }
+    };
+}
+
+mod events {
+    use super::*;
+
+    event_test!(
+        auto_sleep,
+        INT_EN_ASLP,
+        SYSMOD,
+        2,
+        Event::AutoSleep(SystemMode::Sleep)
+    );
+    event_test!(
+        fifo,
+        INT_EN_FIFO,
+        STATUS,
+        BF::F_OVF | 32,
+        Event::Fifo(FifoStatus {
+            overflow: true,
+            watermark: false,
+            count: 32
+        })
+    );
+    event_test!(
+        transient,
+        INT_EN_TRANS,
+        TRANSIENT_SRC,
+        BF::TRANS_EA | BF::XTRANSE,
+        Event::Transient(TransientSource {
+            event_active: true,
+            x_event: true,
+            ..TransientSource::default()
+        })
+    );
+    event_test!(
+        portrait_landscape,
+        INT_EN_LNDPRT,
+        PL_STATUS,
+        BF::NEWLP,
+        Event::PortraitLandscape(PortraitLandscapeStatus {
+            something_changed: true,
+            ..PortraitLandscapeStatus::default()
+        })
+    );
+    event_test!(
+        pulse,
+        INT_EN_PULSE,
+        PULSE_SRC,
+        BF::PULSE_EA | BF::AX_Z,
+        Event::Pulse(PulseSource {
+            event_active: true,
+            z_event: true,
+            ..PulseSource::default()
+        })
+    );
+    event_test!(
+        freefall_motion,
+        INT_EN_FF_MT,
+        FF_MT_SRC,
+        BF::FF_MT_EA | BF::YHE,
+        Event::FreefallMotion(FfMtSource {
+            event_active: true,
+            y_event: true,
+            ..FfMtSource::default()
+        })
+    );
+}
+
+#[test]
+fn services_one_source_per_call_in_order() {
+    let mut sensor = new_mma8452(&[
+        I2cTrans::write(
+            ADDRESS,
+            vec![Register::CTRL_REG4, BF::INT_EN_ASLP | BF::INT_EN_PULSE],
+        ),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::INT_SOURCE],
+            vec![BF::SRC_ASLP | BF::SRC_PULSE],
+        ),
+        I2cTrans::write_read(ADDRESS, vec![Register::SYSMOD], vec![1]),
+        I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![BF::SRC_PULSE]),
+        I2cTrans::write_read(ADDRESS, vec![Register::PULSE_SRC], vec![0]),
+    ]);
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            auto_sleep: true,
+            pulse: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    let pin = PinMock::new(&[
+        PinTrans::get(PinState::Low),
+        PinTrans::get(PinState::Low),
+        PinTrans::get(PinState::High),
+    ]);
+    let mut handler = sensor.interrupt_handler(Int2(pin));
+    let mut sensor = sensor.into_active().ok().unwrap();
+    assert_eq!(
+        Some(Event::AutoSleep(SystemMode::Wake)),
+        handler.service(&mut sensor).unwrap()
+    );
+    assert_eq!(
+        Some(Event::Pulse(PulseSource::default())),
+        handler.service(&mut sensor).unwrap()
+    );
+    assert_eq!(None, handler.service(&mut sensor).unwrap());
+    handler.destroy().0.done();
+    destroy(sensor);
+}
+
+#[test]
+fn ignores_sources_routed_to_inactive_pin() {
+    let (_, routes) = data_ready_int1();
+    let mut sensor = new_mma8652(&[
+        I2cTrans::write(
+            ADDRESS,
+            vec![Register::CTRL_REG4, BF::INT_EN_DRDY | BF::INT_EN_FF_MT],
+        ),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG5, BF::INT_CFG_DRDY]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::INT_SOURCE],
+            vec![BF::SRC_DRDY | BF::SRC_FF_MT],
+        ),
+        I2cTrans::write_read(ADDRESS, vec![Register::FF_MT_SRC], vec![0]),
+    ]);
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            data_ready: true,
+            freefall_motion: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    sensor.set_interrupt_pin_routes(routes).unwrap();
+    let int1 = PinMock::new(&[PinTrans::get(PinState::High)]);
+    let int2 = PinMock::new(&[PinTrans::get(PinState::Low)]);
+    let mut handler = sensor.interrupt_handler((Int1(int1), Int2(int2)));
+    let mut sensor = sensor.into_active().ok().unwrap();
+    assert_eq!(
+        Some(Event::FreefallMotion(FfMtSource::default())),
+        handler.service(&mut sensor).unwrap()
+    );
+    let (Int1(mut int1), Int2(mut int2)) = handler.destroy();
+    int1.done();
+    int2.done();
+    destroy(sensor);
+}
+
+#[test]
+fn uses_configured_polarity() {
+    let (enabled, routes) = data_ready_int1();
+    let mut sensor = new_mma8653(&[
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG3, BF::IPOL]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG4, BF::INT_EN_DRDY]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG5, BF::INT_CFG_DRDY]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 1]),
+    ]);
+    sensor
+        .set_interrupt_pin_polarity(InterruptPinPolarity::ActiveHigh)
+        .unwrap();
+    sensor.set_enabled_interrupts(enabled).unwrap();
+    sensor.set_interrupt_pin_routes(routes).unwrap();
+    let pin = PinMock::new(&[PinTrans::get(PinState::Low)]);
+    let mut handler = sensor.interrupt_handler(Int1(pin));
+    let mut sensor = sensor.into_active().ok().unwrap();
+    assert_eq!(None, handler.service(&mut sensor).unwrap());
+    handler.destroy().0.done();
+    destroy(sensor);
+}
@@ -0,0 +1,59 @@
+mod base;
+use crate::base::{destroy, new_mma8451, new_mma8652, Register, ADDRESS};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mma8x5x::{
+    BackFrontTripAngle, PortraitLandscapeAngles, PortraitLandscapeHysteresis,
+    PortraitLandscapeThreshold, ZLockoutAngle,
+};
+
+macro_rules! tests {
+    ($name:ident, $create:ident) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_set_default_angles() {
+                let mut sensor = $create(&[I2cTrans::write(
+                    ADDRESS,
+                    vec![Register::PL_BF_ZCOMP, 0x44, 0x84],
+                )]);
+                sensor
+                    .set_portrait_landscape_angles(PortraitLandscapeAngles::default())
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_set_angles() {
+                let mut sensor = $create(&[I2cTrans::write(
+                    ADDRESS,
+                    vec![Register::PL_BF_ZCOMP, 0x82, (0x0C << 3) | 1],
+                )]);
+                sensor
+                    .set_portrait_landscape_angles(PortraitLandscapeAngles {
+                        back_front_trip: BackFrontTripAngle::Deg70,
+                        z_lockout: ZLockoutAngle::Deg21,
+                        threshold: PortraitLandscapeThreshold::Deg30,
+                        hysteresis: PortraitLandscapeHysteresis::Deg4,
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451);
+tests!(mma8652, new_mma8652);
+
+#[test]
+fn orientation_capabilities_match_devices() {
+    use mma8x5x::ic::{self, HasFixedOrientation, HasProgrammableOrientation};
+    fn programmable<IC: HasProgrammableOrientation>() {}
+    fn fixed<IC: HasFixedOrientation>() {}
+    programmable::<ic::Mma8451>();
+    programmable::<ic::Mma8652>();
+    fixed::<ic::Mma8452>();
+    fixed::<ic::Mma8453>();
+    fixed::<ic::Mma8653>();
+}
@@ -0,0 +1,105 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, BitFlags as BF, Register, ADDRESS,
+};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mma8x5x::{Error, EventPolarity, OutputDataRate, PulseConfig, PulseSource};
+
+macro_rules! tests {
+    ($name:ident, $create:ident) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_set_default_config() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::PULSE_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::PULSE_THSX, 0, 0, 0, 0, 0, 0]),
+                ]);
+                sensor.set_pulse_config(PulseConfig::default()).unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_set_config() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ODR2]),
+                    I2cTrans::write(
+                        ADDRESS,
+                        vec![
+                            Register::PULSE_CFG,
+                            BF::PULSE_ELE | BF::XSPEFE | BF::ZSPEFE | BF::ZDPEFE,
+                        ],
+                    ),
+                    I2cTrans::write(
+                        ADDRESS,
+                        vec![Register::PULSE_THSX, 32, 0x7F, 16, 12, 10, 24],
+                    ),
+                ]);
+                sensor.set_data_rate(OutputDataRate::Hz50).unwrap();
+                sensor
+                    .set_pulse_config(PulseConfig {
+                        x_single: true,
+                        z_single: true,
+                        z_double: true,
+                        latch: true,
+                        x_threshold: 2.0,
+                        y_threshold: 8.0,
+                        z_threshold: 1.0,
+                        time_limit_ms: 60.0,
+                        latency_ms: 100.0,
+                        window_ms: 240.0,
+                        ..PulseConfig::default()
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn cannot_set_invalid_threshold() {
+                let mut sensor = $create(&[]);
+                assert!(matches!(
+                    sensor.set_pulse_config(PulseConfig {
+                        z_threshold: -1.0,
+                        ..PulseConfig::default()
+                    }),
+                    Err(Error::InvalidInputData)
+                ));
+                destroy(sensor);
+            }
+
+            #[test]
+            fn cannot_set_invalid_window() {
+                let mut sensor = $create(&[]);
+                assert!(matches!(
+                    sensor.set_pulse_config(PulseConfig {
+                        window_ms: 400.0,
+                        ..PulseConfig::default()
+                    }),
+                    Err(Error::InvalidInputData)
+                ));
+                destroy(sensor);
+            }
+
+            get_test!(
+                can_get_pulse_source,
+                $create,
+                PULSE_SRC,
+                BF::PULSE_EA | BF::DPE | BF::AX_X | BF::POL_X,
+                pulse_source,
+                PulseSource {
+                    event_active: true,
+                    double_pulse: true,
+                    x_event: true,
+                    x_polarity: EventPolarity::Negative,
+                    ..PulseSource::default()
+                }
+            );
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451);
+tests!(mma8452, new_mma8452);
+tests!(mma8453, new_mma8453);
+tests!(mma8652, new_mma8652);
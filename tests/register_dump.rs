@@ -0,0 +1,112 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF,
+    Register, ADDRESS,
+};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mma8x5x::{AnyMma8x5x, DetectedIc, ReadMode};
+
+const CONFIG_LEN: usize = (Register::OFF_Z - Register::F_SETUP + 1) as usize;
+
+fn read_config(device_id: u8, ctrl_reg1: u8) -> I2cTrans {
+    let mut config = vec![0; CONFIG_LEN];
+    config[usize::from(Register::WHO_AM_I - Register::F_SETUP)] = device_id;
+    config[usize::from(Register::CTRL_REG1 - Register::F_SETUP)] = ctrl_reg1;
+    I2cTrans::write_read(ADDRESS, vec![Register::F_SETUP], config)
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $ic:ident) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_dump_registers() {
+                let id = DetectedIc::$ic.device_id();
+                let mut sensor = $create(&[
+                    read_config(id, BF::ODR1 | BF::ODR0 | BF::ACTIVE),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::STATUS],
+                        vec![BF::XYZDR, 0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                let dump = sensor.dump_registers().unwrap();
+                assert_eq!(DetectedIc::$ic, dump.ic());
+                assert_eq!(Some(BF::XYZDR), dump.register(Register::STATUS));
+                assert_eq!(Some(0x40), dump.register(Register::OUT_X_L));
+                assert_eq!(Some(id), dump.register(Register::WHO_AM_I));
+                let text = dump.to_string();
+                assert!(text.contains("CTRL_REG1: ODR=100Hz ASLP_RATE=50Hz"));
+                assert!(text.contains(&format!("WHO_AM_I: 0x{:02X}", id)));
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_dump_registers_in_fast_read_mode() {
+                let id = DetectedIc::$ic.device_id();
+                // The read mode is taken from the device, not from the driver
+                let mut sensor = $create(&[
+                    read_config(id, BF::F_READ),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::STATUS],
+                        vec![BF::XYZDR, 0x41, 0x42, 0x43],
+                    ),
+                ]);
+                let dump = sensor.dump_registers().unwrap();
+                assert_eq!(Some(0x41), dump.register(Register::OUT_X_H));
+                assert_eq!(Some(0), dump.register(Register::OUT_X_L));
+                assert_eq!(Some(0x42), dump.register(Register::OUT_Y_H));
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_dump_registers_in_normal_mode_after_fast_read() {
+                let id = DetectedIc::$ic.device_id();
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::F_READ]),
+                    read_config(id, 0),
+                    I2cTrans::write_read(
+                        ADDRESS,
+                        vec![Register::STATUS],
+                        vec![BF::XYZDR, 0x41, 0x40, 0x42, 0x80, 0x43, 0xC0],
+                    ),
+                ]);
+                sensor.set_read_mode(ReadMode::Fast).unwrap();
+                let dump = sensor.dump_registers().unwrap();
+                assert_eq!(Some(0x40), dump.register(Register::OUT_X_L));
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_diff_dumps() {
+                let id = DetectedIc::$ic.device_id();
+                let sensor = $create(&[
+                    read_config(id, 0),
+                    I2cTrans::write_read(ADDRESS, vec![Register::STATUS], vec![0; 7]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ACTIVE]),
+                    read_config(id, BF::ACTIVE),
+                    I2cTrans::write_read(ADDRESS, vec![Register::STATUS], vec![0; 7]),
+                ]);
+                let mut sensor = AnyMma8x5x::from(sensor);
+                let before = sensor.dump_registers().unwrap();
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let after = sensor.dump_registers().unwrap();
+                let mut diff = before.diff(&after);
+                assert_eq!("CTRL_REG1: ACTIVE=0->1", diff.to_string());
+                let change = diff.next().unwrap();
+                assert_eq!(Register::CTRL_REG1, change.address);
+                assert_eq!((0, BF::ACTIVE), (change.old, change.new));
+                assert_eq!(None, diff.next());
+                sensor.destroy().done();
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, Mma8451);
+tests!(mma8452, new_mma8452, Mma8452);
+tests!(mma8453, new_mma8453, Mma8453);
+tests!(mma8652, new_mma8652, Mma8652);
+tests!(mma8653, new_mma8653, Mma8653);
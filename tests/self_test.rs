@@ -0,0 +1,177 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF,
+    Register, ADDRESS,
+};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTrans},
+    i2c::Transaction as I2cTrans,
+};
+use mma8x5x::{Error, FifoMode, GScale, SelfTestAxis};
+
+const ODR_100HZ: u8 = BF::ODR1 | BF::ODR0;
+
+fn sample(x: i16, y: i16, z: i16, bit_shift: u8) -> I2cTrans {
+    let mut data = Vec::new();
+    for value in [x, y, z] {
+        data.extend_from_slice(&((value << bit_shift) as u16).to_be_bytes());
+    }
+    I2cTrans::write_read(ADDRESS, vec![Register::OUT_X_H], data)
+}
+
+fn sampling(x: i16, y: i16, z: i16, bit_shift: u8) -> Vec<I2cTrans> {
+    let mut transactions = vec![I2cTrans::write(
+        ADDRESS,
+        vec![Register::CTRL_REG1, ODR_100HZ | 1],
+    )];
+    for _ in 0..8 {
+        transactions.push(sample(x, y, z, bit_shift));
+    }
+    transactions.push(I2cTrans::write(
+        ADDRESS,
+        vec![Register::CTRL_REG1, ODR_100HZ],
+    ));
+    transactions
+}
+
+fn write_f_setup(fifo: bool, f_setup: u8) -> Vec<I2cTrans> {
+    if fifo {
+        vec![I2cTrans::write(ADDRESS, vec![Register::F_SETUP, f_setup])]
+    } else {
+        Vec::new()
+    }
+}
+
+fn self_test_transactions(on: (i16, i16, i16), bit_shift: u8, fifo: bool) -> Vec<I2cTrans> {
+    let mut transactions = vec![
+        I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 2]),
+        I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+    ];
+    transactions.extend(write_f_setup(fifo, 0));
+    transactions.extend(sampling(-3, 2, 100, bit_shift));
+    transactions.push(I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::ST]));
+    transactions.extend(sampling(on.0, on.1, on.2, bit_shift));
+    transactions.extend([
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+        I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 2]),
+    ]);
+    transactions.extend(write_f_setup(fifo, 0));
+    transactions
+}
+
+fn self_test_delays() -> CheckedDelay {
+    let mut delays = Vec::new();
+    for _ in 0..2 {
+        delays.push(DelayTrans::delay_ms(20));
+        for _ in 0..8 {
+            delays.push(DelayTrans::delay_ms(10));
+        }
+    }
+    CheckedDelay::new(&delays)
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $bit_shift:expr, ($x:expr, $y:expr, $z:expr), $z_limits:expr, $fifo:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn passes_self_test() {
+                let mut sensor = $create(&self_test_transactions(
+                    (-3 + $x, 2 + $y, 100 + $z),
+                    $bit_shift,
+                    $fifo,
+                ));
+                let mut delay = self_test_delays();
+                sensor.set_scale(GScale::G8).unwrap();
+                let report = sensor.run_self_test(&mut delay).unwrap();
+                assert_eq!(
+                    SelfTestAxis {
+                        delta: $z,
+                        min: $z_limits.0,
+                        max: $z_limits.1,
+                        passed: true
+                    },
+                    report.z
+                );
+                assert_eq!($x, report.x.delta);
+                assert_eq!($y, report.y.delta);
+                assert!(report.passed);
+                delay.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn fails_self_test_without_output_change() {
+                let mut sensor = $create(&self_test_transactions((-3, 2, 100), $bit_shift, $fifo));
+                let mut delay = self_test_delays();
+                sensor.set_scale(GScale::G8).unwrap();
+                let report = sensor.run_self_test(&mut delay).unwrap();
+                assert_eq!(0, report.x.delta);
+                assert_eq!(0, report.y.delta);
+                assert_eq!(0, report.z.delta);
+                assert!(!report.x.passed);
+                assert!(!report.y.passed);
+                assert!(!report.z.passed);
+                assert!(!report.passed);
+                delay.done();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn restores_configuration_after_error() {
+                let mut transactions = vec![
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+                ];
+                transactions.extend(write_f_setup($fifo, 0));
+                transactions.extend([
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, ODR_100HZ | 1]),
+                    sample(0, 0, 0, $bit_shift)
+                        .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0]),
+                ]);
+                transactions.extend(write_f_setup($fifo, 0));
+                let mut sensor = $create(&transactions);
+                let mut delay =
+                    CheckedDelay::new(&[DelayTrans::delay_ms(20), DelayTrans::delay_ms(10)]);
+                assert!(matches!(
+                    sensor.run_self_test(&mut delay),
+                    Err(Error::I2C(_))
+                ));
+                delay.done();
+                destroy(sensor);
+            }
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451, 2, (181, 255, 1680), (840, 3360), true);
+tests!(mma8452, new_mma8452, 4, (44, 61, 392), (196, 784), false);
+tests!(mma8453, new_mma8453, 6, (11, 17, 98), (49, 196), false);
+tests!(mma8652, new_mma8652, 4, (48, 52, 356), (178, 712), true);
+tests!(mma8653, new_mma8653, 6, (12, 13, 89), (44, 178), false);
+
+#[test]
+fn disables_fifo_while_sampling() {
+    let mut transactions = vec![
+        I2cTrans::write(ADDRESS, vec![Register::F_SETUP, BF::F_MODE0]),
+        I2cTrans::write(ADDRESS, vec![Register::F_SETUP, BF::F_MODE0 | 20]),
+    ];
+    transactions.extend(self_test_transactions((-3, 2, 100), 4, true));
+    let last = transactions.len() - 1;
+    transactions[last] = I2cTrans::write(ADDRESS, vec![Register::F_SETUP, BF::F_MODE0 | 20]);
+    let mut sensor = new_mma8652(&transactions);
+    let mut delay = self_test_delays();
+    sensor.set_fifo_mode(FifoMode::Circular).unwrap();
+    sensor.set_fifo_watermark(20).unwrap();
+    sensor.set_scale(GScale::G8).unwrap();
+    sensor.run_self_test(&mut delay).unwrap();
+    delay.done();
+    destroy(sensor);
+}
@@ -0,0 +1,678 @@
+#![cfg(feature = "sim")]
+
+mod base;
+use crate::base::{BitFlags as BF, Register, ADDRESS};
+use core::{cell::Cell, time::Duration};
+use embedded_hal::{
+    digital::InputPin,
+    i2c::{ErrorKind, I2c, NoAcknowledgeSource},
+};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use mma8x5x::{
+    sim::SimulatedMma8x5x, Axis, DecodedEvent, DetectedDevice, DetectedIc, EnabledInterrupts,
+    Error, Event, EventAxes, EventPolarity, FreefallMotionConfig, FrontBackOrientation, GScale,
+    HighPassConfig, Int1, InterruptPinPolarity, InterruptPinRoutes, InterruptSourcePinRoute,
+    Measurement, Mma8x5x, OutputDataRate, PortraitLandscapeOrientation, PortraitLandscapeStatus,
+    PulseConfig, ReadMode, SlaveAddr, SystemMode, TransientConfig, UnscaledMeasurement,
+    WakeInterrupts,
+};
+
+fn acceleration() -> Measurement {
+    Measurement {
+        x: 0.5,
+        y: -1.0,
+        z: 1.0,
+    }
+}
+
+fn measurement(axes: &Cell<(f32, f32, f32)>) -> Measurement {
+    let (x, y, z) = axes.get();
+    Measurement { x, y, z }
+}
+
+const SAMPLE_PERIOD: Duration = Duration::from_micros(1250);
+
+macro_rules! tests {
+    ($name:ident, $create:ident, [$($addr:expr)?], $ic:ident, $counts_per_g:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_read_unscaled() {
+                let sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                let sensor = Mma8x5x::$create(sim $(, $addr)?);
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let expected = UnscaledMeasurement {
+                    x: $counts_per_g / 2,
+                    y: -$counts_per_g,
+                    z: $counts_per_g,
+                };
+                assert_eq!(expected, sensor.read_unscaled().unwrap());
+            }
+
+            #[test]
+            fn can_read_scaled() {
+                let sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                let mut sensor = Mma8x5x::$create(sim $(, $addr)?);
+                sensor.set_scale(GScale::G8).unwrap();
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let expected = UnscaledMeasurement {
+                    x: $counts_per_g / 8,
+                    y: -$counts_per_g / 4,
+                    z: $counts_per_g / 4,
+                };
+                assert_eq!(expected, sensor.read_unscaled().unwrap());
+                let m = sensor.read().unwrap();
+                assert_eq!((0.5, -1.0, 1.0), (m.x, m.y, m.z));
+            }
+
+            #[test]
+            fn can_read_fast() {
+                let sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                let mut sensor = Mma8x5x::$create(sim $(, $addr)?);
+                sensor.set_read_mode(ReadMode::Fast).unwrap();
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let m = sensor.read_unscaled().unwrap();
+                assert_eq!(-$counts_per_g, m.y);
+                assert_eq!($counts_per_g, m.z);
+            }
+
+            #[test]
+            fn can_detect() {
+                let sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                let device = DetectedDevice::detect(sim).ok().unwrap();
+                assert_eq!(DetectedIc::$ic, device.ic());
+            }
+
+            #[test]
+            fn can_init() {
+                let sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                let mut sensor = Mma8x5x::$create(sim $(, $addr)?);
+                sensor.set_scale(GScale::G4).unwrap();
+                sensor.init(&mut NoopDelay::new()).unwrap();
+                let sim = sensor.destroy();
+                assert_eq!(0, sim.register(Register::XYZ_DATA_CFG));
+                assert_eq!(0, sim.register(Register::CTRL_REG2));
+            }
+
+            #[test]
+            fn reset_restores_reset_values() {
+                let mut sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                sim.write(ADDRESS, &[Register::PL_CFG, BF::PL_EN]).unwrap();
+                sim.write(ADDRESS, &[Register::CTRL_REG1, BF::ACTIVE]).unwrap();
+                sim.write(ADDRESS, &[Register::CTRL_REG2, BF::RST]).unwrap();
+                assert_eq!(0, sim.register(Register::CTRL_REG1));
+                assert_eq!(0, sim.register(Register::CTRL_REG2));
+                assert_eq!(BF::DBCNTM, sim.register(Register::PL_CFG));
+                assert_eq!(0x44, sim.register(Register::PL_BF_ZCOMP));
+                assert_eq!(0x84, sim.register(Register::P_L_THS_REG));
+            }
+
+            #[test]
+            fn ignores_standby_only_writes_in_active_mode() {
+                let mut sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                sim.write(ADDRESS, &[Register::CTRL_REG1, BF::ACTIVE]).unwrap();
+                sim.write(ADDRESS, &[Register::XYZ_DATA_CFG, BF::FS1]).unwrap();
+                sim.write(ADDRESS, &[Register::CTRL_REG1, BF::ODR0 | BF::ACTIVE])
+                    .unwrap();
+                assert_eq!(0, sim.register(Register::XYZ_DATA_CFG));
+                assert_eq!(BF::ACTIVE, sim.register(Register::CTRL_REG1));
+                sim.write(ADDRESS, &[Register::CTRL_REG1, BF::ODR0]).unwrap();
+                assert_eq!(0, sim.register(Register::CTRL_REG1));
+                sim.write(ADDRESS, &[Register::XYZ_DATA_CFG, BF::FS1]).unwrap();
+                assert_eq!(BF::FS1, sim.register(Register::XYZ_DATA_CFG));
+            }
+
+            #[test]
+            fn ignores_writes_to_read_only_registers() {
+                let mut sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                sim.write(ADDRESS, &[Register::WHO_AM_I, 0]).unwrap();
+                sim.write(ADDRESS, &[Register::INT_SOURCE, 0xFF]).unwrap();
+                assert_eq!(DetectedIc::$ic.device_id(), sim.register(Register::WHO_AM_I));
+                assert_eq!(0, sim.register(Register::INT_SOURCE));
+            }
+
+            #[test]
+            fn reports_system_mode() {
+                let sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                let sensor = Mma8x5x::$create(sim $(, $addr)?);
+                let mut sensor = sensor.into_active().ok().unwrap();
+                assert_eq!(SystemMode::Wake, sensor.system_mode().unwrap());
+                assert!(sensor.data_status().unwrap().xyz_new_data);
+            }
+
+            #[test]
+            fn can_dump_registers() {
+                let sim = SimulatedMma8x5x::$create($($addr,)? acceleration);
+                let mut sensor = Mma8x5x::$create(sim $(, $addr)?);
+                sensor.set_offset_correction(1, 2, -3).unwrap();
+                let mut sensor = sensor.into_active().ok().unwrap();
+                let dump = sensor.dump_registers().unwrap();
+                assert_eq!(DetectedIc::$ic, dump.ic());
+                assert_eq!(Some(DetectedIc::$ic.device_id()), dump.register(Register::WHO_AM_I));
+                assert_eq!(Some(0x44), dump.register(Register::PL_BF_ZCOMP));
+                assert_eq!(Some(BF::ACTIVE), dump.register(Register::CTRL_REG1));
+                let text = dump.to_string();
+                assert!(text.contains("SYSMOD: ") && text.contains("SYSMOD=wake"));
+                assert!(text.contains("OFF_X: 1\nOFF_Y: 2\nOFF_Z: -3"));
+            }
+        }
+    };
+}
+
+tests!(
+    mma8451,
+    new_mma8451,
+    [SlaveAddr::Alternative(true)],
+    Mma8451,
+    4096
+);
+tests!(
+    mma8452,
+    new_mma8452,
+    [SlaveAddr::Alternative(true)],
+    Mma8452,
+    1024
+);
+tests!(
+    mma8453,
+    new_mma8453,
+    [SlaveAddr::Alternative(true)],
+    Mma8453,
+    256
+);
+tests!(mma8652, new_mma8652, [], Mma8652, 1024);
+tests!(mma8653, new_mma8653, [], Mma8653, 256);
+
+#[test]
+fn does_not_acknowledge_other_address() {
+    let sim = SimulatedMma8x5x::new_mma8451(SlaveAddr::Default, acceleration);
+    let mut sensor = Mma8x5x::new_mma8451(sim, SlaveAddr::Alternative(true));
+    match sensor.device_id() {
+        Err(Error::I2C(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn applies_offset_correction() {
+    let sim = SimulatedMma8x5x::new_mma8452(SlaveAddr::Default, acceleration);
+    let mut sensor = Mma8x5x::new_mma8452(sim, SlaveAddr::Default);
+    // 50 * 1.96 mg = 98 mg = ~100 counts
+    sensor.set_offset_correction(50, -50, 0).unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    let m = sensor.read_unscaled().unwrap();
+    assert_eq!((612, -1124, 1024), (m.x, m.y, m.z));
+}
+
+#[test]
+fn fast_read_skips_lsb_and_wraps_to_status() {
+    let mut sim = SimulatedMma8x5x::new_mma8451(SlaveAddr::Default, acceleration);
+    let address = sim.address();
+    sim.write(address, &[Register::CTRL_REG1, BF::F_READ | BF::ACTIVE])
+        .unwrap();
+    let mut data = [0; 5];
+    sim.write_read(address, &[Register::STATUS], &mut data)
+        .unwrap();
+    let status = BF::XDR | BF::YDR | BF::ZDR | BF::XYZDR;
+    assert_eq!([status, 0x20, 0xC0, 0x40, status], data);
+}
+
+#[test]
+fn normal_read_continues_after_data() {
+    let mut sim = SimulatedMma8x5x::new_mma8652(acceleration);
+    sim.write(ADDRESS, &[Register::CTRL_REG1, BF::ACTIVE])
+        .unwrap();
+    let mut data = [0; 9];
+    sim.write_read(ADDRESS, &[Register::OUT_X_H], &mut data)
+        .unwrap();
+    assert_eq!([0x20, 0, 0xC0, 0, 0x40, 0, 0, 0, 0], data);
+}
+
+#[test]
+fn keeps_writable_registers_in_active_mode() {
+    let mut sim = SimulatedMma8x5x::new_mma8652(acceleration);
+    sim.write(ADDRESS, &[Register::CTRL_REG1, BF::ACTIVE])
+        .unwrap();
+    sim.write(ADDRESS, &[Register::PL_COUNT, 0xAB]).unwrap();
+    assert_eq!(0xAB, sim.register(Register::PL_COUNT));
+
+    let mut sim = SimulatedMma8x5x::new_mma8653(acceleration);
+    sim.write(ADDRESS, &[Register::CTRL_REG1, BF::ACTIVE])
+        .unwrap();
+    sim.write(ADDRESS, &[Register::PL_COUNT, 0xAB]).unwrap();
+    assert_eq!(0, sim.register(Register::PL_COUNT));
+}
+
+#[test]
+fn ignores_registers_not_present() {
+    let mut sim = SimulatedMma8x5x::new_mma8653(acceleration);
+    sim.write(ADDRESS, &[Register::TRANSIENT_CFG, 0x1E])
+        .unwrap();
+    sim.write(ADDRESS, &[Register::PULSE_CFG, 0x15]).unwrap();
+    sim.write(ADDRESS, &[Register::F_SETUP, BF::F_MODE0])
+        .unwrap();
+    sim.write(ADDRESS, &[Register::P_L_THS_REG, 0x10]).unwrap();
+    assert_eq!(0, sim.register(Register::TRANSIENT_CFG));
+    assert_eq!(0, sim.register(Register::PULSE_CFG));
+    assert_eq!(0, sim.register(Register::F_SETUP));
+    assert_eq!(0x84, sim.register(Register::P_L_THS_REG));
+}
+
+#[test]
+fn does_not_simulate_fifo() {
+    let mut sim = SimulatedMma8x5x::new_mma8652(acceleration);
+    sim.write(ADDRESS, &[Register::F_SETUP, BF::F_MODE0])
+        .unwrap();
+    sim.write(ADDRESS, &[Register::TRIG_CFG, 0x3C]).unwrap();
+    assert_eq!(0, sim.register(Register::F_SETUP));
+    assert_eq!(0, sim.register(Register::TRIG_CFG));
+    sim.write(ADDRESS, &[Register::CTRL_REG1, BF::F_READ | BF::ACTIVE])
+        .unwrap();
+    let mut data = [0; 5];
+    sim.write_read(ADDRESS, &[Register::STATUS], &mut data)
+        .unwrap();
+    let status = BF::XDR | BF::YDR | BF::ZDR | BF::XYZDR;
+    assert_eq!([status, 0x20, 0xC0, 0x40, status], data);
+}
+
+#[test]
+fn signals_orientation_change_on_int_pin() {
+    let axes = Cell::new((1.0, 0.0, 0.0));
+    let sim = SimulatedMma8x5x::new_mma8652(|| measurement(&axes));
+    let mut sensor = Mma8x5x::new_mma8652(&sim);
+    sensor.enable_portrait_landscape_detection().unwrap();
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            portrait_landscape: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    sensor
+        .set_interrupt_pin_routes(InterruptPinRoutes {
+            portrait_landscape: InterruptSourcePinRoute::Int1,
+            ..InterruptPinRoutes::default()
+        })
+        .unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    let mut handler = sensor.interrupt_handler(Int1(sim.int1()));
+    let status = PortraitLandscapeStatus {
+        something_changed: true,
+        z_tilt_angle_lookout: false,
+        portrait_landscape: PortraitLandscapeOrientation::LandscapeRight,
+        front_back: FrontBackOrientation::Front,
+    };
+    assert_eq!(
+        Some(Event::PortraitLandscape(status)),
+        handler.service(&mut sensor).unwrap()
+    );
+    assert_eq!(None, handler.service(&mut sensor).unwrap());
+
+    axes.set((0.0, 1.0, -0.1));
+    sim.advance(SAMPLE_PERIOD);
+    assert!(sim.int1().is_low().unwrap());
+    assert!(sim.int2().is_high().unwrap());
+    let status = PortraitLandscapeStatus {
+        portrait_landscape: PortraitLandscapeOrientation::PortraitDown,
+        front_back: FrontBackOrientation::Back,
+        ..status
+    };
+    assert_eq!(
+        Some(Event::PortraitLandscape(status)),
+        handler.service(&mut sensor).unwrap()
+    );
+    assert!(sim.int1().is_high().unwrap());
+}
+
+#[test]
+fn debounces_orientation_change() {
+    let axes = Cell::new((0.0, -1.0, 0.0));
+    let sim = SimulatedMma8x5x::new_mma8451(SlaveAddr::Default, || measurement(&axes));
+    let mut sensor = Mma8x5x::new_mma8451(&sim, SlaveAddr::Default);
+    sensor.enable_portrait_landscape_detection().unwrap();
+    sensor.set_debounce_counter(3).unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    axes.set((-1.0, 0.0, 0.0));
+    sim.advance(SAMPLE_PERIOD * 2);
+    assert!(
+        !sensor
+            .portrait_landscape_status()
+            .unwrap()
+            .something_changed
+    );
+    sim.advance(SAMPLE_PERIOD);
+    let status = sensor.portrait_landscape_status().unwrap();
+    assert!(status.something_changed);
+    assert_eq!(
+        PortraitLandscapeOrientation::LandscapeLeft,
+        status.portrait_landscape
+    );
+    assert!(
+        !sensor
+            .portrait_landscape_status()
+            .unwrap()
+            .something_changed
+    );
+}
+
+#[test]
+fn detects_freefall() {
+    let axes = Cell::new((0.0, 0.0, 1.0));
+    let sim = SimulatedMma8x5x::new_mma8451(SlaveAddr::Default, || measurement(&axes));
+    let mut sensor = Mma8x5x::new_mma8451(&sim, SlaveAddr::Default);
+    sensor
+        .set_freefall_motion_config(FreefallMotionConfig {
+            x_axis: true,
+            y_axis: true,
+            z_axis: true,
+            latch: true,
+            threshold: 0.2,
+            ..FreefallMotionConfig::default()
+        })
+        .unwrap();
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            freefall_motion: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    sim.advance(SAMPLE_PERIOD * 4);
+    assert_eq!(None, sensor.next_events().unwrap().next());
+    axes.set((0.05, -0.05, 0.1));
+    sim.advance(SAMPLE_PERIOD);
+    axes.set((0.0, 0.0, 1.0));
+    sim.advance(SAMPLE_PERIOD);
+    let mut events = sensor.next_events().unwrap();
+    assert_eq!(Some(DecodedEvent::Freefall), events.next());
+    assert_eq!(None, events.next());
+    assert_eq!(None, sensor.next_events().unwrap().next());
+}
+
+#[test]
+fn debounces_freefall_in_counter_time_steps() {
+    // 12.5Hz in normal mode: 80ms sample period and 20ms counter time step
+    let axes = Cell::new((0.0, 0.0, 1.0));
+    let sim = SimulatedMma8x5x::new_mma8451(SlaveAddr::Default, || measurement(&axes));
+    let mut sensor = Mma8x5x::new_mma8451(&sim, SlaveAddr::Default);
+    sensor.set_data_rate(OutputDataRate::Hz12_5).unwrap();
+    sensor
+        .set_freefall_motion_config(FreefallMotionConfig {
+            x_axis: true,
+            y_axis: true,
+            z_axis: true,
+            latch: true,
+            threshold: 0.2,
+            debounce_time_ms: 160.0,
+            ..FreefallMotionConfig::default()
+        })
+        .unwrap();
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            freefall_motion: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    axes.set((0.0, 0.0, 0.1));
+    sim.advance(Duration::from_millis(80));
+    assert_eq!(None, sensor.next_events().unwrap().next());
+    sim.advance(Duration::from_millis(80));
+    assert_eq!(
+        Some(DecodedEvent::Freefall),
+        sensor.next_events().unwrap().next()
+    );
+}
+
+#[test]
+fn detects_transient() {
+    let axes = Cell::new((0.0, 0.0, 1.0));
+    let sim = SimulatedMma8x5x::new_mma8452(SlaveAddr::Default, || measurement(&axes));
+    let mut sensor = Mma8x5x::new_mma8452(&sim, SlaveAddr::Default);
+    sensor
+        .set_transient_config(TransientConfig {
+            x_axis: true,
+            latch: true,
+            threshold: 0.5,
+            ..TransientConfig::default()
+        })
+        .unwrap();
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            transient: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    // The static acceleration is filtered out.
+    axes.set((0.0, 0.0, -1.0));
+    sim.advance(SAMPLE_PERIOD * 100);
+    assert_eq!(None, sensor.next_events().unwrap().next());
+    axes.set((1.0, 0.0, -1.0));
+    sim.advance(SAMPLE_PERIOD);
+    let axes = EventAxes {
+        x: true,
+        y: false,
+        z: false,
+    };
+    let mut events = sensor.next_events().unwrap();
+    assert_eq!(Some(DecodedEvent::Transient { axes }), events.next());
+    assert_eq!(None, events.next());
+}
+
+fn tap(axes: &Cell<(f32, f32, f32)>, sim: &SimulatedMma8x5x<impl FnMut() -> Measurement>) {
+    axes.set((0.0, 0.0, 2.0));
+    sim.advance(SAMPLE_PERIOD * 3);
+    axes.set((0.0, 0.0, 1.0));
+    sim.advance(SAMPLE_PERIOD);
+}
+
+#[test]
+fn detects_single_tap() {
+    let axes = Cell::new((0.0, 0.0, 1.0));
+    let sim = SimulatedMma8x5x::new_mma8652(|| measurement(&axes));
+    let mut sensor = Mma8x5x::new_mma8652(&sim);
+    sensor
+        .set_pulse_config(PulseConfig {
+            z_single: true,
+            latch: true,
+            z_threshold: 0.5,
+            time_limit_ms: 10.0,
+            ..PulseConfig::default()
+        })
+        .unwrap();
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            pulse: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    tap(&axes, &sim);
+    let mut events = sensor.next_events().unwrap();
+    let event = DecodedEvent::Tap {
+        axis: Axis::Z,
+        double: false,
+        polarity: EventPolarity::Positive,
+    };
+    assert_eq!(Some(event), events.next());
+    assert_eq!(None, events.next());
+}
+
+#[test]
+fn detects_double_tap() {
+    let axes = Cell::new((0.0, 0.0, 1.0));
+    let sim = SimulatedMma8x5x::new_mma8652(|| measurement(&axes));
+    let mut sensor = Mma8x5x::new_mma8652(&sim);
+    sensor
+        .set_pulse_config(PulseConfig {
+            z_double: true,
+            latch: true,
+            z_threshold: 0.5,
+            time_limit_ms: 10.0,
+            latency_ms: 20.0,
+            window_ms: 100.0,
+            ..PulseConfig::default()
+        })
+        .unwrap();
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            pulse: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    tap(&axes, &sim);
+    assert_eq!(None, sensor.next_events().unwrap().next());
+    sim.advance(Duration::from_millis(30));
+    tap(&axes, &sim);
+    let event = DecodedEvent::Tap {
+        axis: Axis::Z,
+        double: true,
+        polarity: EventPolarity::Positive,
+    };
+    assert_eq!(Some(event), sensor.next_events().unwrap().next());
+}
+
+/// Return whether a pulse of the given number of samples at 100Hz is
+/// detected as a tap with a 40ms time limit.
+fn detects_pulse_at_100hz(pulse_lpf_enabled: bool, samples: u32) -> bool {
+    let axes = Cell::new((0.0, 0.0, 1.0));
+    let sim = SimulatedMma8x5x::new_mma8652(|| measurement(&axes));
+    let mut sensor = Mma8x5x::new_mma8652(&sim);
+    sensor.set_data_rate(OutputDataRate::Hz100).unwrap();
+    sensor
+        .set_high_pass_filter(HighPassConfig {
+            pulse_hpf_bypass: true,
+            pulse_lpf_enabled,
+            ..HighPassConfig::default()
+        })
+        .unwrap();
+    sensor
+        .set_pulse_config(PulseConfig {
+            z_single: true,
+            latch: true,
+            z_threshold: 1.5,
+            time_limit_ms: 40.0,
+            ..PulseConfig::default()
+        })
+        .unwrap();
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            pulse: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    let period = Duration::from_millis(10);
+    axes.set((0.0, 0.0, 2.0));
+    sim.advance(period * samples);
+    axes.set((0.0, 0.0, 1.0));
+    sim.advance(period);
+    sensor.next_events().unwrap().next().is_some()
+}
+
+#[test]
+fn pulse_time_limit_matches_config() {
+    // 10ms time step with the low-pass filter enabled, 2.5ms otherwise
+    for pulse_lpf_enabled in [true, false] {
+        assert!(detects_pulse_at_100hz(pulse_lpf_enabled, 4));
+        assert!(!detects_pulse_at_100hz(pulse_lpf_enabled, 5));
+    }
+}
+
+#[test]
+fn enters_sleep_and_wakes_up() {
+    let axes = Cell::new((0.0, 0.0, 1.0));
+    let sim = SimulatedMma8x5x::new_mma8652(|| measurement(&axes));
+    let mut sensor = Mma8x5x::new_mma8652(&sim);
+    sensor.enable_auto_sleep().unwrap();
+    sensor.set_auto_sleep_count(1).unwrap();
+    sensor
+        .set_transient_config(TransientConfig {
+            x_axis: true,
+            latch: true,
+            threshold: 0.5,
+            ..TransientConfig::default()
+        })
+        .unwrap();
+    sensor
+        .set_wake_interrupts(WakeInterrupts {
+            transient: true,
+            ..WakeInterrupts::default()
+        })
+        .unwrap();
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            auto_sleep: true,
+            transient: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    sim.advance(Duration::from_millis(300));
+    assert_eq!(SystemMode::Wake, sensor.system_mode().unwrap());
+    sim.advance(Duration::from_millis(30));
+    let mut events = sensor.next_events().unwrap();
+    assert_eq!(
+        Some(DecodedEvent::SleepWakeTransition(SystemMode::Sleep)),
+        events.next()
+    );
+    assert_eq!(None, events.next());
+
+    axes.set((1.0, 0.0, 1.0));
+    sim.advance(Duration::from_millis(20));
+    let mut events = sensor.next_events().unwrap();
+    assert_eq!(
+        Some(DecodedEvent::SleepWakeTransition(SystemMode::Wake)),
+        events.next()
+    );
+    assert!(matches!(
+        events.next(),
+        Some(DecodedEvent::Transient { .. })
+    ));
+}
+
+#[test]
+fn routes_interrupts_to_pins() {
+    let sim = SimulatedMma8x5x::new_mma8652(acceleration);
+    let mut sensor = Mma8x5x::new_mma8652(&sim);
+    sensor
+        .set_interrupt_pin_polarity(InterruptPinPolarity::ActiveHigh)
+        .unwrap();
+    sensor
+        .set_enabled_interrupts(EnabledInterrupts {
+            data_ready: true,
+            ..EnabledInterrupts::default()
+        })
+        .unwrap();
+    sensor
+        .set_interrupt_pin_routes(InterruptPinRoutes {
+            data_ready: InterruptSourcePinRoute::Int1,
+            ..InterruptPinRoutes::default()
+        })
+        .unwrap();
+    let mut sensor = sensor.into_active().ok().unwrap();
+    assert!(sim.int1().is_high().unwrap());
+    assert!(sim.int2().is_low().unwrap());
+    sensor.read_unscaled().unwrap();
+    assert!(sim.int1().is_low().unwrap());
+    sim.advance(SAMPLE_PERIOD);
+    assert!(sim.int1().is_high().unwrap());
+}
+
+#[test]
+fn sets_overwrite_flags_if_data_is_not_read() {
+    let sim = SimulatedMma8x5x::new_mma8453(SlaveAddr::Default, acceleration);
+    let mut sensor = Mma8x5x::new_mma8453(&sim, SlaveAddr::Default)
+        .into_active()
+        .ok()
+        .unwrap();
+    assert!(!sensor.data_status().unwrap().xyz_overwrite);
+    sim.advance(SAMPLE_PERIOD);
+    let status = sensor.data_status().unwrap();
+    assert!(status.xyz_new_data);
+    assert!(status.xyz_overwrite);
+    sensor.read_unscaled().unwrap();
+    assert_eq!(0, sim.register(Register::STATUS));
+}
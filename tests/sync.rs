@@ -0,0 +1,139 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, new_mma8653, BitFlags as BF,
+    Register, ADDRESS,
+};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use mma8x5x::{GScale, Mma8x5x, ReadMode, SlaveAddr};
+
+fn sync_transactions(ctrl_reg1: u8, high_pass_filter: bool, fifo: bool) -> Vec<I2cTrans> {
+    let data_cfg = if high_pass_filter {
+        vec![0x12, 0x03]
+    } else {
+        vec![0x12]
+    };
+    let mut transactions = vec![
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::CTRL_REG1],
+            vec![ctrl_reg1, BF::RST | BF::MODS1, 0x02, 0x01, 0x01],
+        ),
+        I2cTrans::write_read(ADDRESS, vec![Register::XYZ_DATA_CFG], data_cfg),
+        I2cTrans::write_read(ADDRESS, vec![Register::PL_CFG], vec![0x40]),
+    ];
+    if fifo {
+        transactions.push(I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::F_SETUP],
+            vec![0x80],
+        ));
+    }
+    transactions
+}
+
+macro_rules! tests {
+    ($name:ident, $create:ident, $attach:ident($($arg:expr),*), $hpf:expr, $fifo:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_sync_from_device() {
+                let mut transactions = sync_transactions(0x18, $hpf, $fifo);
+                transactions.extend([
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x1A]),
+                    // The reset bit is not kept
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::MODS1 | BF::SLPE]),
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0x11]),
+                ]);
+                let mut sensor = $create(&transactions);
+                sensor.sync_from_device().unwrap();
+                sensor.set_read_mode(ReadMode::Fast).unwrap();
+                sensor.enable_auto_sleep().unwrap();
+                sensor.set_scale(GScale::G4).unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn sync_puts_active_device_in_standby() {
+                let mut transactions = sync_transactions(0x19, $hpf, $fifo);
+                transactions.extend([
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x18]),
+                    I2cTrans::write(ADDRESS, vec![Register::XYZ_DATA_CFG, 0x11]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x19]),
+                ]);
+                let mut sensor = $create(&transactions);
+                sensor.sync_from_device().unwrap();
+                sensor.set_scale(GScale::G4).unwrap();
+                let sensor = sensor.into_active().ok().unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn sync_in_active_mode_keeps_device_active() {
+                let mut transactions = sync_transactions(0x19, $hpf, $fifo);
+                transactions.extend(sync_transactions(0x18, $hpf, $fifo));
+                transactions.push(I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x19]));
+                let i2c = I2cMock::new(&transactions);
+                let mut sensor = Mma8x5x::$attach(i2c $(, $arg)*).ok().unwrap();
+                sensor.sync_from_device().unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_attach_active_device() {
+                let mut transactions = sync_transactions(0x19, $hpf, $fifo);
+                transactions.push(I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x18]));
+                let i2c = I2cMock::new(&transactions);
+                let sensor = Mma8x5x::$attach(i2c $(, $arg)*).ok().unwrap();
+                let sensor = sensor.into_standby().ok().unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn attach_activates_device_in_standby() {
+                let mut transactions = sync_transactions(0x18, $hpf, $fifo);
+                transactions.push(I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, 0x19]));
+                let i2c = I2cMock::new(&transactions);
+                let sensor = Mma8x5x::$attach(i2c $(, $arg)*).ok().unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn attach_returns_driver_on_error() {
+                let i2c = I2cMock::new(&[I2cTrans::write_read(
+                    ADDRESS,
+                    vec![Register::CTRL_REG1],
+                    vec![0; 5],
+                )
+                .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))]);
+                let err = Mma8x5x::$attach(i2c $(, $arg)*).err().unwrap();
+                destroy(err.dev);
+            }
+        }
+    };
+}
+
+tests!(
+    mma8451,
+    new_mma8451,
+    attach_mma8451(SlaveAddr::Alternative(true)),
+    true,
+    true
+);
+tests!(
+    mma8452,
+    new_mma8452,
+    attach_mma8452(SlaveAddr::Alternative(true)),
+    true,
+    false
+);
+tests!(
+    mma8453,
+    new_mma8453,
+    attach_mma8453(SlaveAddr::Alternative(true)),
+    true,
+    false
+);
+tests!(mma8652, new_mma8652, attach_mma8652(), true, true);
+tests!(mma8653, new_mma8653, attach_mma8653(), false, false);
@@ -0,0 +1,197 @@
+#![cfg(feature = "trace")]
+
+mod base;
+use crate::base::{BitFlags as BF, Register, ADDRESS};
+use core::fmt;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use mma8x5x::{
+    trace::{RecordingI2c, ReplayError, ReplayI2c},
+    Error, Mma8x5x, UnscaledMeasurement,
+};
+
+fn clock() -> impl FnMut() -> u64 {
+    let mut timestamp = 0;
+    move || {
+        timestamp += 1250;
+        timestamp
+    }
+}
+
+const TRACE: &str = "\
+# MMA8652 reading one sample
+1250 1D W 0D R 4A
+2500 1D W 2A 01
+
+3750 1D W 01 R 40 00 C0 00 10 00
+";
+
+#[test]
+fn records_transactions() {
+    let mock = I2cMock::new(&[
+        I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![0x4A]),
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ACTIVE]),
+        I2cTrans::write_read(
+            ADDRESS,
+            vec![Register::OUT_X_H],
+            vec![0x40, 0, 0xC0, 0, 0x10, 0],
+        ),
+    ]);
+    let i2c = RecordingI2c::new(mock, String::new(), clock());
+    let mut sensor = Mma8x5x::new_mma8652(i2c);
+    assert_eq!(0x4A, sensor.device_id().unwrap());
+    let mut sensor = sensor.into_active().ok().unwrap();
+    sensor.read_unscaled().unwrap();
+    let i2c = sensor.destroy();
+    assert!(!i2c.is_truncated());
+    let (mut mock, trace) = i2c.destroy();
+    mock.done();
+    let expected = "\
+1250 1D W 0D R 4A
+2500 1D W 2A 01
+3750 1D W 01 R 40 00 C0 00 10 00
+";
+    assert_eq!(expected, trace);
+}
+
+#[test]
+fn records_errors() {
+    let mock = I2cMock::new(&[
+        I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ACTIVE])
+            .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data)),
+    ]);
+    let i2c = RecordingI2c::new(mock, String::new(), clock());
+    let sensor = Mma8x5x::new_mma8652(i2c);
+    let sensor = sensor.into_active().err().unwrap().dev;
+    let (mut mock, trace) = sensor.destroy().destroy();
+    mock.done();
+    assert_eq!("1250 1D W 2A 01 E nack-data\n", trace);
+}
+
+/// Writer accepting only a limited number of bytes
+struct LimitedWriter {
+    remaining: usize,
+}
+
+impl fmt::Write for LimitedWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.remaining = self.remaining.checked_sub(s.len()).ok_or(fmt::Error)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn reports_truncated_trace() {
+    let mock = I2cMock::new(&[
+        I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![0x4A]),
+        I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![0x4A]),
+    ]);
+    let writer = LimitedWriter { remaining: 20 };
+    let i2c = RecordingI2c::new(mock, writer, clock());
+    let mut sensor = Mma8x5x::new_mma8652(i2c);
+    sensor.device_id().unwrap();
+    assert_eq!(0x4A, sensor.device_id().unwrap());
+    let i2c = sensor.destroy();
+    assert!(i2c.is_truncated());
+    let (mut mock, writer) = i2c.destroy();
+    mock.done();
+    assert_eq!(2, writer.remaining);
+}
+
+#[test]
+fn replays_trace() {
+    let mut sensor = Mma8x5x::new_mma8652(ReplayI2c::new(TRACE));
+    assert_eq!(0x4A, sensor.device_id().unwrap());
+    let mut sensor = sensor.into_active().ok().unwrap();
+    let expected = UnscaledMeasurement {
+        x: 1024,
+        y: -1024,
+        z: 256,
+    };
+    assert_eq!(expected, sensor.read_unscaled().unwrap());
+    assert!(sensor.destroy().is_finished());
+}
+
+#[test]
+fn replays_recorded_trace() {
+    let mock = I2cMock::new(&[
+        I2cTrans::write_read(ADDRESS, vec![Register::WHO_AM_I], vec![0x4A]),
+        I2cTrans::write_read(ADDRESS, vec![Register::INT_SOURCE], vec![0x80]),
+    ]);
+    let i2c = RecordingI2c::new(mock, String::new(), clock());
+    let mut sensor = Mma8x5x::new_mma8652(i2c);
+    sensor.device_id().unwrap();
+    let recorded = sensor.interrupt_status().unwrap();
+    let (mut mock, trace) = sensor.destroy().destroy();
+    mock.done();
+
+    let mut sensor = Mma8x5x::new_mma8652(ReplayI2c::new(&trace));
+    assert_eq!(0x4A, sensor.device_id().unwrap());
+    assert_eq!(recorded, sensor.interrupt_status().unwrap());
+    assert!(sensor.destroy().is_finished());
+}
+
+#[test]
+fn returns_recorded_error() {
+    let trace = "0 1D W 0D R 00 E nack-address\n";
+    let mut sensor = Mma8x5x::new_mma8652(ReplayI2c::new(trace));
+    match sensor.device_id() {
+        Err(Error::I2C(ReplayError::Recorded(ErrorKind::NoAcknowledge(
+            NoAcknowledgeSource::Address,
+        )))) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn detects_mismatch() {
+    let sensor = Mma8x5x::new_mma8652(ReplayI2c::new(TRACE));
+    match sensor.into_active() {
+        Err(e) => match e.error {
+            Error::I2C(ReplayError::Mismatch(2)) => (),
+            _ => panic!(),
+        },
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn detects_different_length() {
+    let trace = "0 1D W 0D R 4A 00\n";
+    let mut sensor = Mma8x5x::new_mma8652(ReplayI2c::new(trace));
+    match sensor.device_id() {
+        Err(Error::I2C(ReplayError::Mismatch(1))) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn detects_different_address() {
+    let trace = "0 1C W 0D R 4A\n";
+    let mut sensor = Mma8x5x::new_mma8652(ReplayI2c::new(trace));
+    match sensor.device_id() {
+        Err(Error::I2C(ReplayError::Mismatch(1))) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn detects_invalid_trace() {
+    let trace = "\n0 1D X 0D R 4A\n";
+    let mut sensor = Mma8x5x::new_mma8652(ReplayI2c::new(trace));
+    match sensor.device_id() {
+        Err(Error::I2C(ReplayError::InvalidTrace(2))) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn detects_end_of_trace() {
+    let replay = ReplayI2c::new("# Empty\n");
+    assert!(replay.is_finished());
+    let mut sensor = Mma8x5x::new_mma8652(replay);
+    match sensor.device_id() {
+        Err(Error::I2C(ReplayError::EndOfTrace)) => (),
+        _ => panic!(),
+    }
+}
@@ -0,0 +1,145 @@
+mod base;
+use crate::base::{
+    destroy, new_mma8451, new_mma8452, new_mma8453, new_mma8652, BitFlags as BF, Register, ADDRESS,
+};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mma8x5x::{
+    DebounceCounterMode, Error, EventPolarity, OutputDataRate, PowerMode, TransientConfig,
+    TransientSource,
+};
+
+macro_rules! tests {
+    ($name:ident, $create:ident) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn can_set_default_config() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_THS, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_COUNT, 0]),
+                ]);
+                sensor
+                    .set_transient_config(TransientConfig::default())
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn can_set_config() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(
+                        ADDRESS,
+                        vec![
+                            Register::TRANSIENT_CFG,
+                            BF::TRANS_ELE | BF::XTEFE | BF::ZTEFE | BF::HPF_BYP,
+                        ],
+                    ),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_THS, BF::TRANS_DBCNTM | 8]),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_COUNT, 8]),
+                ]);
+                sensor
+                    .set_transient_config(TransientConfig {
+                        x_axis: true,
+                        z_axis: true,
+                        latch: true,
+                        bypass_high_pass_filter: true,
+                        threshold: 0.5,
+                        debounce_counter_mode: DebounceCounterMode::Clear,
+                        debounce_time_ms: 10.0,
+                        ..TransientConfig::default()
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn debounce_time_depends_on_data_rate() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ODR1 | BF::ODR0]),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_THS, 16]),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_COUNT, 5]),
+                ]);
+                sensor.set_data_rate(OutputDataRate::Hz100).unwrap();
+                sensor
+                    .set_transient_config(TransientConfig {
+                        threshold: 1.0,
+                        debounce_time_ms: 50.0,
+                        ..TransientConfig::default()
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn debounce_time_depends_on_power_mode() {
+                let mut sensor = $create(&[
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG1, BF::ODR1 | BF::ODR0]),
+                    I2cTrans::write(ADDRESS, vec![Register::CTRL_REG2, BF::MODS1]),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_CFG, 0]),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_THS, 16]),
+                    I2cTrans::write(ADDRESS, vec![Register::TRANSIENT_COUNT, 20]),
+                ]);
+                sensor.set_data_rate(OutputDataRate::Hz100).unwrap();
+                sensor
+                    .set_wake_power_mode(PowerMode::HighResolution)
+                    .unwrap();
+                sensor
+                    .set_transient_config(TransientConfig {
+                        threshold: 1.0,
+                        debounce_time_ms: 50.0,
+                        ..TransientConfig::default()
+                    })
+                    .unwrap();
+                destroy(sensor);
+            }
+
+            #[test]
+            fn cannot_set_invalid_threshold() {
+                let mut sensor = $create(&[]);
+                assert!(matches!(
+                    sensor.set_transient_config(TransientConfig {
+                        threshold: 8.5,
+                        ..TransientConfig::default()
+                    }),
+                    Err(Error::InvalidInputData)
+                ));
+                destroy(sensor);
+            }
+
+            #[test]
+            fn cannot_set_invalid_debounce_time() {
+                let mut sensor = $create(&[]);
+                assert!(matches!(
+                    sensor.set_transient_config(TransientConfig {
+                        debounce_time_ms: 320.0,
+                        ..TransientConfig::default()
+                    }),
+                    Err(Error::InvalidInputData)
+                ));
+                destroy(sensor);
+            }
+
+            get_test!(
+                can_get_transient_source,
+                $create,
+                TRANSIENT_SRC,
+                BF::TRANS_EA | BF::YTRANSE | BF::Y_TRANS_POL,
+                transient_source,
+                TransientSource {
+                    event_active: true,
+                    y_event: true,
+                    y_polarity: EventPolarity::Negative,
+                    ..TransientSource::default()
+                }
+            );
+        }
+    };
+}
+
+tests!(mma8451, new_mma8451);
+tests!(mma8452, new_mma8452);
+tests!(mma8453, new_mma8453);
+tests!(mma8652, new_mma8652);